@@ -0,0 +1,237 @@
+//! Common actions and the permissions they require.
+//!
+//! Most actions members take in a channel need a combination of permissions
+//! rather than a single one: deleting someone else's message needs Manage
+//! Messages along with View Channel, and streaming in a voice channel needs
+//! Connect and Stream along with View Channel. Some actions can only be taken
+//! in some types of channels. [`Action::required`] holds these combinations
+//! so that they don't need to be repeated wherever an action is checked.
+//!
+//! Threads aren't supported by this version of the model, so there are no
+//! actions for creating or managing them.
+//!
+//! [`Action::required`]: enum.Action.html#method.required
+
+use super::reaction::{self, EmojiSource};
+use twilight_model::{channel::ChannelType, guild::Permissions};
+
+/// Action that a member can take in a channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Action {
+    /// Add a new reaction to a message with a Unicode or guild emoji.
+    ///
+    /// Refer to the [`reaction`] module for the other kinds of reactions.
+    ///
+    /// [`reaction`]: ../reaction/index.html
+    AddReaction,
+    /// Send a message with files attached.
+    AttachFiles,
+    /// Connect to a voice or stage channel.
+    Connect,
+    /// Create an invite to the channel.
+    CreateInvite,
+    /// Deafen members in a voice channel.
+    DeafenMembers,
+    /// Delete a message sent by another member.
+    DeleteOthersMessage,
+    /// Send a message with embedded links.
+    EmbedLinks,
+    /// Edit the channel or its permission overwrites.
+    ManageChannel,
+    /// Create, edit, or delete the channel's webhooks.
+    ManageWebhooks,
+    /// Send a message mentioning `@everyone` or `@here`.
+    MentionEveryone,
+    /// Move members out of a voice or stage channel.
+    MoveMembers,
+    /// Mute members in a voice or stage channel.
+    MuteMembers,
+    /// Pin or unpin a message.
+    PinMessage,
+    /// Read messages sent before the member opened the channel.
+    ReadMessageHistory,
+    /// Request to speak in a stage channel.
+    RequestToSpeak,
+    /// Send a message.
+    SendMessage,
+    /// Send a text-to-speech message.
+    SendTtsMessage,
+    /// Speak in a voice channel.
+    Speak,
+    /// Stream video or screen share in a voice channel, also known as going
+    /// live.
+    StartStream,
+    /// Use application commands, such as slash commands.
+    UseSlashCommands,
+    /// View the channel.
+    ViewChannel,
+}
+
+impl Action {
+    /// Permissions required to take the action in a channel of a type.
+    ///
+    /// Returns `None` if the action can't be taken in channels of the type,
+    /// such as sending a message in a voice channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twilight_model::{channel::ChannelType, guild::Permissions};
+    /// use twilight_permission_calculator::action::Action;
+    ///
+    /// assert_eq!(
+    ///     Some(Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL),
+    ///     Action::DeleteOthersMessage.required(ChannelType::GuildText),
+    /// );
+    /// assert!(Action::SendMessage.required(ChannelType::GuildVoice).is_none());
+    /// ```
+    pub fn required(self, channel_type: ChannelType) -> Option<Permissions> {
+        let text = matches!(
+            channel_type,
+            ChannelType::GuildNews | ChannelType::GuildText
+        );
+        let voice = matches!(
+            channel_type,
+            ChannelType::GuildStageVoice | ChannelType::GuildVoice
+        );
+        let guild = !matches!(channel_type, ChannelType::Group | ChannelType::Private);
+        let send = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+        let connect = Permissions::CONNECT | Permissions::VIEW_CHANNEL;
+
+        let (applies, required) = match self {
+            Self::AddReaction => (text, reaction::required(EmojiSource::Guild, false)),
+            Self::AttachFiles => (text, send | Permissions::ATTACH_FILES),
+            Self::Connect => (voice, connect),
+            Self::CreateInvite => (
+                guild && channel_type != ChannelType::GuildCategory,
+                Permissions::CREATE_INVITE | Permissions::VIEW_CHANNEL,
+            ),
+            Self::DeafenMembers => (
+                channel_type == ChannelType::GuildVoice,
+                Permissions::DEAFEN_MEMBERS | Permissions::VIEW_CHANNEL,
+            ),
+            Self::DeleteOthersMessage | Self::PinMessage => (
+                text,
+                Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL,
+            ),
+            Self::EmbedLinks => (text, send | Permissions::EMBED_LINKS),
+            Self::ManageChannel => (
+                guild,
+                Permissions::MANAGE_CHANNELS | Permissions::VIEW_CHANNEL,
+            ),
+            Self::ManageWebhooks => (
+                text,
+                Permissions::MANAGE_WEBHOOKS | Permissions::VIEW_CHANNEL,
+            ),
+            Self::MentionEveryone => (text, send | Permissions::MENTION_EVERYONE),
+            Self::MoveMembers => (voice, Permissions::MOVE_MEMBERS | Permissions::VIEW_CHANNEL),
+            Self::MuteMembers => (voice, Permissions::MUTE_MEMBERS | Permissions::VIEW_CHANNEL),
+            Self::ReadMessageHistory => (
+                text,
+                Permissions::READ_MESSAGE_HISTORY | Permissions::VIEW_CHANNEL,
+            ),
+            Self::RequestToSpeak => (
+                channel_type == ChannelType::GuildStageVoice,
+                connect | Permissions::REQUEST_TO_SPEAK,
+            ),
+            Self::SendMessage => (text, send),
+            Self::SendTtsMessage => (text, send | Permissions::SEND_TTS_MESSAGES),
+            Self::Speak => (
+                channel_type == ChannelType::GuildVoice,
+                connect | Permissions::SPEAK,
+            ),
+            Self::StartStream => (
+                channel_type == ChannelType::GuildVoice,
+                connect | Permissions::STREAM,
+            ),
+            Self::UseSlashCommands => (
+                text,
+                Permissions::USE_SLASH_COMMANDS | Permissions::VIEW_CHANNEL,
+            ),
+            Self::ViewChannel => (guild, Permissions::VIEW_CHANNEL),
+        };
+
+        if applies {
+            Some(required)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether a member with permissions in a channel can take an action in it.
+///
+/// Returns `false` if the action can't be taken in channels of the type.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{channel::ChannelType, guild::Permissions};
+/// use twilight_permission_calculator::action::{self, Action};
+///
+/// let permissions = Permissions::CONNECT | Permissions::STREAM | Permissions::VIEW_CHANNEL;
+///
+/// assert!(action::can_perform(permissions, Action::StartStream, ChannelType::GuildVoice));
+/// assert!(!action::can_perform(permissions, Action::Speak, ChannelType::GuildVoice));
+/// ```
+pub fn can_perform(permissions: Permissions, action: Action, channel_type: ChannelType) -> bool {
+    match action.required(channel_type) {
+        Some(required) => permissions.contains(required),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Action;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{channel::ChannelType, guild::Permissions};
+
+    assert_impl_all!(Action: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_required() {
+        assert_eq!(
+            Some(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            Action::SendMessage.required(ChannelType::GuildNews)
+        );
+        assert_eq!(
+            Some(Permissions::CONNECT | Permissions::STREAM | Permissions::VIEW_CHANNEL),
+            Action::StartStream.required(ChannelType::GuildVoice)
+        );
+        assert_eq!(
+            Some(Permissions::CREATE_INVITE | Permissions::VIEW_CHANNEL),
+            Action::CreateInvite.required(ChannelType::GuildStageVoice)
+        );
+        assert!(Action::CreateInvite
+            .required(ChannelType::GuildCategory)
+            .is_none());
+        assert!(Action::StartStream
+            .required(ChannelType::GuildStageVoice)
+            .is_none());
+        assert!(Action::ViewChannel.required(ChannelType::Private).is_none());
+    }
+
+    #[test]
+    fn test_can_perform() {
+        let permissions = Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL;
+
+        assert!(super::can_perform(
+            permissions,
+            Action::PinMessage,
+            ChannelType::GuildText
+        ));
+        assert!(!super::can_perform(
+            permissions,
+            Action::PinMessage,
+            ChannelType::GuildVoice
+        ));
+        assert!(!super::can_perform(
+            permissions,
+            Action::SendMessage,
+            ChannelType::GuildText
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+//! Calculating permissions against data backends that are fetched from
+//! asynchronously.
+//!
+//! This is the asynchronous counterpart of the [`source`] module, for
+//! backends such as Redis or HTTP APIs that fetch roles and overwrites lazily
+//! rather than holding everything in memory. [`AsyncPermissionSource`] is
+//! implemented by the backend, and [`AsyncSourceCalculator`] fetches from it
+//! and calculates with the same logic as [`SourceCalculator`].
+//!
+//! The trait's methods return boxed futures so that it can be implemented
+//! without any macros and used as a trait object.
+//!
+//! [`AsyncPermissionSource`]: trait.AsyncPermissionSource.html
+//! [`AsyncSourceCalculator`]: struct.AsyncSourceCalculator.html
+//! [`SourceCalculator`]: ../source/struct.SourceCalculator.html
+//! [`source`]: ../source/index.html
+
+use super::source::{self, MemberData, SourceChannel, SourceError};
+use std::{future::Future, pin::Pin};
+use twilight_model::{
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Boxed future returned by the methods of an [`AsyncPermissionSource`].
+///
+/// [`AsyncPermissionSource`]: trait.AsyncPermissionSource.html
+pub type SourceFuture<'a, T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>>;
+
+/// Backend asynchronously supplying the data that permissions are calculated
+/// from.
+///
+/// Refer to [`PermissionSource`] for what each method supplies.
+///
+/// [`PermissionSource`]: ../source/trait.PermissionSource.html
+pub trait AsyncPermissionSource {
+    /// Error returned when the backend fails to supply data.
+    type Error;
+
+    /// ID of the owner of a guild, if known.
+    fn owner_id(&self, guild_id: GuildId) -> SourceFuture<'_, Option<UserId>, Self::Error>;
+
+    /// Permissions of roles of a guild.
+    ///
+    /// The `@everyone` role has the same ID as the guild and is always
+    /// requested. Roles that aren't known are left out.
+    fn role_permissions<'a>(
+        &'a self,
+        guild_id: GuildId,
+        role_ids: &'a [RoleId],
+    ) -> SourceFuture<'a, Vec<(RoleId, Permissions)>, Self::Error>;
+
+    /// IDs of the roles of a member, not including the `@everyone` role.
+    ///
+    /// Resolves to `None` if the member isn't known.
+    fn member_role_ids(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> SourceFuture<'_, Option<Vec<RoleId>>, Self::Error>;
+
+    /// Type and permission overwrites of a channel of a guild.
+    ///
+    /// Resolves to `None` if the channel isn't known.
+    fn channel(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> SourceFuture<'_, Option<SourceChannel>, Self::Error>;
+}
+
+/// Calculate permissions with data fetched asynchronously from a source.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, UserId},
+/// };
+/// use twilight_permission_calculator::async_source::{
+///     AsyncPermissionSource, AsyncSourceCalculator,
+/// };
+///
+/// async fn can_send<S: AsyncPermissionSource + Sync>(source: &S) -> bool {
+///     AsyncSourceCalculator::new(source)
+///         .in_channel(GuildId(1), UserId(2), ChannelId(3))
+///         .await
+///         .map_or(false, |permissions| {
+///             permissions.contains(Permissions::SEND_MESSAGES)
+///         })
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSourceCalculator<'a, S: ?Sized> {
+    source: &'a S,
+}
+
+impl<'a, S: AsyncPermissionSource + ?Sized> AsyncSourceCalculator<'a, S> {
+    /// Create a calculator fetching data from a source.
+    pub fn new(source: &'a S) -> Self {
+        Self { source }
+    }
+
+    /// Calculate the guild-level permissions of a member.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`SourceCalculator::root`].
+    ///
+    /// [`SourceCalculator::root`]: ../source/struct.SourceCalculator.html#method.root
+    pub async fn root(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Permissions, SourceError<S::Error>> {
+        let (owner_id, member_roles) = self.member(guild_id, user_id).await?;
+
+        source::calculate(guild_id, user_id, owner_id, &member_roles, None)
+    }
+
+    /// Calculate the permissions of a member in a channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`SourceCalculator::in_channel`].
+    ///
+    /// [`SourceCalculator::in_channel`]: ../source/struct.SourceCalculator.html#method.in_channel
+    pub async fn in_channel(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+    ) -> Result<Permissions, SourceError<S::Error>> {
+        let channel = self
+            .source
+            .channel(guild_id, channel_id)
+            .await
+            .map_err(|source| SourceError::Source { source })?
+            .ok_or(SourceError::ChannelMissing { channel_id })?;
+        let (owner_id, member_roles) = self.member(guild_id, user_id).await?;
+
+        source::calculate(guild_id, user_id, owner_id, &member_roles, Some(&channel))
+    }
+
+    /// Fetch the owner of the guild and the permissions of the member's roles.
+    async fn member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<MemberData, SourceError<S::Error>> {
+        let role_ids = self
+            .source
+            .member_role_ids(guild_id, user_id)
+            .await
+            .map_err(|source| SourceError::Source { source })?
+            .ok_or(SourceError::MemberMissing { guild_id, user_id })?;
+        let role_ids = source::with_everyone(guild_id, role_ids);
+        let member_roles = self
+            .source
+            .role_permissions(guild_id, &role_ids)
+            .await
+            .map_err(|source| SourceError::Source { source })?;
+        let owner_id = self
+            .source
+            .owner_id(guild_id)
+            .await
+            .map_err(|source| SourceError::Source { source })?;
+
+        Ok((owner_id, member_roles))
+    }
+}
+
+impl<S: ?Sized> Clone for AsyncSourceCalculator<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: ?Sized> Copy for AsyncSourceCalculator<'_, S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncPermissionSource, AsyncSourceCalculator, SourceFuture};
+    use crate::source::{SourceChannel, SourceError};
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use std::{
+        fmt::Debug,
+        future::{self, Future},
+        sync::Arc,
+        task::{Context, Poll, Wake, Waker},
+    };
+    use twilight_model::{
+        channel::ChannelType,
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(AsyncSourceCalculator<'static, Fixture>: Clone, Copy, Debug, Send, Sync);
+    assert_obj_safe!(AsyncPermissionSource<Error = ()>);
+
+    /// Source that fails to supply the owner of any guild but guild 1.
+    #[derive(Debug)]
+    struct Fixture;
+
+    impl AsyncPermissionSource for Fixture {
+        type Error = &'static str;
+
+        fn owner_id(&self, guild_id: GuildId) -> SourceFuture<'_, Option<UserId>, Self::Error> {
+            Box::pin(future::ready(if guild_id.0 == 1 {
+                Ok(None)
+            } else {
+                Err("owner unavailable")
+            }))
+        }
+
+        fn role_permissions<'a>(
+            &'a self,
+            _: GuildId,
+            role_ids: &'a [RoleId],
+        ) -> SourceFuture<'a, Vec<(RoleId, Permissions)>, Self::Error> {
+            Box::pin(async move {
+                Ok(role_ids
+                    .iter()
+                    .map(|id| (*id, Permissions::VIEW_CHANNEL))
+                    .collect())
+            })
+        }
+
+        fn member_role_ids(
+            &self,
+            _: GuildId,
+            _: UserId,
+        ) -> SourceFuture<'_, Option<Vec<RoleId>>, Self::Error> {
+            Box::pin(future::ready(Ok(Some(Vec::new()))))
+        }
+
+        fn channel(
+            &self,
+            _: GuildId,
+            channel_id: ChannelId,
+        ) -> SourceFuture<'_, Option<SourceChannel>, Self::Error> {
+            Box::pin(future::ready(Ok(if channel_id.0 == 3 {
+                Some(SourceChannel {
+                    kind: ChannelType::GuildVoice,
+                    overwrites: Vec::new(),
+                })
+            } else {
+                None
+            })))
+        }
+    }
+
+    /// Waker that does nothing, since the fixture's futures never wait.
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Poll a future that never waits to completion.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn test_async_source_calculator() {
+        let calculator = AsyncSourceCalculator::new(&Fixture);
+
+        assert_eq!(
+            Ok(Permissions::VIEW_CHANNEL),
+            block_on(calculator.in_channel(GuildId(1), UserId(2), ChannelId(3))),
+        );
+        assert_eq!(
+            Err(SourceError::ChannelMissing {
+                channel_id: ChannelId(4),
+            }),
+            block_on(calculator.in_channel(GuildId(1), UserId(2), ChannelId(4))),
+        );
+        assert_eq!(
+            Err(SourceError::Source {
+                source: "owner unavailable",
+            }),
+            block_on(calculator.root(GuildId(5), UserId(2))),
+        );
+    }
+}
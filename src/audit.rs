@@ -0,0 +1,775 @@
+//! Scanning a guild for risky permission configurations.
+//!
+//! [`audit`] goes over a guild's roles, channels, and members and reports
+//! [`Finding`]s: places where permissions are granted more broadly than is
+//! usually intended, such as the `@everyone` role being able to mention
+//! everyone, or a role granting Administrator to many members.
+//!
+//! Permissions are considered risky if they're classified as
+//! [`RiskLevel::Elevated`] or [`RiskLevel::Dangerous`].
+//!
+//! A [`RuleSet`] decides which findings are reported and how severe they
+//! are, grouping them into a [`Report`] of critical, warning, and
+//! informational findings.
+//!
+//! [`Finding`]: enum.Finding.html
+//! [`Report`]: struct.Report.html
+//! [`RuleSet`]: struct.RuleSet.html
+//! [`RiskLevel::Dangerous`]: ../risk/enum.RiskLevel.html#variant.Dangerous
+//! [`RiskLevel::Elevated`]: ../risk/enum.RiskLevel.html#variant.Elevated
+//! [`audit`]: fn.audit.html
+
+use super::{
+    names,
+    risk::{DANGEROUS, ELEVATED},
+    CalculatorError,
+};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Permissions that are risky to grant to everyone.
+const RISKY: Permissions = Permissions::from_bits_truncate(DANGEROUS.bits() | ELEVATED.bits());
+
+/// Risky permission configuration found by an [`audit`].
+///
+/// [`audit`]: fn.audit.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Finding {
+    /// Role grants the Administrator permission.
+    AdministratorRole {
+        /// Number of the given members who have the role.
+        members: usize,
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Channel's overwrite of the `@everyone` role allows risky permissions.
+    EveryoneOverwrite {
+        /// ID of the channel.
+        channel_id: ChannelId,
+        /// Risky permissions that are allowed.
+        permissions: Permissions,
+    },
+    /// `@everyone` role has risky permissions.
+    EveryoneRole {
+        /// Risky permissions of the role.
+        permissions: Permissions,
+    },
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::AdministratorRole { members, role_id } => f.write_fmt(format_args!(
+                "role {} grants Administrator to {} member(s)",
+                role_id, members
+            )),
+            Self::EveryoneOverwrite {
+                channel_id,
+                permissions,
+            } => {
+                f.write_fmt(format_args!("channel {} allows @everyone to ", channel_id))?;
+
+                names::write_list(f, *permissions)
+            }
+            Self::EveryoneRole { permissions } => {
+                f.write_str("@everyone has ")?;
+
+                names::write_list(f, *permissions)
+            }
+        }
+    }
+}
+
+/// Audit a guild's roles, channels, and members for risky permission
+/// configurations.
+///
+/// Guild roles are given as tuples of the role's ID and permissions, and must
+/// include the `@everyone` role, which has the same ID as the guild. Channels
+/// are given as tuples of the channel's ID, type, and permission overwrites.
+/// Members are given as tuples of the member's ID and the IDs of their roles,
+/// and are only used to count the members of roles.
+///
+/// Findings about the `@everyone` role come first, followed by those about
+/// other roles in the order they were given, followed by those about
+/// channels in the order they were given.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::audit;
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::MENTION_EVERYONE | Permissions::SEND_MESSAGES),
+///     (RoleId(2), Permissions::ADMINISTRATOR),
+/// ];
+/// let webhooks = &[PermissionOverwrite {
+///     allow: Permissions::MANAGE_WEBHOOKS,
+///     deny: Permissions::empty(),
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+/// let channels = vec![(ChannelId(3), ChannelType::GuildText, &webhooks[..])];
+/// let members = vec![(UserId(4), &[RoleId(2)][..])];
+///
+/// let findings = audit::audit(GuildId(1), guild_roles, channels, members)?;
+///
+/// assert_eq!(
+///     vec![
+///         "@everyone has Mention Everyone",
+///         "role 2 grants Administrator to 1 member(s)",
+///         "channel 3 allows @everyone to Manage Webhooks",
+///     ],
+///     findings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+/// );
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+/// isn't in the guild's roles.
+///
+/// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+pub fn audit<
+    'b,
+    'c,
+    C: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    M: IntoIterator<Item = (UserId, &'c [RoleId])>,
+>(
+    guild_id: GuildId,
+    guild_roles: &[(RoleId, Permissions)],
+    channels: C,
+    members: M,
+) -> Result<Vec<Finding>, CalculatorError> {
+    let everyone_id = RoleId(guild_id.0);
+    let everyone = guild_roles
+        .iter()
+        .find(|(id, _)| *id == everyone_id)
+        .map(|(_, permissions)| *permissions)
+        .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+
+    let mut findings = Vec::new();
+
+    if everyone.intersects(RISKY) {
+        findings.push(Finding::EveryoneRole {
+            permissions: everyone & RISKY,
+        });
+    }
+
+    let mut administrators = guild_roles
+        .iter()
+        .filter(|(id, permissions)| {
+            *id != everyone_id && permissions.contains(Permissions::ADMINISTRATOR)
+        })
+        .map(|(role_id, _)| (*role_id, 0))
+        .collect::<Vec<_>>();
+
+    if !administrators.is_empty() {
+        for (_, role_ids) in members {
+            for (role_id, count) in administrators.iter_mut() {
+                if role_ids.contains(role_id) {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    findings.extend(
+        administrators
+            .into_iter()
+            .map(|(role_id, members)| Finding::AdministratorRole { members, role_id }),
+    );
+
+    for (channel_id, _, channel_overwrites) in channels {
+        let allowed = channel_overwrites
+            .iter()
+            .filter(|overwrite| overwrite.kind == PermissionOverwriteType::Role(everyone_id))
+            .fold(Permissions::empty(), |allowed, overwrite| {
+                allowed | overwrite.allow
+            });
+
+        if allowed.intersects(RISKY) {
+            findings.push(Finding::EveryoneOverwrite {
+                channel_id,
+                permissions: allowed & RISKY,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// How severe a reported [`Finding`] is.
+///
+/// Severities are ordered from least to most severe.
+///
+/// [`Finding`]: enum.Finding.html
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// Finding is worth knowing about, but is often intended.
+    Info,
+    /// Finding should be reviewed.
+    Warning,
+    /// Finding should be fixed.
+    Critical,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        })
+    }
+}
+
+/// Rule of a [`RuleSet`] reporting a kind of [`Finding`].
+///
+/// [`Finding`]: enum.Finding.html
+/// [`RuleSet`]: struct.RuleSet.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Rule {
+    /// Role grants the Administrator permission.
+    ///
+    /// Reports [`Finding::AdministratorRole`].
+    ///
+    /// [`Finding::AdministratorRole`]: enum.Finding.html#variant.AdministratorRole
+    AdministratorRole,
+    /// Channel's overwrite of the `@everyone` role allows dangerous
+    /// permissions.
+    ///
+    /// Reports [`Finding::EveryoneOverwrite`] with only the dangerous
+    /// permissions.
+    ///
+    /// [`Finding::EveryoneOverwrite`]: enum.Finding.html#variant.EveryoneOverwrite
+    EveryoneOverwriteDangerous,
+    /// Channel's overwrite of the `@everyone` role allows elevated
+    /// permissions.
+    ///
+    /// Reports [`Finding::EveryoneOverwrite`] with only the elevated
+    /// permissions.
+    ///
+    /// [`Finding::EveryoneOverwrite`]: enum.Finding.html#variant.EveryoneOverwrite
+    EveryoneOverwriteElevated,
+    /// `@everyone` role has dangerous permissions.
+    ///
+    /// Reports [`Finding::EveryoneRole`] with only the dangerous permissions.
+    ///
+    /// [`Finding::EveryoneRole`]: enum.Finding.html#variant.EveryoneRole
+    EveryoneRoleDangerous,
+    /// `@everyone` role has elevated permissions.
+    ///
+    /// Reports [`Finding::EveryoneRole`] with only the elevated permissions.
+    ///
+    /// [`Finding::EveryoneRole`]: enum.Finding.html#variant.EveryoneRole
+    EveryoneRoleElevated,
+}
+
+impl Rule {
+    /// All of the rules.
+    pub const ALL: [Self; 5] = [
+        Self::AdministratorRole,
+        Self::EveryoneOverwriteDangerous,
+        Self::EveryoneOverwriteElevated,
+        Self::EveryoneRoleDangerous,
+        Self::EveryoneRoleElevated,
+    ];
+
+    /// Severity of the rule in the default [`RuleSet`].
+    ///
+    /// [`RuleSet`]: struct.RuleSet.html
+    pub fn default_severity(self) -> Severity {
+        match self {
+            Self::AdministratorRole => Severity::Info,
+            Self::EveryoneOverwriteDangerous | Self::EveryoneRoleDangerous => Severity::Critical,
+            Self::EveryoneOverwriteElevated | Self::EveryoneRoleElevated => Severity::Warning,
+        }
+    }
+
+    /// Index of the rule in [`ALL`].
+    ///
+    /// [`ALL`]: #associatedconstant.ALL
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Set of enabled [`Rule`]s and their severities.
+///
+/// The default rule set enables every rule at its
+/// [default severity][`Rule::default_severity`].
+///
+/// # Examples
+///
+/// Treat Administrator roles as critical and ignore elevated permissions in
+/// channel overwrites:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::audit::{Finding, Rule, RuleSet, Severity};
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::MENTION_EVERYONE | Permissions::SEND_MESSAGES),
+///     (RoleId(2), Permissions::ADMINISTRATOR),
+/// ];
+/// let members = vec![(UserId(3), &[RoleId(2)][..])];
+///
+/// let report = RuleSet::new()
+///     .severity(Rule::AdministratorRole, Severity::Critical)
+///     .disable(Rule::EveryoneOverwriteElevated)
+///     .audit(GuildId(1), guild_roles, Vec::new(), members)?;
+///
+/// assert_eq!(
+///     &[Finding::AdministratorRole {
+///         members: 1,
+///         role_id: RoleId(2),
+///     }],
+///     report.critical(),
+/// );
+/// assert_eq!(
+///     &[Finding::EveryoneRole {
+///         permissions: Permissions::MENTION_EVERYONE,
+///     }],
+///     report.warning(),
+/// );
+/// assert!(report.info().is_empty());
+/// # Ok(()) }
+/// ```
+///
+/// [`Rule::default_severity`]: enum.Rule.html#method.default_severity
+/// [`Rule`]: enum.Rule.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "rule sets aren't useful if you don't audit with them"]
+pub struct RuleSet {
+    severities: [Option<Severity>; 5],
+}
+
+impl RuleSet {
+    /// Create the default rule set.
+    pub fn new() -> Self {
+        let mut severities = [None; 5];
+
+        for rule in Rule::ALL.iter() {
+            severities[rule.index()] = Some(rule.default_severity());
+        }
+
+        Self { severities }
+    }
+
+    /// Disable a rule so that its findings aren't reported.
+    pub fn disable(mut self, rule: Rule) -> Self {
+        self.severities[rule.index()] = None;
+
+        self
+    }
+
+    /// Enable a rule at its [default severity], unless it's already enabled.
+    ///
+    /// [default severity]: enum.Rule.html#method.default_severity
+    pub fn enable(mut self, rule: Rule) -> Self {
+        self.severities[rule.index()].get_or_insert(rule.default_severity());
+
+        self
+    }
+
+    /// Enable a rule at a severity.
+    pub fn severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.severities[rule.index()] = Some(severity);
+
+        self
+    }
+
+    /// Severity of a rule, if it's enabled.
+    pub const fn severity_of(&self, rule: Rule) -> Option<Severity> {
+        self.severities[rule.index()]
+    }
+
+    /// [Audit] a guild and report the findings of the enabled rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone`
+    /// role isn't in the guild's roles.
+    ///
+    /// [Audit]: fn.audit.html
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn audit<
+        'b,
+        'c,
+        C: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        M: IntoIterator<Item = (UserId, &'c [RoleId])>,
+    >(
+        &self,
+        guild_id: GuildId,
+        guild_roles: &[(RoleId, Permissions)],
+        channels: C,
+        members: M,
+    ) -> Result<Report, CalculatorError> {
+        audit(guild_id, guild_roles, channels, members).map(|findings| self.report(findings))
+    }
+
+    /// Group findings by the severity of the enabled rules reporting them.
+    ///
+    /// Findings whose permissions are covered by multiple rules are split,
+    /// with each rule reporting only the permissions it covers. Findings
+    /// keep their relative order within a severity.
+    pub fn report<I: IntoIterator<Item = Finding>>(&self, findings: I) -> Report {
+        let mut report = Report::default();
+
+        for finding in findings {
+            match finding {
+                Finding::AdministratorRole { .. } => {
+                    self.push(&mut report, Rule::AdministratorRole, finding);
+                }
+                Finding::EveryoneOverwrite {
+                    channel_id,
+                    permissions,
+                } => {
+                    for (rule, covered) in [
+                        (Rule::EveryoneOverwriteDangerous, DANGEROUS),
+                        (Rule::EveryoneOverwriteElevated, ELEVATED),
+                    ]
+                    .iter()
+                    {
+                        if permissions.intersects(*covered) {
+                            let finding = Finding::EveryoneOverwrite {
+                                channel_id,
+                                permissions: permissions & *covered,
+                            };
+
+                            self.push(&mut report, *rule, finding);
+                        }
+                    }
+                }
+                Finding::EveryoneRole { permissions } => {
+                    for (rule, covered) in [
+                        (Rule::EveryoneRoleDangerous, DANGEROUS),
+                        (Rule::EveryoneRoleElevated, ELEVATED),
+                    ]
+                    .iter()
+                    {
+                        if permissions.intersects(*covered) {
+                            let finding = Finding::EveryoneRole {
+                                permissions: permissions & *covered,
+                            };
+
+                            self.push(&mut report, *rule, finding);
+                        }
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Add a finding to a report if its rule is enabled.
+    fn push(&self, report: &mut Report, rule: Rule, finding: Finding) {
+        if let Some(severity) = self.severity_of(rule) {
+            report.findings_mut(severity).push(finding);
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Findings of a [`RuleSet`] grouped by severity.
+///
+/// [`RuleSet`]: struct.RuleSet.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    critical: Vec<Finding>,
+    info: Vec<Finding>,
+    warning: Vec<Finding>,
+}
+
+impl Report {
+    /// Findings with a severity of [`Severity::Critical`].
+    ///
+    /// [`Severity::Critical`]: enum.Severity.html#variant.Critical
+    pub fn critical(&self) -> &[Finding] {
+        &self.critical
+    }
+
+    /// Findings with a severity of [`Severity::Warning`].
+    ///
+    /// [`Severity::Warning`]: enum.Severity.html#variant.Warning
+    pub fn warning(&self) -> &[Finding] {
+        &self.warning
+    }
+
+    /// Findings with a severity of [`Severity::Info`].
+    ///
+    /// [`Severity::Info`]: enum.Severity.html#variant.Info
+    pub fn info(&self) -> &[Finding] {
+        &self.info
+    }
+
+    /// Findings with a severity.
+    pub fn findings(&self, severity: Severity) -> &[Finding] {
+        match severity {
+            Severity::Critical => &self.critical,
+            Severity::Warning => &self.warning,
+            Severity::Info => &self.info,
+        }
+    }
+
+    /// Whether there are no findings.
+    pub fn is_empty(&self) -> bool {
+        self.critical.is_empty() && self.warning.is_empty() && self.info.is_empty()
+    }
+
+    /// Iterate over the findings from most to least severe.
+    pub fn iter(&self) -> impl Iterator<Item = (Severity, &Finding)> + '_ {
+        [Severity::Critical, Severity::Warning, Severity::Info]
+            .iter()
+            .flat_map(move |severity| {
+                self.findings(*severity)
+                    .iter()
+                    .map(move |finding| (*severity, finding))
+            })
+    }
+
+    fn findings_mut(&mut self, severity: Severity) -> &mut Vec<Finding> {
+        match severity {
+            Severity::Critical => &mut self.critical,
+            Severity::Warning => &mut self.warning,
+            Severity::Info => &mut self.info,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Finding, Report, Rule, RuleSet, Severity};
+    use crate::CalculatorError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Finding::AdministratorRole: members, role_id);
+    assert_fields!(Finding::EveryoneOverwrite: channel_id, permissions);
+    assert_fields!(Finding::EveryoneRole: permissions);
+    assert_impl_all!(
+        Finding: Clone,
+        Copy,
+        Debug,
+        Display,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(Report: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(Rule: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(RuleSet: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(
+        Severity: Clone,
+        Copy,
+        Debug,
+        Display,
+        Eq,
+        Hash,
+        Ord,
+        PartialEq,
+        PartialOrd,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_audit() {
+        let guild_id = GuildId(1);
+        let guild_roles = &[
+            (
+                RoleId(1),
+                Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            ),
+            (RoleId(2), Permissions::ADMINISTRATOR),
+            (RoleId(3), Permissions::BAN_MEMBERS),
+            (RoleId(4), Permissions::ADMINISTRATOR),
+        ];
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS | Permissions::MANAGE_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::MANAGE_ROLES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+        let channels = vec![
+            (ChannelId(5), ChannelType::GuildText, &[][..]),
+            (ChannelId(6), ChannelType::GuildText, &overwrites[..]),
+        ];
+        let members = vec![
+            (UserId(7), &[RoleId(2), RoleId(3)][..]),
+            (UserId(8), &[RoleId(2)][..]),
+        ];
+
+        assert_eq!(
+            vec![
+                Finding::AdministratorRole {
+                    members: 2,
+                    role_id: RoleId(2),
+                },
+                Finding::AdministratorRole {
+                    members: 0,
+                    role_id: RoleId(4),
+                },
+                Finding::EveryoneOverwrite {
+                    channel_id: ChannelId(6),
+                    permissions: Permissions::MANAGE_MESSAGES,
+                },
+            ],
+            super::audit(guild_id, guild_roles, channels, members).unwrap(),
+        );
+
+        assert!(super::audit(
+            guild_id,
+            &[(RoleId(1), Permissions::VIEW_CHANNEL)],
+            Vec::new(),
+            Vec::new()
+        )
+        .unwrap()
+        .is_empty());
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing { guild_id }),
+            super::audit(guild_id, &guild_roles[1..], Vec::new(), Vec::new()),
+        );
+    }
+
+    #[test]
+    fn test_rule_set() {
+        let rules = RuleSet::new();
+
+        for rule in Rule::ALL.iter() {
+            assert_eq!(Some(rule.default_severity()), rules.severity_of(*rule));
+        }
+
+        let rules = rules
+            .disable(Rule::AdministratorRole)
+            .severity(Rule::EveryoneRoleElevated, Severity::Info);
+        assert_eq!(None, rules.severity_of(Rule::AdministratorRole));
+        assert_eq!(
+            Some(Severity::Info),
+            rules.severity_of(Rule::EveryoneRoleElevated)
+        );
+
+        // Enabling keeps a configured severity.
+        let rules = rules
+            .enable(Rule::AdministratorRole)
+            .enable(Rule::EveryoneRoleElevated);
+        assert_eq!(
+            Some(Severity::Info),
+            rules.severity_of(Rule::AdministratorRole)
+        );
+        assert_eq!(
+            Some(Severity::Info),
+            rules.severity_of(Rule::EveryoneRoleElevated)
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let findings = vec![
+            Finding::EveryoneRole {
+                permissions: Permissions::MANAGE_ROLES | Permissions::MENTION_EVERYONE,
+            },
+            Finding::AdministratorRole {
+                members: 2,
+                role_id: RoleId(2),
+            },
+            Finding::EveryoneOverwrite {
+                channel_id: ChannelId(3),
+                permissions: Permissions::MANAGE_MESSAGES,
+            },
+        ];
+
+        let report = RuleSet::new().report(findings.clone());
+        assert_eq!(
+            &[Finding::EveryoneRole {
+                permissions: Permissions::MANAGE_ROLES,
+            }],
+            report.critical(),
+        );
+        assert_eq!(
+            &[
+                Finding::EveryoneRole {
+                    permissions: Permissions::MENTION_EVERYONE,
+                },
+                Finding::EveryoneOverwrite {
+                    channel_id: ChannelId(3),
+                    permissions: Permissions::MANAGE_MESSAGES,
+                },
+            ],
+            report.warning(),
+        );
+        assert_eq!(&findings[1..2], report.info());
+        assert_eq!(report.info(), report.findings(Severity::Info));
+        assert_eq!(
+            vec![
+                Severity::Critical,
+                Severity::Warning,
+                Severity::Warning,
+                Severity::Info
+            ],
+            report
+                .iter()
+                .map(|(severity, _)| severity)
+                .collect::<Vec<_>>(),
+        );
+
+        let report = RuleSet::new()
+            .disable(Rule::EveryoneRoleDangerous)
+            .disable(Rule::EveryoneRoleElevated)
+            .disable(Rule::EveryoneOverwriteElevated)
+            .severity(Rule::AdministratorRole, Severity::Critical)
+            .report(findings.clone());
+        assert_eq!(&findings[1..2], report.critical());
+        assert!(report.warning().is_empty());
+        assert!(report.info().is_empty());
+        assert!(!report.is_empty());
+        assert!(RuleSet::new().report(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,353 @@
+//! Verifying a guild's permissions against a declarative blueprint.
+//!
+//! A [`Blueprint`] describes the intended access of roles in channels, such as
+//! "moderators can manage messages in #reports" and "@everyone can't see
+//! #staff". Verifying it against a guild's roles and channel overwrites finds
+//! every place where the guild has drifted from its intended setup, which is
+//! what bots that manage guilds from configuration need to reconcile them.
+//!
+//! [`Blueprint`]: struct.Blueprint.html
+
+use super::{role, CalculatorError};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId},
+};
+
+/// Intended access of a role in a channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Rule {
+    /// Permissions the role must not have.
+    absent: Permissions,
+    channel_id: ChannelId,
+    /// Permissions the role must have.
+    present: Permissions,
+    role_id: RoleId,
+}
+
+/// Declarative description of who should be able to do what in which
+/// channels.
+///
+/// The permissions of a role are those that a member with only the
+/// `@everyone` role and that role would have, so a blueprint describes the
+/// least access that each role grants. Roles that can't view a channel are
+/// considered to have no permissions in it.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::ChannelType,
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::blueprint::{Blueprint, Violation};
+///
+/// let staff = ChannelId(3);
+/// let blueprint = Blueprint::new()
+///     .deny(staff, RoleId(1), Permissions::VIEW_CHANNEL)
+///     .require(staff, RoleId(2), Permissions::VIEW_CHANNEL);
+///
+/// // The staff channel was created without any overwrites, so everyone can
+/// // see it.
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::VIEW_CHANNEL),
+///     (RoleId(2), Permissions::empty()),
+/// ];
+/// let channels = vec![(staff, ChannelType::GuildText, &[][..])];
+///
+/// let report = blueprint.verify(GuildId(1), guild_roles, channels)?;
+///
+/// assert_eq!(
+///     vec![Violation::Unexpected {
+///         channel_id: staff,
+///         permissions: Permissions::VIEW_CHANNEL,
+///         role_id: RoleId(1),
+///     }],
+///     report.violations,
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+#[must_use = "the blueprint isn't useful if you don't verify it"]
+pub struct Blueprint {
+    rules: Vec<Rule>,
+}
+
+impl Blueprint {
+    /// Create an empty blueprint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require that a role doesn't have permissions in a channel.
+    pub fn deny(
+        mut self,
+        channel_id: ChannelId,
+        role_id: RoleId,
+        permissions: Permissions,
+    ) -> Self {
+        self.rules.push(Rule {
+            absent: permissions,
+            channel_id,
+            present: Permissions::empty(),
+            role_id,
+        });
+
+        self
+    }
+
+    /// Require that a role has permissions in a channel.
+    pub fn require(
+        mut self,
+        channel_id: ChannelId,
+        role_id: RoleId,
+        permissions: Permissions,
+    ) -> Self {
+        self.rules.push(Rule {
+            absent: Permissions::empty(),
+            channel_id,
+            present: permissions,
+            role_id,
+        });
+
+        self
+    }
+
+    /// Verify the blueprint against a guild's roles and channels.
+    ///
+    /// Guild roles are given as tuples of the role's ID and permissions, and
+    /// must include the `@everyone` role, which has the same ID as the guild.
+    /// Channels are given as tuples of the channel's ID, type, and permission
+    /// overwrites.
+    ///
+    /// Violations are reported in the order the rules were added.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the guild's roles.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn verify<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        guild_id: GuildId,
+        guild_roles: &[(RoleId, Permissions)],
+        channels: U,
+    ) -> Result<BlueprintReport, CalculatorError> {
+        let everyone = guild_roles
+            .iter()
+            .find(|(id, _)| id.0 == guild_id.0)
+            .map(|(_, permissions)| *permissions)
+            .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+        let channels = channels.into_iter().collect::<Vec<_>>();
+
+        let mut violations = Vec::new();
+
+        for rule in &self.rules {
+            let role = match guild_roles.iter().find(|(id, _)| *id == rule.role_id) {
+                Some(role) => *role,
+                None => {
+                    violations.push(Violation::RoleMissing {
+                        role_id: rule.role_id,
+                    });
+
+                    continue;
+                }
+            };
+
+            let (channel_type, channel_overwrites) = match channels
+                .iter()
+                .find(|(id, _, _)| *id == rule.channel_id)
+            {
+                Some((_, channel_type, channel_overwrites)) => (*channel_type, *channel_overwrites),
+                None => {
+                    violations.push(Violation::ChannelMissing {
+                        channel_id: rule.channel_id,
+                    });
+
+                    continue;
+                }
+            };
+
+            let mut permissions =
+                role::in_channel(guild_id, everyone, role, channel_type, channel_overwrites);
+
+            // A role that can't view a channel can't use any of its other
+            // permissions in it, even if they aren't denied.
+            if !permissions.contains(Permissions::VIEW_CHANNEL) {
+                permissions = Permissions::empty();
+            }
+
+            let missing = rule.present - permissions;
+
+            if !missing.is_empty() {
+                violations.push(Violation::Missing {
+                    channel_id: rule.channel_id,
+                    permissions: missing,
+                    role_id: rule.role_id,
+                });
+            }
+
+            let unexpected = rule.absent & permissions;
+
+            if !unexpected.is_empty() {
+                violations.push(Violation::Unexpected {
+                    channel_id: rule.channel_id,
+                    permissions: unexpected,
+                    role_id: rule.role_id,
+                });
+            }
+        }
+
+        Ok(BlueprintReport { violations })
+    }
+}
+
+/// Difference between a guild and its blueprint.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Violation {
+    /// Channel in the blueprint doesn't exist in the guild.
+    ChannelMissing {
+        /// ID of the channel.
+        channel_id: ChannelId,
+    },
+    /// Role is missing permissions that the blueprint requires in a channel.
+    Missing {
+        /// ID of the channel.
+        channel_id: ChannelId,
+        /// Permissions the role is missing.
+        permissions: Permissions,
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Role in the blueprint doesn't exist in the guild.
+    RoleMissing {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Role has permissions that the blueprint denies in a channel.
+    Unexpected {
+        /// ID of the channel.
+        channel_id: ChannelId,
+        /// Permissions the role shouldn't have.
+        permissions: Permissions,
+        /// ID of the role.
+        role_id: RoleId,
+    },
+}
+
+/// Result of verifying a guild against a blueprint, created via
+/// [`Blueprint::verify`].
+///
+/// [`Blueprint::verify`]: struct.Blueprint.html#method.verify
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BlueprintReport {
+    /// Violations of the blueprint, in the order the rules were added.
+    pub violations: Vec<Violation>,
+}
+
+impl BlueprintReport {
+    /// Whether the guild matches the blueprint.
+    pub fn is_compliant(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Blueprint, BlueprintReport, Violation};
+    use crate::CalculatorError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId},
+    };
+
+    assert_fields!(BlueprintReport: violations);
+    assert_fields!(Violation::ChannelMissing: channel_id);
+    assert_fields!(Violation::Missing: channel_id, permissions, role_id);
+    assert_fields!(Violation::RoleMissing: role_id);
+    assert_fields!(Violation::Unexpected: channel_id, permissions, role_id);
+    assert_impl_all!(Blueprint: Clone, Debug, Default, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(BlueprintReport: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Violation: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_verify() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::MANAGE_MESSAGES),
+        ];
+        let staff = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let channels = vec![
+            (ChannelId(3), ChannelType::GuildText, &staff[..]),
+            (ChannelId(4), ChannelType::GuildText, &[][..]),
+        ];
+
+        let blueprint = Blueprint::new()
+            .deny(ChannelId(3), RoleId(1), Permissions::VIEW_CHANNEL)
+            .require(ChannelId(3), RoleId(2), Permissions::MANAGE_MESSAGES)
+            .require(
+                ChannelId(4),
+                RoleId(1),
+                Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            )
+            .deny(ChannelId(4), RoleId(2), Permissions::MANAGE_MESSAGES)
+            .require(ChannelId(5), RoleId(1), Permissions::VIEW_CHANNEL)
+            .require(ChannelId(4), RoleId(6), Permissions::VIEW_CHANNEL);
+
+        let report = blueprint.verify(GuildId(1), guild_roles, channels).unwrap();
+
+        assert!(!report.is_compliant());
+        assert_eq!(
+            vec![
+                Violation::Missing {
+                    channel_id: ChannelId(3),
+                    permissions: Permissions::MANAGE_MESSAGES,
+                    role_id: RoleId(2),
+                },
+                Violation::Missing {
+                    channel_id: ChannelId(4),
+                    permissions: Permissions::SEND_MESSAGES,
+                    role_id: RoleId(1),
+                },
+                Violation::Unexpected {
+                    channel_id: ChannelId(4),
+                    permissions: Permissions::MANAGE_MESSAGES,
+                    role_id: RoleId(2),
+                },
+                Violation::ChannelMissing {
+                    channel_id: ChannelId(5)
+                },
+                Violation::RoleMissing { role_id: RoleId(6) },
+            ],
+            report.violations,
+        );
+    }
+
+    #[test]
+    fn test_everyone_role_missing() {
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            Blueprint::new().verify(GuildId(1), &[], Vec::new()),
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Checking whether guild features that are unlocked by boosting can be used.
+//!
+//! Some features, such as role icons, require both a permission and the guild
+//! to have reached a boost tier. [`check`] tells the two apart, so that a
+//! member lacking a permission can be told to ask an administrator while a
+//! guild lacking boosts can be told which tier it needs.
+//!
+//! Soundboard permissions aren't known to `twilight-model`, so soundboard
+//! features aren't covered.
+//!
+//! [`check`]: fn.check.html
+
+use twilight_model::guild::{Permissions, PremiumTier};
+
+/// Guild feature that is unlocked by boosting.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum BoostFeature {
+    /// Animated guild icon.
+    AnimatedIcon,
+    /// Guild banner.
+    Banner,
+    /// Background of the guild's invites.
+    InviteSplash,
+    /// Icons displayed next to the names of members with a role.
+    RoleIcons,
+    /// Custom invite link of the guild.
+    VanityUrl,
+}
+
+impl BoostFeature {
+    /// Boost tier the guild must have reached for the feature to be
+    /// available.
+    pub fn required_tier(self) -> PremiumTier {
+        match self {
+            Self::AnimatedIcon | Self::InviteSplash => PremiumTier::Tier1,
+            Self::Banner | Self::RoleIcons => PremiumTier::Tier2,
+            Self::VanityUrl => PremiumTier::Tier3,
+        }
+    }
+
+    /// Guild-level permissions required to use the feature.
+    pub fn required_permissions(self) -> Permissions {
+        match self {
+            Self::AnimatedIcon | Self::Banner | Self::InviteSplash | Self::VanityUrl => {
+                Permissions::MANAGE_GUILD
+            }
+            Self::RoleIcons => Permissions::MANAGE_ROLES,
+        }
+    }
+}
+
+/// Whether a boost feature can be used, created via [`check`].
+///
+/// [`check`]: fn.check.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Capability {
+    /// Feature can be used.
+    Available,
+    /// Guild has reached the required boost tier, but the member is missing
+    /// permissions.
+    MissingPermissions {
+        /// Permissions the member is missing.
+        missing: Permissions,
+    },
+    /// Guild hasn't reached the required boost tier.
+    ///
+    /// This is returned regardless of the member's permissions, since no
+    /// permission makes the feature available.
+    Unavailable {
+        /// Boost tier the guild must reach.
+        required_tier: PremiumTier,
+    },
+}
+
+impl Capability {
+    /// Whether the feature can be used.
+    pub fn is_available(self) -> bool {
+        self == Self::Available
+    }
+}
+
+/// Rank of a boost tier, since tiers aren't ordered.
+fn rank(tier: PremiumTier) -> u8 {
+    match tier {
+        PremiumTier::Tier1 => 1,
+        PremiumTier::Tier2 => 2,
+        PremiumTier::Tier3 => 3,
+        _ => 0,
+    }
+}
+
+/// Check whether a member with guild-level permissions can use a boost
+/// feature in a guild with a boost tier.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::{Permissions, PremiumTier};
+/// use twilight_permission_calculator::boost::{self, BoostFeature, Capability};
+///
+/// assert_eq!(
+///     Capability::Unavailable {
+///         required_tier: PremiumTier::Tier2,
+///     },
+///     boost::check(BoostFeature::RoleIcons, Permissions::all(), PremiumTier::Tier1),
+/// );
+/// assert_eq!(
+///     Capability::MissingPermissions {
+///         missing: Permissions::MANAGE_ROLES,
+///     },
+///     boost::check(BoostFeature::RoleIcons, Permissions::empty(), PremiumTier::Tier3),
+/// );
+/// ```
+pub fn check(feature: BoostFeature, permissions: Permissions, tier: PremiumTier) -> Capability {
+    let required_tier = feature.required_tier();
+
+    if rank(tier) < rank(required_tier) {
+        return Capability::Unavailable { required_tier };
+    }
+
+    let missing = feature.required_permissions() - permissions;
+
+    if missing.is_empty() {
+        Capability::Available
+    } else {
+        Capability::MissingPermissions { missing }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoostFeature, Capability};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::{Permissions, PremiumTier};
+
+    assert_fields!(Capability::MissingPermissions: missing);
+    assert_fields!(Capability::Unavailable: required_tier);
+    assert_impl_all!(BoostFeature: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Capability: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_check() {
+        assert!(super::check(
+            BoostFeature::InviteSplash,
+            Permissions::MANAGE_GUILD,
+            PremiumTier::Tier1
+        )
+        .is_available());
+        assert_eq!(
+            Capability::Unavailable {
+                required_tier: PremiumTier::Tier3
+            },
+            super::check(
+                BoostFeature::VanityUrl,
+                Permissions::empty(),
+                PremiumTier::None
+            ),
+        );
+        assert_eq!(
+            Capability::MissingPermissions {
+                missing: Permissions::MANAGE_GUILD
+            },
+            super::check(
+                BoostFeature::Banner,
+                Permissions::MANAGE_ROLES,
+                PremiumTier::Tier2
+            ),
+        );
+    }
+}
@@ -0,0 +1,447 @@
+//! Permission state of guilds kept up to date from gateway events.
+//!
+//! Bots connected to the gateway already receive every change to a guild's
+//! roles, channels, and members. [`PermissionCache`] consumes those events and
+//! keeps a [`GuildPermissionIndex`] and the roles of each member for every
+//! guild, so that permissions can be queried by ID without the caller
+//! managing any of the calculator's inputs.
+//!
+//! Only guild channels are cached; events for direct messages and groups are
+//! ignored. Members are only known once they've been received in the guild's
+//! create event, a member chunk, or a member add or update event, or have been
+//! inserted directly, such as from REST responses.
+//!
+//! [`GuildPermissionIndex`]: ../index/struct.GuildPermissionIndex.html
+//! [`PermissionCache`]: struct.PermissionCache.html
+
+use super::{index::GuildPermissionIndex, model::channel_parts};
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{Channel, GuildChannel},
+    gateway::{
+        event::Event,
+        payload::{
+            ChannelCreate, ChannelDelete, ChannelUpdate, GuildCreate, GuildDelete, GuildUpdate,
+            MemberAdd, MemberChunk, MemberRemove, MemberUpdate, RoleCreate, RoleDelete, RoleUpdate,
+        },
+    },
+    guild::{Member, Permissions, Role},
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Update the permission cache with an event or payload.
+///
+/// This is implemented for [`Event`] and for the payloads of the events that
+/// change permissions.
+///
+/// [`Event`]: https://docs.rs/twilight-model/*/twilight_model/gateway/event/enum.Event.html
+pub trait UpdatePermissionCache {
+    /// Update the cache with the event or payload.
+    fn update(&self, cache: &mut PermissionCache);
+}
+
+/// Permission state of guilds kept up to date from gateway events.
+///
+/// # Examples
+///
+/// Update the cache with every event received and query permissions by ID:
+///
+/// ```rust,no_run
+/// use twilight_model::{
+///     gateway::event::Event,
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, UserId},
+/// };
+/// use twilight_permission_calculator::cache::PermissionCache;
+///
+/// # fn events() -> Vec<Event> { Vec::new() }
+/// let mut cache = PermissionCache::new();
+///
+/// for event in events() {
+///     cache.update(&event);
+///
+///     let permissions = cache.member_in_channel(GuildId(1), UserId(2), ChannelId(3));
+///
+///     if permissions.map_or(false, |p| p.contains(Permissions::SEND_MESSAGES)) {
+///         println!("user 2 can send messages in channel 3");
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PermissionCache {
+    guilds: HashMap<GuildId, GuildState>,
+}
+
+/// Cached permission state of one guild.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct GuildState {
+    index: GuildPermissionIndex,
+    members: HashMap<UserId, Vec<RoleId>>,
+}
+
+impl PermissionCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the cache with an event or payload.
+    pub fn update(&mut self, value: &impl UpdatePermissionCache) {
+        value.update(self);
+    }
+
+    /// Insert the index of a guild, such as one created from REST responses
+    /// before the guild's create event is received.
+    ///
+    /// The guild's cached members are kept if it was already cached.
+    pub fn insert_guild(&mut self, index: GuildPermissionIndex) {
+        let guild_id = index.guild_id();
+
+        match self.guilds.get_mut(&guild_id) {
+            Some(guild) => guild.index = index,
+            None => {
+                self.guilds.insert(
+                    guild_id,
+                    GuildState {
+                        index,
+                        members: HashMap::new(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Insert or replace the role IDs of a member of a cached guild.
+    ///
+    /// Returns whether the guild is cached.
+    pub fn insert_member(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        role_ids: &[RoleId],
+    ) -> bool {
+        match self.guilds.get_mut(&guild_id) {
+            Some(guild) => {
+                guild.members.insert(user_id, role_ids.to_vec());
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Insert or replace the role IDs of members of a cached guild, such as a
+    /// page of the REST API's list guild members route.
+    ///
+    /// Returns whether the guild is cached.
+    pub fn insert_members<'a, I: IntoIterator<Item = &'a Member>>(
+        &mut self,
+        guild_id: GuildId,
+        members: I,
+    ) -> bool {
+        match self.guilds.get_mut(&guild_id) {
+            Some(guild) => {
+                for member in members {
+                    guild.members.insert(member.user.id, member.roles.clone());
+                }
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Index of a cached guild.
+    pub fn guild(&self, guild_id: GuildId) -> Option<&GuildPermissionIndex> {
+        self.guilds.get(&guild_id).map(|guild| &guild.index)
+    }
+
+    /// Calculate the guild-level permissions of a member.
+    ///
+    /// Returns `None` if the guild or member isn't cached.
+    pub fn member_root(&self, guild_id: GuildId, user_id: UserId) -> Option<Permissions> {
+        let guild = self.guilds.get(&guild_id)?;
+        let role_ids = guild.members.get(&user_id)?;
+
+        Some(guild.index.root(user_id, role_ids))
+    }
+
+    /// Calculate the permissions of a member in a channel.
+    ///
+    /// Returns `None` if the guild, member, or channel isn't cached.
+    pub fn member_in_channel(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+    ) -> Option<Permissions> {
+        let guild = self.guilds.get(&guild_id)?;
+        let role_ids = guild.members.get(&user_id)?;
+
+        guild.index.in_channel(user_id, role_ids, channel_id)
+    }
+
+    fn insert_channel(&mut self, channel: &GuildChannel) {
+        let (channel_id, guild_id, channel_type, overwrites) = channel_parts(channel);
+
+        if let Some(guild) = guild_id.and_then(|id| self.guilds.get_mut(&id)) {
+            guild
+                .index
+                .insert_channel(channel_id, channel_type, overwrites);
+        }
+    }
+
+    fn insert_role(&mut self, guild_id: GuildId, role: &Role) {
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            guild.index.insert_role(role.id, role.permissions);
+        }
+    }
+}
+
+impl UpdatePermissionCache for Event {
+    fn update(&self, cache: &mut PermissionCache) {
+        match self {
+            Event::ChannelCreate(payload) => payload.update(cache),
+            Event::ChannelDelete(payload) => payload.update(cache),
+            Event::ChannelUpdate(payload) => payload.update(cache),
+            Event::GuildCreate(payload) => payload.update(cache),
+            Event::GuildDelete(payload) => payload.update(cache),
+            Event::GuildUpdate(payload) => payload.update(cache),
+            Event::MemberAdd(payload) => payload.update(cache),
+            Event::MemberChunk(payload) => payload.update(cache),
+            Event::MemberRemove(payload) => payload.update(cache),
+            Event::MemberUpdate(payload) => payload.update(cache),
+            Event::RoleCreate(payload) => payload.update(cache),
+            Event::RoleDelete(payload) => payload.update(cache),
+            Event::RoleUpdate(payload) => payload.update(cache),
+            _ => {}
+        }
+    }
+}
+
+impl UpdatePermissionCache for ChannelCreate {
+    fn update(&self, cache: &mut PermissionCache) {
+        if let Channel::Guild(channel) = &self.0 {
+            cache.insert_channel(channel);
+        }
+    }
+}
+
+impl UpdatePermissionCache for ChannelDelete {
+    fn update(&self, cache: &mut PermissionCache) {
+        if let Channel::Guild(channel) = &self.0 {
+            let (channel_id, guild_id, _, _) = channel_parts(channel);
+
+            if let Some(guild) = guild_id.and_then(|id| cache.guilds.get_mut(&id)) {
+                guild.index.remove_channel(channel_id);
+            }
+        }
+    }
+}
+
+impl UpdatePermissionCache for ChannelUpdate {
+    fn update(&self, cache: &mut PermissionCache) {
+        if let Channel::Guild(channel) = &self.0 {
+            cache.insert_channel(channel);
+        }
+    }
+}
+
+impl UpdatePermissionCache for GuildCreate {
+    fn update(&self, cache: &mut PermissionCache) {
+        let guild = &self.0;
+
+        // Guilds that are unavailable are sent without their roles, in which
+        // case there's nothing to calculate with.
+        let index = match GuildPermissionIndex::from_guild(guild, &guild.channels) {
+            Ok(index) => index,
+            Err(_) => return,
+        };
+
+        cache.guilds.insert(
+            guild.id,
+            GuildState {
+                index,
+                members: HashMap::new(),
+            },
+        );
+        cache.insert_members(guild.id, &guild.members);
+    }
+}
+
+impl UpdatePermissionCache for GuildDelete {
+    fn update(&self, cache: &mut PermissionCache) {
+        cache.guilds.remove(&self.id);
+    }
+}
+
+impl UpdatePermissionCache for GuildUpdate {
+    fn update(&self, cache: &mut PermissionCache) {
+        let guild = &self.0;
+
+        if let Some(state) = cache.guilds.get_mut(&guild.id) {
+            state.index.set_owner_id(Some(guild.owner_id));
+            state
+                .index
+                .retain_roles(|role_id| guild.roles.iter().any(|role| role.id == role_id));
+
+            for role in &guild.roles {
+                state.index.insert_role(role.id, role.permissions);
+            }
+        }
+    }
+}
+
+impl UpdatePermissionCache for MemberAdd {
+    fn update(&self, cache: &mut PermissionCache) {
+        cache.insert_members(self.0.guild_id, Some(&self.0));
+    }
+}
+
+impl UpdatePermissionCache for MemberChunk {
+    fn update(&self, cache: &mut PermissionCache) {
+        cache.insert_members(self.guild_id, &self.members);
+    }
+}
+
+impl UpdatePermissionCache for MemberRemove {
+    fn update(&self, cache: &mut PermissionCache) {
+        if let Some(guild) = cache.guilds.get_mut(&self.guild_id) {
+            guild.members.remove(&self.user.id);
+        }
+    }
+}
+
+impl UpdatePermissionCache for MemberUpdate {
+    fn update(&self, cache: &mut PermissionCache) {
+        cache.insert_member(self.guild_id, self.user.id, &self.roles);
+    }
+}
+
+impl UpdatePermissionCache for RoleCreate {
+    fn update(&self, cache: &mut PermissionCache) {
+        cache.insert_role(self.guild_id, &self.role);
+    }
+}
+
+impl UpdatePermissionCache for RoleDelete {
+    fn update(&self, cache: &mut PermissionCache) {
+        if let Some(guild) = cache.guilds.get_mut(&self.guild_id) {
+            guild.index.remove_role(self.role_id);
+        }
+    }
+}
+
+impl UpdatePermissionCache for RoleUpdate {
+    fn update(&self, cache: &mut PermissionCache) {
+        cache.insert_role(self.guild_id, &self.role);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PermissionCache, UpdatePermissionCache};
+    use crate::{index::GuildPermissionIndex, test_util::role};
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        gateway::{
+            event::Event,
+            payload::{GuildDelete, MemberAdd, MemberRemove, MemberUpdate, RoleCreate, RoleDelete},
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(PermissionCache: Clone, Debug, Default, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(Event: UpdatePermissionCache);
+
+    fn user(user_id: u64) -> String {
+        format!(
+            r#"{{"avatar":null,"discriminator":"0001","id":"{}","username":"user"}}"#,
+            user_id,
+        )
+    }
+
+    #[test]
+    fn test_events() {
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let index = GuildPermissionIndex::new(
+            GuildId(1),
+            &[(RoleId(1), Permissions::VIEW_CHANNEL)],
+            vec![(ChannelId(2), ChannelType::GuildText, &overwrites[..])],
+        )
+        .unwrap();
+
+        let mut cache = PermissionCache::new();
+        cache.insert_guild(index);
+
+        let member_add: MemberAdd = serde_json::from_str(&format!(
+            r#"{{"deaf":false,"guild_id":"1","joined_at":"2021-01-01T00:00:00.000000+00:00","mute":false,"roles":[],"user":{}}}"#,
+            user(3),
+        ))
+        .unwrap();
+        cache.update(&member_add);
+        assert_eq!(
+            Some(Permissions::VIEW_CHANNEL),
+            cache.member_in_channel(GuildId(1), UserId(3), ChannelId(2)),
+        );
+        assert!(cache
+            .member_in_channel(GuildId(1), UserId(3), ChannelId(4))
+            .is_none());
+        assert!(cache.member_root(GuildId(1), UserId(5)).is_none());
+
+        cache.update(&RoleCreate {
+            guild_id: GuildId(1),
+            role: role(6, Permissions::SEND_MESSAGES | Permissions::MANAGE_ROLES),
+        });
+        let member_update: MemberUpdate = serde_json::from_str(&format!(
+            r#"{{"guild_id":"1","joined_at":"2021-01-01T00:00:00.000000+00:00","nick":null,"premium_since":null,"roles":["6"],"user":{}}}"#,
+            user(3),
+        ))
+        .unwrap();
+        cache.update(&member_update);
+        assert_eq!(
+            Some(Permissions::VIEW_CHANNEL | Permissions::MANAGE_ROLES),
+            cache.member_in_channel(GuildId(1), UserId(3), ChannelId(2)),
+        );
+
+        cache.update(&RoleDelete {
+            guild_id: GuildId(1),
+            role_id: RoleId(6),
+        });
+        assert_eq!(
+            Some(Permissions::VIEW_CHANNEL),
+            cache.member_root(GuildId(1), UserId(3)),
+        );
+
+        let member_remove: MemberRemove =
+            serde_json::from_str(&format!(r#"{{"guild_id":"1","user":{}}}"#, user(3))).unwrap();
+        cache.update(&member_remove);
+        assert!(cache.member_root(GuildId(1), UserId(3)).is_none());
+
+        assert!(cache.insert_member(GuildId(1), UserId(3), &[]));
+        assert!(!cache.insert_member(GuildId(7), UserId(3), &[]));
+        assert!(cache.insert_members(GuildId(1), Some(&member_add.0)));
+        assert!(!cache.insert_members(GuildId(7), Some(&member_add.0)));
+        assert_eq!(
+            Some(Permissions::VIEW_CHANNEL),
+            cache.member_root(GuildId(1), UserId(3)),
+        );
+
+        cache.update(&GuildDelete {
+            id: GuildId(1),
+            unavailable: false,
+        });
+        assert!(cache.guild(GuildId(1)).is_none());
+        assert!(cache.member_root(GuildId(1), UserId(3)).is_none());
+    }
+}
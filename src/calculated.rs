@@ -0,0 +1,296 @@
+//! Calculated permissions in a channel with helpers for common checks.
+//!
+//! Checking bare [`Permissions`] is easy to get subtly wrong: sending a
+//! message needs View Channel as well as Send Messages, and an empty set of
+//! permissions may mean that the member is powerless in a channel or that the
+//! channel is hidden from them. [`CalculatedPermissions`] names these checks
+//! so that they read like what they mean, and [`ChannelPermissions`] makes
+//! callers branch on whether the channel is visible at all.
+//!
+//! [`CalculatedPermissions`]: struct.CalculatedPermissions.html
+//! [`ChannelPermissions`]: enum.ChannelPermissions.html
+//! [`Permissions`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Permissions.html
+
+use twilight_model::guild::Permissions;
+
+/// Permissions of a member in a channel, created via
+/// [`Calculator::in_channel_calculated`].
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::Calculator;
+///
+/// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+/// let overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::VIEW_CHANNEL,
+///     kind: PermissionOverwriteType::Member(UserId(2)),
+/// }];
+///
+/// let calculated = Calculator::new(GuildId(1), UserId(2), member_roles)
+///     .in_channel_calculated(ChannelType::GuildText, overwrites)?;
+///
+/// assert!(!calculated.can_send_messages());
+/// assert!(calculated.is_empty_due_to_hidden_channel());
+/// assert_eq!(
+///     Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+///     calculated.missing(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+/// );
+/// # Ok(()) }
+/// ```
+///
+/// [`Calculator::in_channel_calculated`]: ../struct.Calculator.html#method.in_channel_calculated
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CalculatedPermissions {
+    permissions: Permissions,
+}
+
+impl CalculatedPermissions {
+    /// Wrap calculated permissions.
+    pub const fn new(permissions: Permissions) -> Self {
+        Self { permissions }
+    }
+
+    /// Calculated permissions.
+    pub const fn permissions(self) -> Permissions {
+        self.permissions
+    }
+
+    /// Whether the member has all of the given permissions.
+    pub const fn contains(self, permissions: Permissions) -> bool {
+        self.permissions.contains(permissions)
+    }
+
+    /// Permissions out of those required that the member doesn't have.
+    pub fn missing(self, required: Permissions) -> Permissions {
+        required - self.permissions
+    }
+
+    /// Whether the member can view the channel.
+    pub const fn can_view(self) -> bool {
+        self.contains(Permissions::VIEW_CHANNEL)
+    }
+
+    /// Whether the member can view the channel and send messages in it.
+    ///
+    /// Refer to the [`send`] module for a more detailed check.
+    ///
+    /// [`send`]: ../send/index.html
+    pub const fn can_send_messages(self) -> bool {
+        self.contains(Permissions::from_bits_truncate(
+            Permissions::SEND_MESSAGES.bits() | Permissions::VIEW_CHANNEL.bits(),
+        ))
+    }
+
+    /// Whether the member can view the channel and read its message history.
+    pub const fn can_read_message_history(self) -> bool {
+        self.contains(Permissions::from_bits_truncate(
+            Permissions::READ_MESSAGE_HISTORY.bits() | Permissions::VIEW_CHANNEL.bits(),
+        ))
+    }
+
+    /// Whether the member can view the channel and connect to it.
+    pub const fn can_connect(self) -> bool {
+        self.contains(Permissions::from_bits_truncate(
+            Permissions::CONNECT.bits() | Permissions::VIEW_CHANNEL.bits(),
+        ))
+    }
+
+    /// Whether the member can view and connect to the channel and speak in
+    /// it.
+    pub const fn can_speak(self) -> bool {
+        self.contains(Permissions::from_bits_truncate(
+            Permissions::CONNECT.bits()
+                | Permissions::SPEAK.bits()
+                | Permissions::VIEW_CHANNEL.bits(),
+        ))
+    }
+
+    /// Whether the member can't use any permissions in the channel because
+    /// they can't view it, rather than because they weren't granted any.
+    ///
+    /// This is the same check as [`visibility`], so it's true whenever the
+    /// channel is [hidden]. Permissions are usually empty then, since denying
+    /// View Channel with an overwrite implicitly denies every permission, but
+    /// a member whose roles don't grant View Channel keeps their other
+    /// permissions.
+    ///
+    /// [`visibility`]: #method.visibility
+    /// [hidden]: enum.ChannelPermissions.html#variant.Hidden
+    pub const fn is_empty_due_to_hidden_channel(self) -> bool {
+        !self.can_view()
+    }
+
+    /// Whether the channel is visible to the member, along with their
+    /// permissions in it if it is.
+    pub fn visibility(self) -> ChannelPermissions {
+        if self.can_view() {
+            ChannelPermissions::Visible(self.permissions)
+        } else {
+            ChannelPermissions::Hidden
+        }
+    }
+}
+
+/// Permissions of a member in a channel, depending on whether the channel is
+/// visible to them.
+///
+/// A member who can't view a channel can't use any permission in it, so
+/// there's nothing to tell apart between a hidden channel and a visible
+/// channel in which the member has no other permissions when only looking at
+/// the permissions. Created via [`Calculator::in_channel_visibility`].
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::{calculated::ChannelPermissions, Calculator};
+///
+/// let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+/// let overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::VIEW_CHANNEL,
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+/// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+///
+/// assert_eq!(
+///     ChannelPermissions::Visible(Permissions::VIEW_CHANNEL),
+///     calculator.clone().in_channel_visibility(ChannelType::GuildText, &[])?,
+/// );
+/// assert_eq!(
+///     ChannelPermissions::Hidden,
+///     calculator.in_channel_visibility(ChannelType::GuildText, overwrites)?,
+/// );
+/// # Ok(()) }
+/// ```
+///
+/// [`Calculator::in_channel_visibility`]: ../struct.Calculator.html#method.in_channel_visibility
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChannelPermissions {
+    /// Member can't view the channel.
+    Hidden,
+    /// Member can view the channel and has these permissions in it, which
+    /// include View Channel.
+    Visible(Permissions),
+}
+
+impl ChannelPermissions {
+    /// Whether the member can't view the channel.
+    pub fn is_hidden(self) -> bool {
+        matches!(self, Self::Hidden)
+    }
+
+    /// Permissions of the member in the channel, which are empty if the
+    /// channel is hidden.
+    pub fn permissions(self) -> Permissions {
+        match self {
+            Self::Hidden => Permissions::empty(),
+            Self::Visible(permissions) => permissions,
+        }
+    }
+}
+
+impl From<CalculatedPermissions> for ChannelPermissions {
+    fn from(calculated: CalculatedPermissions) -> Self {
+        calculated.visibility()
+    }
+}
+
+impl From<CalculatedPermissions> for Permissions {
+    fn from(calculated: CalculatedPermissions) -> Self {
+        calculated.permissions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CalculatedPermissions, ChannelPermissions};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::Permissions;
+
+    assert_impl_all!(
+        CalculatedPermissions: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        ChannelPermissions: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_helpers() {
+        let calculated = CalculatedPermissions::new(
+            Permissions::CONNECT | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+        );
+        assert!(calculated.can_view());
+        assert!(calculated.can_send_messages());
+        assert!(calculated.can_connect());
+        assert!(!calculated.can_speak());
+        assert!(!calculated.can_read_message_history());
+        assert_eq!(
+            Permissions::SPEAK,
+            calculated.missing(Permissions::CONNECT | Permissions::SPEAK)
+        );
+        assert!(!calculated.is_empty_due_to_hidden_channel());
+
+        // Permissions other than View Channel aren't usable without it.
+        let invisible = CalculatedPermissions::new(Permissions::SEND_MESSAGES);
+        assert!(!invisible.can_send_messages());
+        assert!(invisible.is_empty_due_to_hidden_channel());
+        assert_eq!(Permissions::SEND_MESSAGES, Permissions::from(invisible));
+
+        let hidden = CalculatedPermissions::new(Permissions::empty());
+        assert!(hidden.permissions().is_empty());
+        assert!(hidden.is_empty_due_to_hidden_channel());
+    }
+
+    #[test]
+    fn test_visibility() {
+        let visible = CalculatedPermissions::new(Permissions::VIEW_CHANNEL);
+        assert_eq!(
+            ChannelPermissions::Visible(Permissions::VIEW_CHANNEL),
+            visible.visibility()
+        );
+        assert!(!visible.visibility().is_hidden());
+
+        // Channels are hidden without View Channel even if other permissions
+        // remain, such as when the `@everyone` role's overwrite denies it.
+        let hidden =
+            ChannelPermissions::from(CalculatedPermissions::new(Permissions::SEND_MESSAGES));
+        assert_eq!(ChannelPermissions::Hidden, hidden);
+        assert!(hidden.is_hidden());
+        assert!(hidden.permissions().is_empty());
+    }
+}
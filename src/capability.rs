@@ -0,0 +1,333 @@
+//! Checking whether a member can take an action right now, including
+//! restrictions that aren't permissions.
+//!
+//! Having the permissions for an action isn't always enough to take it. A
+//! member who is timed out, who hasn't passed the guild's membership
+//! screening, or who doesn't meet the guild's verification level can only
+//! view channels and read their history, and age-restricted channels can't
+//! be used by members who haven't confirmed that they're adults. [`Gates`]
+//! describes these restrictions and [`check`] applies them on top of the
+//! permissions required by an [`Action`].
+//!
+//! [`Action`]: ../action/enum.Action.html
+//! [`Gates`]: struct.Gates.html
+//! [`check`]: fn.check.html
+
+use super::{action::Action, PERMISSIONS_TIMED_OUT};
+use twilight_model::{
+    channel::ChannelType,
+    guild::{Permissions, VerificationLevel},
+};
+
+/// Restrictions on a member in a channel that aren't permissions.
+///
+/// No restrictions apply by default.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Gates {
+    age_restricted: bool,
+    age_verified: bool,
+    bot: bool,
+    pending: bool,
+    timed_out: bool,
+    unverified: Option<VerificationLevel>,
+}
+
+impl Gates {
+    /// Create gates without any restrictions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the channel is age-restricted, also known as NSFW.
+    pub fn age_restricted(mut self, age_restricted: bool) -> Self {
+        self.age_restricted = age_restricted;
+
+        self
+    }
+
+    /// Set whether the member has confirmed that they're an adult, allowing
+    /// them to use age-restricted channels.
+    pub fn age_verified(mut self, age_verified: bool) -> Self {
+        self.age_verified = age_verified;
+
+        self
+    }
+
+    /// Set whether the member is a bot.
+    ///
+    /// Bots aren't subject to membership screening, verification levels, or
+    /// age restrictions.
+    pub fn bot(mut self, bot: bool) -> Self {
+        self.bot = bot;
+
+        self
+    }
+
+    /// Set whether the member hasn't passed the guild's membership screening
+    /// yet.
+    pub fn pending(mut self, pending: bool) -> Self {
+        self.pending = pending;
+
+        self
+    }
+
+    /// Set whether the member is timed out.
+    ///
+    /// [`Calculator::capability`] sets this from the member's timeout.
+    ///
+    /// [`Calculator::capability`]: ../struct.Calculator.html#method.capability
+    pub fn timed_out(mut self, timed_out: bool) -> Self {
+        self.timed_out = timed_out;
+
+        self
+    }
+
+    /// Set that the member doesn't meet the guild's verification level,
+    /// which is `level`.
+    ///
+    /// Verification levels only apply to members without any roles, which
+    /// [`Calculator::capability`] accounts for.
+    ///
+    /// [`Calculator::capability`]: ../struct.Calculator.html#method.capability
+    pub fn unverified(mut self, level: VerificationLevel) -> Self {
+        self.unverified.replace(level);
+
+        self
+    }
+
+    /// Apply what a calculator knows about the member: whether they're timed
+    /// out and whether they're exempt from the verification level.
+    pub(crate) fn member(mut self, timed_out: bool, verification_exempt: bool) -> Self {
+        self.timed_out |= timed_out;
+
+        if verification_exempt {
+            self.unverified.take();
+        }
+
+        self
+    }
+}
+
+/// Whether a member can take an action in a channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Capability {
+    /// Member can't take the action.
+    No(Restriction),
+    /// Member can take the action.
+    Yes,
+}
+
+impl Capability {
+    /// Whether the member can take the action.
+    pub fn is_yes(self) -> bool {
+        matches!(self, Self::Yes)
+    }
+}
+
+/// Reason that a member can't take an action in a channel.
+///
+/// Refer to [`check`] for which restriction is returned when more than one
+/// applies.
+///
+/// [`check`]: fn.check.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Restriction {
+    /// Channel is age-restricted and the member hasn't confirmed that they're
+    /// an adult.
+    AgeRestricted,
+    /// Action can't be taken in channels of the type.
+    ChannelType {
+        /// Type of the channel.
+        kind: ChannelType,
+    },
+    /// Member is missing permissions required to take the action.
+    MissingPermissions {
+        /// Missing permissions.
+        missing: Permissions,
+    },
+    /// Member hasn't passed the guild's membership screening.
+    Pending,
+    /// Member is timed out.
+    TimedOut,
+    /// Member doesn't meet the guild's verification level.
+    VerificationLevel {
+        /// Verification level of the guild.
+        level: VerificationLevel,
+    },
+}
+
+/// Whether a member with permissions in a channel can take an action in it
+/// given the restrictions on them.
+///
+/// Timeouts, membership screening, and verification levels only restrict
+/// actions that need more than viewing the channel and reading its history.
+/// Restrictions are checked in this order, returning the first that applies:
+/// the channel's type, the channel's age restriction, the member's timeout,
+/// membership screening, the verification level, and lastly the member's
+/// permissions.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{channel::ChannelType, guild::Permissions};
+/// use twilight_permission_calculator::{
+///     action::Action,
+///     capability::{self, Capability, Gates, Restriction},
+/// };
+///
+/// let permissions = Permissions::READ_MESSAGE_HISTORY
+///     | Permissions::SEND_MESSAGES
+///     | Permissions::VIEW_CHANNEL;
+/// let gates = Gates::new().pending(true);
+///
+/// assert_eq!(
+///     Capability::No(Restriction::Pending),
+///     capability::check(permissions, Action::SendMessage, ChannelType::GuildText, gates),
+/// );
+/// assert!(capability::check(
+///     permissions,
+///     Action::ReadMessageHistory,
+///     ChannelType::GuildText,
+///     gates,
+/// )
+/// .is_yes());
+/// ```
+pub fn check(
+    permissions: Permissions,
+    action: Action,
+    channel_type: ChannelType,
+    gates: Gates,
+) -> Capability {
+    let required = match action.required(channel_type) {
+        Some(required) => required,
+        None => return Capability::No(Restriction::ChannelType { kind: channel_type }),
+    };
+
+    if gates.age_restricted && !gates.age_verified && !gates.bot {
+        return Capability::No(Restriction::AgeRestricted);
+    }
+
+    if !PERMISSIONS_TIMED_OUT.contains(required) {
+        if gates.timed_out {
+            return Capability::No(Restriction::TimedOut);
+        }
+
+        if gates.pending && !gates.bot {
+            return Capability::No(Restriction::Pending);
+        }
+
+        if let Some(level) = gates.unverified.filter(|_| !gates.bot) {
+            return Capability::No(Restriction::VerificationLevel { level });
+        }
+    }
+
+    let missing = required - permissions;
+
+    if missing.is_empty() {
+        Capability::Yes
+    } else {
+        Capability::No(Restriction::MissingPermissions { missing })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Capability, Gates, Restriction};
+    use crate::action::Action;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::ChannelType,
+        guild::{Permissions, VerificationLevel},
+    };
+
+    assert_impl_all!(Capability: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        Gates: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(Restriction: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    const PERMISSIONS: Permissions = Permissions::from_bits_truncate(
+        Permissions::READ_MESSAGE_HISTORY.bits()
+            | Permissions::SEND_MESSAGES.bits()
+            | Permissions::VIEW_CHANNEL.bits(),
+    );
+
+    fn check(action: Action, gates: Gates) -> Capability {
+        super::check(PERMISSIONS, action, ChannelType::GuildText, gates)
+    }
+
+    #[test]
+    fn test_no_gates() {
+        assert!(check(Action::SendMessage, Gates::new()).is_yes());
+        assert_eq!(
+            Capability::No(Restriction::MissingPermissions {
+                missing: Permissions::ATTACH_FILES
+            }),
+            check(Action::AttachFiles, Gates::new())
+        );
+        assert_eq!(
+            Capability::No(Restriction::ChannelType {
+                kind: ChannelType::GuildVoice
+            }),
+            super::check(
+                PERMISSIONS,
+                Action::SendMessage,
+                ChannelType::GuildVoice,
+                Gates::new()
+            )
+        );
+    }
+
+    #[test]
+    fn test_read_only_gates() {
+        let gates = [
+            (Gates::new().timed_out(true), Restriction::TimedOut),
+            (Gates::new().pending(true), Restriction::Pending),
+            (
+                Gates::new().unverified(VerificationLevel::High),
+                Restriction::VerificationLevel {
+                    level: VerificationLevel::High,
+                },
+            ),
+        ];
+
+        for (gates, restriction) in gates.iter().copied() {
+            assert_eq!(
+                Capability::No(restriction),
+                check(Action::SendMessage, gates)
+            );
+            assert!(check(Action::ReadMessageHistory, gates).is_yes());
+        }
+
+        // Bots aren't screened or verified, but can be timed out.
+        let bot = Gates::new().bot(true);
+        assert!(check(Action::SendMessage, bot.pending(true)).is_yes());
+        assert!(check(Action::SendMessage, bot.unverified(VerificationLevel::Low)).is_yes());
+        assert_eq!(
+            Capability::No(Restriction::TimedOut),
+            check(Action::SendMessage, bot.timed_out(true))
+        );
+    }
+
+    #[test]
+    fn test_age_restricted() {
+        let gates = Gates::new().age_restricted(true);
+
+        assert_eq!(
+            Capability::No(Restriction::AgeRestricted),
+            check(Action::ViewChannel, gates)
+        );
+        assert!(check(Action::ViewChannel, gates.age_verified(true)).is_yes());
+        assert!(check(Action::ViewChannel, gates.bot(true)).is_yes());
+    }
+}
@@ -0,0 +1,282 @@
+//! Previewing how permissions change when a channel is synced with its
+//! category.
+//!
+//! Syncing a channel - via the "Sync Now" button in the Discord client -
+//! replaces all of the channel's permission overwrites with the overwrites of
+//! its parent category. [`CategorySync`] calculates the permissions of every
+//! role, and optionally of tracked members, with both sets of overwrites so
+//! that the change can be confirmed before it's made.
+//!
+//! [`CategorySync`]: struct.CategorySync.html
+
+use super::{diff::PermissionDiff, role, Calculator, CalculatorError};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Calculate how the permissions of roles and members in a channel change
+/// when it's synced with its category.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::category_sync::CategorySync;
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+///     (RoleId(2), Permissions::MANAGE_MESSAGES),
+/// ];
+///
+/// // The channel currently denies moderators the Manage Messages permission
+/// // while the category doesn't have any overwrites.
+/// let channel_overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::MANAGE_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(2)),
+/// }];
+///
+/// let preview = CategorySync::new(GuildId(1), guild_roles, ChannelType::GuildText)
+///     .preview(channel_overwrites, &[])?;
+///
+/// assert_eq!(1, preview.roles.len());
+/// assert_eq!(RoleId(2), preview.roles[0].0);
+/// assert_eq!(Permissions::MANAGE_MESSAGES, preview.roles[0].1.added);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the category sync isn't useful if you don't preview it"]
+pub struct CategorySync<'a> {
+    channel_type: ChannelType,
+    guild_id: GuildId,
+    guild_roles: &'a [(RoleId, Permissions)],
+    members: &'a [(UserId, &'a [(RoleId, Permissions)])],
+}
+
+impl<'a> CategorySync<'a> {
+    /// Create a preview of syncing a channel of a guild with its category.
+    ///
+    /// The guild's roles must include the `@everyone` role, which has the same
+    /// ID as the guild.
+    pub fn new(
+        guild_id: GuildId,
+        guild_roles: &'a [(RoleId, Permissions)],
+        channel_type: ChannelType,
+    ) -> Self {
+        Self {
+            channel_type,
+            guild_id,
+            guild_roles,
+            members: &[],
+        }
+    }
+
+    /// Set the members whose permissions are tracked, along with their roles.
+    ///
+    /// Member roles are given like they are to [`Calculator::new`].
+    ///
+    /// [`Calculator::new`]: ../struct.Calculator.html#method.new
+    pub fn members(mut self, members: &'a [(UserId, &'a [(RoleId, Permissions)])]) -> Self {
+        self.members = members;
+
+        self
+    }
+
+    /// Calculate the difference in permissions between the channel's current
+    /// overwrites and the overwrites of its category.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the guild's roles or the roles of a tracked member.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn preview(
+        &self,
+        channel_overwrites: &[PermissionOverwrite],
+        category_overwrites: &[PermissionOverwrite],
+    ) -> Result<SyncPreview, CalculatorError> {
+        let everyone = self
+            .guild_roles
+            .iter()
+            .find(|(id, _)| id.0 == self.guild_id.0)
+            .map(|(_, permissions)| *permissions)
+            .ok_or(CalculatorError::EveryoneRoleMissing {
+                guild_id: self.guild_id,
+            })?;
+
+        let roles = self
+            .guild_roles
+            .iter()
+            .filter_map(|role| {
+                let diff = self.role_diff(everyone, *role, channel_overwrites, category_overwrites);
+
+                Some((role.0, diff)).filter(|(_, diff)| !diff.is_empty())
+            })
+            .collect();
+
+        let mut members = Vec::new();
+
+        for (user_id, member_roles) in self.members {
+            let calculator = Calculator::new(self.guild_id, *user_id, member_roles);
+            let before = calculator
+                .clone()
+                .in_channel(self.channel_type, channel_overwrites)?;
+            let after = calculator.in_channel(self.channel_type, category_overwrites)?;
+            let diff = PermissionDiff::new(before, after);
+
+            if !diff.is_empty() {
+                members.push((*user_id, diff));
+            }
+        }
+
+        Ok(SyncPreview { members, roles })
+    }
+
+    /// Calculate the difference in a role's permissions between two sets of
+    /// overwrites.
+    fn role_diff(
+        &self,
+        everyone: Permissions,
+        role: (RoleId, Permissions),
+        before: &[PermissionOverwrite],
+        after: &[PermissionOverwrite],
+    ) -> PermissionDiff {
+        let calculate = |overwrites| {
+            role::in_channel(self.guild_id, everyone, role, self.channel_type, overwrites)
+        };
+
+        PermissionDiff::new(calculate(before), calculate(after))
+    }
+}
+
+/// Changes in permissions of roles and members when a channel is synced with
+/// its category.
+///
+/// Only roles and members whose permissions change are included.
+///
+/// Created via [`CategorySync::preview`].
+///
+/// [`CategorySync::preview`]: struct.CategorySync.html#method.preview
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SyncPreview {
+    /// Tracked members whose permissions change, in the order they were given.
+    pub members: Vec<(UserId, PermissionDiff)>,
+    /// Roles whose permissions change, in the order they were given.
+    pub roles: Vec<(RoleId, PermissionDiff)>,
+}
+
+impl SyncPreview {
+    /// Whether syncing the channel doesn't change the permissions of any role
+    /// or tracked member.
+    pub fn is_unchanged(&self) -> bool {
+        self.members.is_empty() && self.roles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CategorySync, SyncPreview};
+    use crate::{diff::PermissionDiff, CalculatorError};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(SyncPreview: members, roles);
+    assert_impl_all!(CategorySync<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(SyncPreview: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_preview() {
+        let guild_roles = &[
+            (
+                RoleId(1),
+                Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            ),
+            (RoleId(2), Permissions::empty()),
+            (RoleId(3), Permissions::ADMINISTRATOR),
+        ];
+        let channel_overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        }];
+        let category_overwrites = &[PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(4)),
+        }];
+        let member_roles = &[guild_roles[0], guild_roles[1]];
+        let members = &[
+            (UserId(4), &member_roles[..]),
+            (UserId(5), &guild_roles[..1]),
+        ];
+
+        let preview = CategorySync::new(GuildId(1), guild_roles, ChannelType::GuildText)
+            .members(members)
+            .preview(channel_overwrites, category_overwrites)
+            .unwrap();
+
+        let visible = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+        assert!(!preview.is_unchanged());
+        assert_eq!(
+            vec![(
+                RoleId(2),
+                PermissionDiff::new(Permissions::empty(), visible)
+            )],
+            preview.roles,
+        );
+        assert_eq!(
+            vec![(
+                UserId(4),
+                PermissionDiff::new(Permissions::empty(), visible | Permissions::ADD_REACTIONS),
+            )],
+            preview.members,
+        );
+    }
+
+    #[test]
+    fn test_preview_unchanged() {
+        let guild_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+
+        let preview = CategorySync::new(GuildId(1), guild_roles, ChannelType::GuildText)
+            .preview(overwrites, overwrites)
+            .unwrap();
+
+        assert!(preview.is_unchanged());
+    }
+
+    #[test]
+    fn test_everyone_role_missing() {
+        let guild_roles = &[(RoleId(2), Permissions::VIEW_CHANNEL)];
+
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            CategorySync::new(GuildId(1), guild_roles, ChannelType::GuildText).preview(&[], &[]),
+        );
+    }
+}
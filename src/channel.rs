@@ -0,0 +1,563 @@
+//! Calculating the permissions of many members in one channel.
+//!
+//! Features such as listing everyone who can speak in a channel need the
+//! permissions of every member in the same channel. [`ChannelCalculator`]
+//! groups the channel's overwrites by role and member once, so that each
+//! member's permissions are calculated from only the overwrites that apply to
+//! them rather than from every overwrite of the channel.
+//!
+//! [`is_channel_public`] checks whether a channel is visible to everyone, and
+//! [`allowed_in`] lists the permissions that can apply in a type of channel.
+//!
+//! [`ChannelCalculator`]: struct.ChannelCalculator.html
+//! [`allowed_in`]: fn.allowed_in.html
+//! [`is_channel_public`]: fn.is_channel_public.html
+
+use super::{
+    Calculator, CalculatorError, PERMISSIONS_ROOT, PERMISSIONS_STAGE, PERMISSIONS_TEXT,
+    PERMISSIONS_VOICE,
+};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Permissions that a member can have in a type of channel.
+///
+/// These are the permissions left after [`Calculator::in_channel`] removes
+/// those that can't be used in the channel: guild-level permissions such as
+/// Ban Members are never included, text permissions are only included in text
+/// and news channels, and voice permissions are only included in voice
+/// channels and, for those used to join and moderate a stage, stage channels.
+/// Categories have no text or voice permissions.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{channel::ChannelType, guild::Permissions};
+/// use twilight_permission_calculator::channel;
+///
+/// let text = channel::allowed_in(ChannelType::GuildText);
+/// assert!(text.contains(Permissions::SEND_MESSAGES));
+/// assert!(!text.contains(Permissions::CONNECT));
+///
+/// let stage = channel::allowed_in(ChannelType::GuildStageVoice);
+/// assert!(stage.contains(Permissions::REQUEST_TO_SPEAK));
+/// assert!(!stage.contains(Permissions::SPEAK));
+/// ```
+///
+/// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+pub fn allowed_in(channel_type: ChannelType) -> Permissions {
+    let mut permissions = Permissions::all() - PERMISSIONS_ROOT;
+
+    if !matches!(
+        channel_type,
+        ChannelType::GuildNews | ChannelType::GuildText
+    ) {
+        permissions.remove(PERMISSIONS_TEXT);
+    }
+
+    match channel_type {
+        ChannelType::GuildStageVoice => permissions.remove(PERMISSIONS_VOICE - PERMISSIONS_STAGE),
+        ChannelType::GuildVoice => permissions.remove(PERMISSIONS_STAGE - PERMISSIONS_VOICE),
+        _ => permissions.remove(PERMISSIONS_STAGE | PERMISSIONS_VOICE),
+    }
+
+    permissions
+}
+
+/// Whether the `@everyone` role can view a channel, so that every member of
+/// the guild can see it.
+///
+/// The `@everyone` role can view the channel if it has the View Channel
+/// permission at the guild level and the channel's overwrite of the
+/// `@everyone` role doesn't deny it, or if the overwrite allows it. Roles with
+/// the Administrator permission can view every channel. Overwrites of other
+/// roles and of members don't matter.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::channel;
+///
+/// let guild_id = GuildId(1);
+/// let staff_only = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::VIEW_CHANNEL,
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+///
+/// assert!(channel::is_channel_public(guild_id, Permissions::VIEW_CHANNEL, &[]));
+/// assert!(!channel::is_channel_public(guild_id, Permissions::VIEW_CHANNEL, staff_only));
+/// ```
+pub fn is_channel_public<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+    guild_id: GuildId,
+    everyone: Permissions,
+    channel_overwrites: U,
+) -> bool {
+    if everyone.contains(Permissions::ADMINISTRATOR) {
+        return true;
+    }
+
+    let mut allow = Permissions::empty();
+    let mut deny = Permissions::empty();
+
+    for overwrite in channel_overwrites {
+        if overwrite.kind == PermissionOverwriteType::Role(RoleId(guild_id.0)) {
+            allow.insert(overwrite.allow);
+            deny.insert(overwrite.deny);
+        }
+    }
+
+    ((everyone - deny) | allow).contains(Permissions::VIEW_CHANNEL)
+}
+
+/// Calculate the permissions of many members in one channel.
+///
+/// # Examples
+///
+/// List the members who can connect to a voice channel:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::channel::ChannelCalculator;
+///
+/// let everyone = (RoleId(1), Permissions::CONNECT | Permissions::VIEW_CHANNEL);
+/// let muted = (RoleId(2), Permissions::empty());
+/// let overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::CONNECT,
+///     kind: PermissionOverwriteType::Role(RoleId(2)),
+/// }];
+/// let alice = [everyone];
+/// let bob = [everyone, muted];
+///
+/// let calculator = ChannelCalculator::new(GuildId(1), ChannelType::GuildVoice, overwrites);
+/// let members = vec![(UserId(3), &alice[..]), (UserId(4), &bob[..])];
+/// let permissions = calculator.members(members)?;
+///
+/// assert!(permissions[&UserId(3)].contains(Permissions::CONNECT));
+/// assert!(!permissions[&UserId(4)].contains(Permissions::CONNECT));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "the channel calculator isn't useful if you don't calculate with it"]
+pub struct ChannelCalculator {
+    channel_type: ChannelType,
+    everyone: Option<PermissionOverwrite>,
+    guild_id: GuildId,
+    members: HashMap<UserId, PermissionOverwrite>,
+    owner_id: Option<UserId>,
+    roles: HashMap<RoleId, PermissionOverwrite>,
+}
+
+impl ChannelCalculator {
+    /// Create a calculator for members of a guild in one of its channels.
+    ///
+    /// Overwrites are grouped by role and member, and overwrites for the same
+    /// role or member are combined.
+    pub fn new<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        guild_id: GuildId,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Self {
+        let mut everyone = None;
+        let mut members = HashMap::new();
+        let mut roles = HashMap::new();
+
+        for overwrite in channel_overwrites {
+            let entry = match overwrite.kind {
+                PermissionOverwriteType::Member(user_id) => {
+                    members.entry(user_id).or_insert_with(|| empty(overwrite))
+                }
+                PermissionOverwriteType::Role(role_id) if role_id.0 == guild_id.0 => {
+                    everyone.get_or_insert_with(|| empty(overwrite))
+                }
+                PermissionOverwriteType::Role(role_id) => {
+                    roles.entry(role_id).or_insert_with(|| empty(overwrite))
+                }
+            };
+
+            entry.allow.insert(overwrite.allow);
+            entry.deny.insert(overwrite.deny);
+        }
+
+        Self {
+            channel_type,
+            everyone,
+            guild_id,
+            members,
+            owner_id: None,
+            roles,
+        }
+    }
+
+    /// Configure the ID of the owner of the guild.
+    ///
+    /// Refer to the documentation for [`Calculator::owner_id`].
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id.replace(owner_id);
+
+        self
+    }
+
+    /// Calculate the permissions of a member in the channel.
+    ///
+    /// The member's roles are given like in [`Calculator::new`], and must
+    /// include the `@everyone` role.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::new`]: ../struct.Calculator.html#method.new
+    pub fn member(
+        &self,
+        user_id: UserId,
+        member_roles: &[(RoleId, Permissions)],
+    ) -> Result<Permissions, CalculatorError> {
+        let mut calculator = Calculator::new(self.guild_id, user_id, member_roles);
+
+        if let Some(owner_id) = self.owner_id {
+            calculator = calculator.owner_id(owner_id);
+        }
+
+        let root = calculator.root()?;
+
+        Ok(self.in_channel_with_root(&calculator, root))
+    }
+
+    /// Calculate the permissions of each of a set of members in the channel.
+    ///
+    /// Members are given as tuples of the member's ID and roles. If a member
+    /// is given more than once, then the last entry for them is used.
+    ///
+    /// With the `rayon` feature enabled, members are calculated in parallel.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error returned by [`member`]. Without the `rayon` feature,
+    /// this is the first error.
+    ///
+    /// [`member`]: #method.member
+    pub fn members<'b, I: IntoIterator<Item = (UserId, &'b [(RoleId, Permissions)])>>(
+        &self,
+        members: I,
+    ) -> Result<HashMap<UserId, Permissions>, CalculatorError> {
+        let calculate =
+            |(user_id, member_roles)| Ok((user_id, self.member(user_id, member_roles)?));
+
+        #[cfg(feature = "rayon")]
+        let permissions = members
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(calculate)
+            .collect();
+
+        #[cfg(not(feature = "rayon"))]
+        let permissions = members.into_iter().map(calculate).collect();
+
+        permissions
+    }
+
+    /// IDs of the members of a set who have permissions in the channel.
+    ///
+    /// Members are given like in [`members`], and IDs are returned in
+    /// ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`members`].
+    ///
+    /// [`members`]: #method.members
+    pub fn members_with<'b, I: IntoIterator<Item = (UserId, &'b [(RoleId, Permissions)])>>(
+        &self,
+        members: I,
+        permissions: Permissions,
+    ) -> Result<Vec<UserId>, CalculatorError> {
+        let mut user_ids = self
+            .members(members)?
+            .into_iter()
+            .filter(|(_, calculated)| calculated.contains(permissions))
+            .map(|(user_id, _)| user_id)
+            .collect::<Vec<_>>();
+        user_ids.sort_unstable();
+
+        Ok(user_ids)
+    }
+
+    /// IDs of the members of a set who can see the channel.
+    ///
+    /// This is [`members_with`] the View Channel permission.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::channel::ChannelCalculator;
+    ///
+    /// let everyone = (RoleId(1), Permissions::empty());
+    /// let staff = (RoleId(2), Permissions::empty());
+    /// let overwrites = &[PermissionOverwrite {
+    ///     allow: Permissions::VIEW_CHANNEL,
+    ///     deny: Permissions::empty(),
+    ///     kind: PermissionOverwriteType::Role(RoleId(2)),
+    /// }];
+    /// let alice = [everyone];
+    /// let bob = [everyone, staff];
+    ///
+    /// let calculator = ChannelCalculator::new(GuildId(1), ChannelType::GuildText, overwrites);
+    /// let members = vec![(UserId(3), &alice[..]), (UserId(4), &bob[..])];
+    ///
+    /// assert_eq!(vec![UserId(4)], calculator.who_can_see(members)?);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`members`].
+    ///
+    /// [`members`]: #method.members
+    /// [`members_with`]: #method.members_with
+    pub fn who_can_see<'b, I: IntoIterator<Item = (UserId, &'b [(RoleId, Permissions)])>>(
+        &self,
+        members: I,
+    ) -> Result<Vec<UserId>, CalculatorError> {
+        self.members_with(members, Permissions::VIEW_CHANNEL)
+    }
+
+    /// Calculate the permissions of the member of a calculator in the channel
+    /// from their guild-level permissions.
+    ///
+    /// Only the overwrites of the `@everyone` role, the member's roles, and
+    /// the member are given to the calculator.
+    pub(crate) fn in_channel_with_root(
+        &self,
+        calculator: &Calculator<'_>,
+        root: Permissions,
+    ) -> Permissions {
+        let overwrites = self
+            .everyone
+            .iter()
+            .chain(
+                calculator
+                    .member_roles
+                    .iter()
+                    .filter_map(|(role_id, _)| self.roles.get(role_id)),
+            )
+            .chain(self.members.get(&calculator.user_id))
+            .collect::<Vec<_>>();
+
+        calculator.in_channel_with_root(root, self.channel_type, overwrites.iter().copied())
+    }
+}
+
+/// Create an overwrite of the same kind that neither allows nor denies
+/// anything.
+fn empty(overwrite: &PermissionOverwrite) -> PermissionOverwrite {
+    PermissionOverwrite {
+        allow: Permissions::empty(),
+        deny: Permissions::empty(),
+        kind: overwrite.kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChannelCalculator;
+    use crate::{Calculator, CalculatorError};
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(ChannelCalculator: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_allowed_in() {
+        for channel_type in [
+            ChannelType::GuildCategory,
+            ChannelType::GuildNews,
+            ChannelType::GuildStageVoice,
+            ChannelType::GuildStore,
+            ChannelType::GuildText,
+            ChannelType::GuildVoice,
+        ]
+        .iter()
+        {
+            let allowed = super::allowed_in(*channel_type);
+            assert!(!allowed.contains(Permissions::ADMINISTRATOR));
+            assert!(allowed.contains(Permissions::VIEW_CHANNEL));
+
+            // The mask is what the calculator leaves of all permissions.
+            let calculated = Calculator::new(
+                GuildId(1),
+                UserId(2),
+                &[(RoleId(1), Permissions::all() - Permissions::ADMINISTRATOR)],
+            )
+            .in_channel(*channel_type, &[])
+            .unwrap();
+            assert_eq!(calculated, allowed);
+        }
+
+        assert!(super::allowed_in(ChannelType::GuildCategory).contains(Permissions::MANAGE_ROLES));
+        assert!(!super::allowed_in(ChannelType::GuildCategory).contains(Permissions::SEND_MESSAGES));
+        assert!(super::allowed_in(ChannelType::GuildNews).contains(Permissions::SEND_MESSAGES));
+        assert!(super::allowed_in(ChannelType::GuildVoice).contains(Permissions::SPEAK));
+        assert!(!super::allowed_in(ChannelType::GuildVoice).contains(Permissions::REQUEST_TO_SPEAK));
+    }
+
+    #[test]
+    fn test_is_channel_public() {
+        let guild_id = GuildId(1);
+        let overwrite = |role_id, allow, deny| PermissionOverwrite {
+            allow,
+            deny,
+            kind: PermissionOverwriteType::Role(RoleId(role_id)),
+        };
+        let others = &[overwrite(
+            2,
+            Permissions::empty(),
+            Permissions::VIEW_CHANNEL,
+        )];
+        let opened = &[overwrite(
+            1,
+            Permissions::VIEW_CHANNEL,
+            Permissions::empty(),
+        )];
+        let both = &[overwrite(
+            1,
+            Permissions::VIEW_CHANNEL,
+            Permissions::VIEW_CHANNEL,
+        )];
+
+        assert!(super::is_channel_public(
+            guild_id,
+            Permissions::VIEW_CHANNEL,
+            others
+        ));
+        assert!(!super::is_channel_public(
+            guild_id,
+            Permissions::empty(),
+            &[]
+        ));
+        assert!(super::is_channel_public(
+            guild_id,
+            Permissions::empty(),
+            opened
+        ));
+        assert!(super::is_channel_public(
+            guild_id,
+            Permissions::empty(),
+            both
+        ));
+        assert!(super::is_channel_public(
+            guild_id,
+            Permissions::ADMINISTRATOR,
+            &[overwrite(
+                1,
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL
+            )],
+        ));
+    }
+
+    #[test]
+    fn test_members() {
+        let everyone = (RoleId(1), Permissions::VIEW_CHANNEL);
+        let moderator = (RoleId(2), Permissions::empty());
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::VIEW_CHANNEL,
+                kind: PermissionOverwriteType::Member(UserId(5)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(5)),
+            },
+        ];
+        let member_roles = [everyone];
+        let moderator_roles = [everyone, moderator];
+        let members = vec![
+            (UserId(3), &member_roles[..]),
+            (UserId(4), &moderator_roles[..]),
+            (UserId(5), &moderator_roles[..]),
+        ];
+
+        let calculator = ChannelCalculator::new(GuildId(1), ChannelType::GuildText, overwrites)
+            .owner_id(UserId(6));
+        let permissions = calculator.members(members.clone()).unwrap();
+        assert_eq!(3, permissions.len());
+
+        for (user_id, member_roles) in members.clone() {
+            assert_eq!(
+                Calculator::new(GuildId(1), user_id, member_roles)
+                    .owner_id(UserId(6))
+                    .in_channel(ChannelType::GuildText, overwrites)
+                    .unwrap(),
+                permissions[&user_id],
+            );
+        }
+
+        assert_eq!(Permissions::VIEW_CHANNEL, permissions[&UserId(3)]);
+        assert_eq!(Permissions::empty(), permissions[&UserId(5)]);
+        assert_eq!(
+            vec![UserId(3), UserId(4)],
+            calculator.who_can_see(members.clone()).unwrap(),
+        );
+        assert!(matches!(
+            calculator.member(UserId(7), &[moderator]),
+            Err(CalculatorError::EveryoneRoleMissing { .. })
+        ));
+    }
+}
@@ -0,0 +1,422 @@
+//! Whether members can use application commands, according to the command
+//! permission overrides of a guild.
+//!
+//! Guilds can override who can use an application's commands, either for
+//! every command of the application or for a single command, by allowing or
+//! denying roles, users, and channels. [`CommandCalculator`] determines which
+//! of those overrides apply to a member in a channel.
+//!
+//! Overrides are resolved like Discord does:
+//!
+//! - A member can't use a command in a channel that is denied, either directly
+//!   or via the "all channels" override, which has the ID of the guild minus 1;
+//! - An override for the member themselves takes precedence over overrides
+//!   for their roles;
+//! - If any of the member's roles is allowed then the member is allowed, and
+//!   otherwise if any is denied then the member is denied;
+//! - The override for the `@everyone` role, which has the same ID as the
+//!   guild, applies if no other override for the member does;
+//! - Overrides of a single command take precedence over overrides of the
+//!   whole application, separately for channels and for members.
+//!
+//! If no override decides whether the member can use the command, then it's
+//! up to the command's default member permissions, which [`default_missing`]
+//! and [`is_default_usable`] check against the member's guild-level
+//! permissions.
+//!
+//! [`CommandCalculator`]: struct.CommandCalculator.html
+//! [`default_missing`]: fn.default_missing.html
+//! [`is_default_usable`]: fn.is_default_usable.html
+
+use twilight_model::{
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Target of a command permission override.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CommandPermissionType {
+    /// Override of a channel.
+    ///
+    /// The channel with the ID of the guild minus 1 stands for every channel
+    /// of the guild.
+    Channel(ChannelId),
+    /// Override of a role.
+    Role(RoleId),
+    /// Override of a user.
+    User(UserId),
+}
+
+/// Override of who can use application commands in a guild.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CommandPermission {
+    /// Target of the override.
+    pub kind: CommandPermissionType,
+    /// Whether the target is allowed to use the command.
+    pub permission: bool,
+}
+
+/// Whether a member can use a command according to the permission overrides
+/// of a guild.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CommandUsability {
+    /// Command can't be used in the channel.
+    ChannelDenied,
+    /// No override decides whether the member can use the command, so the
+    /// command's default member permissions apply.
+    Default,
+    /// Member is denied from using the command.
+    MemberDenied,
+    /// Member is allowed to use the command.
+    Usable,
+}
+
+impl CommandUsability {
+    /// Whether the member can use the command, given whether they can by
+    /// default.
+    pub fn is_usable(self, default: bool) -> bool {
+        match self {
+            Self::ChannelDenied | Self::MemberDenied => false,
+            Self::Default => default,
+            Self::Usable => true,
+        }
+    }
+}
+
+/// Calculate whether a member can use application commands.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::id::{ChannelId, GuildId, RoleId, UserId};
+/// use twilight_permission_calculator::command::{
+///     CommandCalculator, CommandPermission, CommandPermissionType, CommandUsability,
+/// };
+///
+/// let guild_id = GuildId(1);
+/// let member_role_ids = &[RoleId(3)];
+///
+/// // Deny the command for everyone but the moderator role.
+/// let command_permissions = &[
+///     CommandPermission {
+///         kind: CommandPermissionType::Role(RoleId(1)),
+///         permission: false,
+///     },
+///     CommandPermission {
+///         kind: CommandPermissionType::Role(RoleId(3)),
+///         permission: true,
+///     },
+/// ];
+///
+/// let calculator = CommandCalculator::new(guild_id, UserId(2), member_role_ids);
+///
+/// assert_eq!(
+///     CommandUsability::Usable,
+///     calculator.in_channel(ChannelId(4), command_permissions, &[]),
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "calculators aren't useful if you don't calculate permissions"]
+pub struct CommandCalculator<'a> {
+    guild_id: GuildId,
+    member_role_ids: &'a [RoleId],
+    user_id: UserId,
+}
+
+impl<'a> CommandCalculator<'a> {
+    /// Create a calculator for a member.
+    ///
+    /// The member's role IDs don't need to include the `@everyone` role.
+    pub fn new(guild_id: GuildId, user_id: UserId, member_role_ids: &'a [RoleId]) -> Self {
+        Self {
+            guild_id,
+            member_role_ids,
+            user_id,
+        }
+    }
+
+    /// Calculate whether the member can use a command in a channel.
+    ///
+    /// `command_permissions` are the overrides of the command itself, while
+    /// `application_permissions` are the overrides of every command of the
+    /// application.
+    pub fn in_channel(
+        &self,
+        channel_id: ChannelId,
+        command_permissions: &[CommandPermission],
+        application_permissions: &[CommandPermission],
+    ) -> CommandUsability {
+        let channel = self
+            .channel(channel_id, command_permissions)
+            .or_else(|| self.channel(channel_id, application_permissions));
+
+        if channel == Some(false) {
+            return CommandUsability::ChannelDenied;
+        }
+
+        let member = self
+            .member(command_permissions)
+            .or_else(|| self.member(application_permissions));
+
+        match member {
+            Some(true) => CommandUsability::Usable,
+            Some(false) => CommandUsability::MemberDenied,
+            None => CommandUsability::Default,
+        }
+    }
+
+    /// Override of the channel, falling back to the override of every
+    /// channel.
+    fn channel(&self, channel_id: ChannelId, permissions: &[CommandPermission]) -> Option<bool> {
+        let all_channels = ChannelId(self.guild_id.0.wrapping_sub(1));
+
+        find(permissions, CommandPermissionType::Channel(channel_id))
+            .or_else(|| find(permissions, CommandPermissionType::Channel(all_channels)))
+    }
+
+    /// Override of the member, falling back to the overrides of their roles
+    /// and then of the `@everyone` role.
+    fn member(&self, permissions: &[CommandPermission]) -> Option<bool> {
+        if let Some(permission) = find(permissions, CommandPermissionType::User(self.user_id)) {
+            return Some(permission);
+        }
+
+        let everyone_id = RoleId(self.guild_id.0);
+        let mut denied = false;
+
+        for role_id in self.member_role_ids {
+            if *role_id == everyone_id {
+                continue;
+            }
+
+            match find(permissions, CommandPermissionType::Role(*role_id)) {
+                Some(true) => return Some(true),
+                Some(false) => denied = true,
+                None => {}
+            }
+        }
+
+        if denied {
+            return Some(false);
+        }
+
+        find(permissions, CommandPermissionType::Role(everyone_id))
+    }
+}
+
+/// Permissions a member is missing to use a command by default, according to
+/// the command's default member permissions.
+///
+/// `permissions` are the member's guild-level permissions, such as calculated
+/// via [`Calculator::root`] with the owner of the guild configured. Members
+/// with the Administrator permission can use every command, and commands
+/// without default member permissions can be used by everyone. Commands with
+/// empty default member permissions can only be used by administrators, in
+/// which case the Administrator permission is returned as missing.
+///
+/// Overrides of the command's permissions take precedence; refer to
+/// [`CommandUsability::is_usable`].
+///
+/// # Examples
+///
+/// Build a help menu of only the commands that a member can see:
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::command;
+///
+/// let commands = &[
+///     ("help", None),
+///     ("ban", Some(Permissions::BAN_MEMBERS)),
+///     ("setup", Some(Permissions::empty())),
+/// ];
+/// let permissions = Permissions::BAN_MEMBERS | Permissions::KICK_MEMBERS;
+///
+/// let visible = commands
+///     .iter()
+///     .filter(|(_, default)| command::is_default_usable(*default, permissions))
+///     .map(|(name, _)| *name)
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(["help", "ban"], visible.as_slice());
+/// assert_eq!(
+///     Permissions::ADMINISTRATOR,
+///     command::default_missing(Some(Permissions::empty()), permissions),
+/// );
+/// ```
+///
+/// [`Calculator::root`]: ../struct.Calculator.html#method.root
+/// [`CommandUsability::is_usable`]: enum.CommandUsability.html#method.is_usable
+pub fn default_missing(
+    default_member_permissions: Option<Permissions>,
+    permissions: Permissions,
+) -> Permissions {
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        return Permissions::empty();
+    }
+
+    match default_member_permissions {
+        Some(required) if required.is_empty() => Permissions::ADMINISTRATOR,
+        Some(required) => required - permissions,
+        None => Permissions::empty(),
+    }
+}
+
+/// Whether a member can use a command by default, according to the command's
+/// default member permissions.
+///
+/// Refer to [`default_missing`] for how the member's permissions are checked.
+///
+/// [`default_missing`]: fn.default_missing.html
+pub fn is_default_usable(
+    default_member_permissions: Option<Permissions>,
+    permissions: Permissions,
+) -> bool {
+    default_missing(default_member_permissions, permissions).is_empty()
+}
+
+/// Find the override of a target.
+fn find(permissions: &[CommandPermission], kind: CommandPermissionType) -> Option<bool> {
+    permissions
+        .iter()
+        .find(|permission| permission.kind == kind)
+        .map(|permission| permission.permission)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CommandCalculator, CommandPermission, CommandPermissionType, CommandUsability};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(CommandPermission: kind, permission);
+    assert_impl_all!(CommandCalculator<'_>: Clone, Debug, Eq, PartialEq, Send, Sync);
+    assert_impl_all!(
+        CommandPermission: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        CommandPermissionType: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        CommandUsability: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_default_missing() {
+        let permissions = Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES;
+
+        assert!(super::is_default_usable(None, Permissions::empty()));
+        assert!(super::is_default_usable(
+            Some(Permissions::KICK_MEMBERS),
+            permissions
+        ));
+        assert_eq!(
+            Permissions::BAN_MEMBERS,
+            super::default_missing(
+                Some(Permissions::BAN_MEMBERS | Permissions::KICK_MEMBERS),
+                permissions,
+            ),
+        );
+        assert_eq!(
+            Permissions::ADMINISTRATOR,
+            super::default_missing(Some(Permissions::empty()), permissions),
+        );
+        assert!(super::is_default_usable(
+            Some(Permissions::empty()),
+            Permissions::ADMINISTRATOR
+        ));
+        assert!(super::is_default_usable(
+            Some(Permissions::BAN_MEMBERS),
+            Permissions::ADMINISTRATOR
+        ));
+    }
+
+    fn permission(kind: CommandPermissionType, permission: bool) -> CommandPermission {
+        CommandPermission { kind, permission }
+    }
+
+    #[test]
+    fn test_in_channel() {
+        let calculator = CommandCalculator::new(GuildId(10), UserId(2), &[RoleId(3), RoleId(4)]);
+        let channel_id = ChannelId(5);
+
+        assert_eq!(
+            CommandUsability::Default,
+            calculator.in_channel(channel_id, &[], &[]),
+        );
+
+        // Any allowed role wins over denied roles and @everyone.
+        let roles = &[
+            permission(CommandPermissionType::Role(RoleId(10)), false),
+            permission(CommandPermissionType::Role(RoleId(3)), false),
+            permission(CommandPermissionType::Role(RoleId(4)), true),
+        ];
+        assert_eq!(
+            CommandUsability::Usable,
+            calculator.in_channel(channel_id, roles, &[]),
+        );
+
+        // The member's own override wins over their roles.
+        let user = &[
+            permission(CommandPermissionType::Role(RoleId(4)), true),
+            permission(CommandPermissionType::User(UserId(2)), false),
+        ];
+        assert_eq!(
+            CommandUsability::MemberDenied,
+            calculator.in_channel(channel_id, user, &[]),
+        );
+
+        // Denying every channel, but allowing this one.
+        let channels = &[
+            permission(CommandPermissionType::Channel(ChannelId(9)), false),
+            permission(CommandPermissionType::Channel(channel_id), true),
+        ];
+        assert_eq!(
+            CommandUsability::Default,
+            calculator.in_channel(channel_id, channels, &[]),
+        );
+        assert_eq!(
+            CommandUsability::ChannelDenied,
+            calculator.in_channel(ChannelId(6), channels, roles),
+        );
+
+        // Command overrides take precedence over the application's.
+        let everyone_denied = &[permission(CommandPermissionType::Role(RoleId(10)), false)];
+        assert_eq!(
+            CommandUsability::MemberDenied,
+            calculator.in_channel(channel_id, &[], everyone_denied),
+        );
+        assert_eq!(
+            CommandUsability::Usable,
+            calculator.in_channel(channel_id, roles, everyone_denied),
+        );
+
+        assert!(CommandUsability::Default.is_usable(true));
+        assert!(!CommandUsability::ChannelDenied.is_usable(true));
+    }
+}
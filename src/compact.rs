@@ -0,0 +1,349 @@
+//! Compact encoding of large tables of calculated permissions.
+//!
+//! Auditing a large guild produces the permissions of thousands of members in
+//! each of its channels, but most of them share one of a handful of distinct
+//! permission sets. [`encode`] stores each distinct set once in a dictionary
+//! and refers to it by index, and [`decode`] lazily reads the rows back, so
+//! that snapshots of audits stay small.
+//!
+//! Encoded tables are a dictionary of permission sets followed by runs of rows
+//! in the same channel. All integers are encoded as variable-length
+//! little-endian base 128 integers.
+//!
+//! [`decode`]: fn.decode.html
+//! [`encode`]: fn.encode.html
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    guild::Permissions,
+    id::{ChannelId, UserId},
+};
+
+/// Error decoding an encoded table, returned by [`decode`] and [`Decode`].
+///
+/// [`decode`]: fn.decode.html
+/// [`Decode`]: struct.Decode.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// Row refers to a permission set that isn't in the dictionary.
+    IndexInvalid {
+        /// Index of the permission set.
+        index: u64,
+        /// Offset of the row in bytes.
+        offset: usize,
+    },
+    /// Integer is longer than 64 bits.
+    IntegerOverflow {
+        /// Offset of the integer in bytes.
+        offset: usize,
+    },
+    /// Table ends in the middle of an integer.
+    Truncated {
+        /// Offset of the end of the table in bytes.
+        offset: usize,
+    },
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::IndexInvalid { index, offset } => f.write_fmt(format_args!(
+                "permission set {} of row at offset {} is not in the dictionary",
+                index, offset
+            )),
+            Self::IntegerOverflow { offset } => f.write_fmt(format_args!(
+                "integer at offset {} is longer than 64 bits",
+                offset
+            )),
+            Self::Truncated { offset } => {
+                f.write_fmt(format_args!("table is truncated at offset {}", offset))
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Encode rows of the permissions of members in channels.
+///
+/// Consecutive rows in the same channel are stored together, so rows should
+/// be grouped by channel for the smallest encoding. Rows are decoded in the
+/// order they were given.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{ChannelId, UserId},
+/// };
+/// use twilight_permission_calculator::compact;
+///
+/// let rows = vec![
+///     (ChannelId(1), UserId(2), Permissions::VIEW_CHANNEL),
+///     (ChannelId(1), UserId(3), Permissions::VIEW_CHANNEL),
+///     (ChannelId(4), UserId(2), Permissions::empty()),
+/// ];
+///
+/// let encoded = compact::encode(rows.clone());
+/// let decoded = compact::decode(&encoded)?.collect::<Result<Vec<_>, _>>()?;
+///
+/// assert_eq!(rows, decoded);
+/// # Ok(()) }
+/// ```
+pub fn encode<I: IntoIterator<Item = (ChannelId, UserId, Permissions)>>(rows: I) -> Vec<u8> {
+    let mut dictionary = Vec::new();
+    let mut indices = HashMap::new();
+    let mut runs = Vec::<(ChannelId, Vec<(UserId, u64)>)>::new();
+
+    for (channel_id, user_id, permissions) in rows {
+        let index = *indices.entry(permissions).or_insert_with(|| {
+            dictionary.push(permissions);
+
+            dictionary.len() as u64 - 1
+        });
+
+        match runs.last_mut() {
+            Some((id, run)) if *id == channel_id => run.push((user_id, index)),
+            _ => runs.push((channel_id, vec![(user_id, index)])),
+        }
+    }
+
+    let mut bytes = Vec::new();
+    write_integer(&mut bytes, dictionary.len() as u64);
+
+    for permissions in dictionary {
+        write_integer(&mut bytes, permissions.bits());
+    }
+
+    for (channel_id, run) in runs {
+        write_integer(&mut bytes, channel_id.0);
+        write_integer(&mut bytes, run.len() as u64);
+
+        for (user_id, index) in run {
+            write_integer(&mut bytes, user_id.0);
+            write_integer(&mut bytes, index);
+        }
+    }
+
+    bytes
+}
+
+/// Decode a table encoded via [`encode`].
+///
+/// The dictionary is read immediately, while rows are read as the returned
+/// iterator is consumed.
+///
+/// Permission sets are read with [`Permissions::from_bits_truncate`], so
+/// permissions that this version of the model doesn't know are dropped.
+///
+/// # Errors
+///
+/// Returns [`DecodeError::IntegerOverflow`] or [`DecodeError::Truncated`] if
+/// the dictionary is malformed.
+///
+/// [`DecodeError::IntegerOverflow`]: enum.DecodeError.html#variant.IntegerOverflow
+/// [`DecodeError::Truncated`]: enum.DecodeError.html#variant.Truncated
+/// [`encode`]: fn.encode.html
+/// [`Permissions::from_bits_truncate`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Permissions.html#method.from_bits_truncate
+pub fn decode(bytes: &[u8]) -> Result<Decode<'_>, DecodeError> {
+    let mut offset = 0;
+    let len = read_integer(bytes, &mut offset)?;
+    // The length is untrusted, so don't preallocate more than the table could
+    // hold.
+    let mut dictionary = Vec::with_capacity((len as usize).min(bytes.len()));
+
+    for _ in 0..len {
+        dictionary.push(Permissions::from_bits_truncate(read_integer(
+            bytes,
+            &mut offset,
+        )?));
+    }
+
+    Ok(Decode {
+        bytes,
+        channel: None,
+        dictionary,
+        failed: false,
+        offset,
+    })
+}
+
+/// Iterator over the rows of an encoded table, created via [`decode`].
+///
+/// Iteration stops after the first error.
+///
+/// [`decode`]: fn.decode.html
+#[derive(Clone, Debug)]
+pub struct Decode<'a> {
+    bytes: &'a [u8],
+    /// Channel of the current run and the number of rows left in it.
+    channel: Option<(ChannelId, u64)>,
+    dictionary: Vec<Permissions>,
+    failed: bool,
+    offset: usize,
+}
+
+impl Decode<'_> {
+    fn read_row(&mut self) -> Result<Option<(ChannelId, UserId, Permissions)>, DecodeError> {
+        let (channel_id, remaining) = loop {
+            match self.channel {
+                Some((channel_id, remaining)) if remaining > 0 => break (channel_id, remaining),
+                _ if self.offset == self.bytes.len() => return Ok(None),
+                _ => {
+                    let channel_id = ChannelId(read_integer(self.bytes, &mut self.offset)?);
+                    let len = read_integer(self.bytes, &mut self.offset)?;
+
+                    self.channel.replace((channel_id, len));
+                }
+            }
+        };
+
+        let offset = self.offset;
+        let user_id = UserId(read_integer(self.bytes, &mut self.offset)?);
+        let index = read_integer(self.bytes, &mut self.offset)?;
+
+        let permissions = *self
+            .dictionary
+            .get(index as usize)
+            .ok_or(DecodeError::IndexInvalid { index, offset })?;
+
+        self.channel.replace((channel_id, remaining - 1));
+
+        Ok(Some((channel_id, user_id, permissions)))
+    }
+}
+
+impl Iterator for Decode<'_> {
+    type Item = Result<(ChannelId, UserId, Permissions), DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let row = self.read_row().transpose();
+        self.failed = matches!(row, Some(Err(_)));
+
+        row
+    }
+}
+
+/// Append a variable-length integer.
+fn write_integer(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+
+    bytes.push(value as u8);
+}
+
+/// Read a variable-length integer, advancing the offset past it.
+fn read_integer(bytes: &[u8], offset: &mut usize) -> Result<u64, DecodeError> {
+    let start = *offset;
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*offset).ok_or(DecodeError::Truncated {
+            offset: bytes.len(),
+        })?;
+        *offset += 1;
+
+        let bits = u64::from(byte & 0x7f);
+
+        if shift == 63 && bits > 1 {
+            return Err(DecodeError::IntegerOverflow { offset: start });
+        }
+
+        value |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(DecodeError::IntegerOverflow { offset: start })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decode, DecodeError};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{error::Error, fmt::Debug, hash::Hash};
+    use twilight_model::{
+        guild::Permissions,
+        id::{ChannelId, UserId},
+    };
+
+    assert_fields!(DecodeError::IndexInvalid: index, offset);
+    assert_fields!(DecodeError::IntegerOverflow: offset);
+    assert_fields!(DecodeError::Truncated: offset);
+    assert_impl_all!(Decode<'_>: Clone, Debug, Iterator, Send, Sync);
+    assert_impl_all!(DecodeError: Clone, Debug, Error, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_round_trip() {
+        let rows = (0..1000)
+            .map(|id| {
+                let permissions = if id % 3 == 0 {
+                    Permissions::all()
+                } else {
+                    Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL
+                };
+
+                (ChannelId(id / 100), UserId(u64::MAX - id), permissions)
+            })
+            .collect::<Vec<_>>();
+
+        let encoded = super::encode(rows.clone());
+        let decoded = super::decode(&encoded)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(rows, decoded);
+        // Each row is its user ID and a single byte for its permission set.
+        assert!(encoded.len() < rows.len() * 12);
+
+        assert_eq!(
+            0,
+            super::decode(&super::encode(Vec::new())).unwrap().count()
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let encoded = super::encode(vec![(ChannelId(1), UserId(2), Permissions::empty())]);
+
+        let mut decode = super::decode(&encoded[..encoded.len() - 1]).unwrap();
+        assert_eq!(
+            Some(Err(DecodeError::Truncated {
+                offset: encoded.len() - 1
+            })),
+            decode.next(),
+        );
+        assert!(decode.next().is_none());
+
+        let mut invalid = encoded.clone();
+        *invalid.last_mut().unwrap() = 1;
+        assert_eq!(
+            Some(Err(DecodeError::IndexInvalid {
+                index: 1,
+                offset: 4
+            })),
+            super::decode(&invalid).unwrap().next(),
+        );
+
+        assert_eq!(
+            Some(DecodeError::IntegerOverflow { offset: 0 }),
+            super::decode(&[0xff; 11]).err(),
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Deprecated compatibility layer for the calculator API that was split into
+//! a guild calculator and a member calculator.
+//!
+//! Earlier versions were configured once with all of a guild's roles, and
+//! then picked out a member by the IDs of their roles:
+//!
+//! ```rust
+//! # #![allow(deprecated)]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use twilight_model::{
+//!     channel::ChannelType,
+//!     guild::Permissions,
+//!     id::{GuildId, RoleId, UserId},
+//! };
+//! use twilight_permission_calculator::compat::Calculator;
+//!
+//! let roles = &[
+//!     (RoleId(1), Permissions::VIEW_CHANNEL),
+//!     (RoleId(2), Permissions::SEND_MESSAGES),
+//!     (RoleId(3), Permissions::KICK_MEMBERS),
+//! ];
+//!
+//! let calculator = Calculator::new(GuildId(1), UserId(4), roles);
+//! let permissions = calculator
+//!     .member(UserId(5), &[RoleId(2)])
+//!     .in_channel(ChannelType::GuildText, &[])?;
+//!
+//! assert_eq!(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL, permissions);
+//! # Ok(()) }
+//! ```
+//!
+//! The crate's [`Calculator`] takes the member's roles along with their
+//! permissions instead. The types here keep the old signatures working on top
+//! of it, so that bots can move over one call at a time. They'll be removed in
+//! a future version.
+//!
+//! [`Calculator`]: ../struct.Calculator.html
+
+#![allow(deprecated)]
+
+pub use super::role::RoleCalculator;
+
+use super::CalculatorError;
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Calculator configured with all of the roles of a guild.
+///
+/// Refer to the [module-level documentation] for an example.
+///
+/// [module-level documentation]: index.html
+#[deprecated(
+    since = "0.1.0",
+    note = "use `twilight_permission_calculator::Calculator` with the member's roles instead"
+)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the calculator isn't useful if you don't pick out a member or role"]
+pub struct Calculator<'a> {
+    guild_id: GuildId,
+    owner_id: UserId,
+    roles: &'a [(RoleId, Permissions)],
+}
+
+impl<'a> Calculator<'a> {
+    /// Create a calculator for a guild.
+    ///
+    /// `roles` is every role of the guild along with its permissions,
+    /// including the `@everyone` role.
+    pub const fn new(
+        guild_id: GuildId,
+        owner_id: UserId,
+        roles: &'a [(RoleId, Permissions)],
+    ) -> Self {
+        Self {
+            guild_id,
+            owner_id,
+            roles,
+        }
+    }
+
+    /// Create a calculator for a member of the guild with the IDs of their
+    /// roles.
+    ///
+    /// The `@everyone` role doesn't need to be included in the member's
+    /// roles. Roles that aren't in the guild's roles are ignored.
+    pub fn member(&self, user_id: UserId, member_role_ids: &[RoleId]) -> MemberCalculator {
+        let member_roles = self
+            .roles
+            .iter()
+            .filter(|(role_id, _)| {
+                role_id.0 == self.guild_id.0 || member_role_ids.contains(role_id)
+            })
+            .copied()
+            .collect::<Vec<_>>();
+
+        MemberCalculator(
+            super::Calculator::from_owned(self.guild_id, user_id, member_roles)
+                .owner_id(self.owner_id),
+        )
+    }
+
+    /// Create a calculator for a role of the guild.
+    ///
+    /// Refer to [`RoleCalculator::from_guild_roles`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`RoleCalculator::from_guild_roles`].
+    ///
+    /// [`RoleCalculator::from_guild_roles`]: ../role/struct.RoleCalculator.html#method.from_guild_roles
+    pub fn role(&self, role_id: RoleId) -> Result<RoleCalculator, CalculatorError> {
+        RoleCalculator::from_guild_roles(self.guild_id, self.roles, role_id)
+    }
+}
+
+/// Calculator for a member of a guild, created via [`Calculator::member`].
+///
+/// [`Calculator::member`]: struct.Calculator.html#method.member
+#[deprecated(
+    since = "0.1.0",
+    note = "use `twilight_permission_calculator::Calculator` with the member's roles instead"
+)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the member calculator isn't useful if you don't calculate permissions"]
+pub struct MemberCalculator(super::Calculator<'static>);
+
+impl MemberCalculator {
+    /// Calculate the guild-level permissions of the member.
+    ///
+    /// Refer to [`Calculator::root`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::root`].
+    ///
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    pub fn root(&self) -> Result<Permissions, CalculatorError> {
+        self.0.root()
+    }
+
+    /// Calculate the permissions of the member in a channel.
+    ///
+    /// Refer to [`Calculator::in_channel`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    pub fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Permissions, CalculatorError> {
+        self.0.clone().in_channel(channel_type, channel_overwrites)
+    }
+}
+
+impl From<MemberCalculator> for super::Calculator<'static> {
+    fn from(calculator: MemberCalculator) -> Self {
+        calculator.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Calculator, MemberCalculator};
+    use crate::CalculatorError;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(Calculator<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(MemberCalculator: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    const ROLES: &[(RoleId, Permissions)] = &[
+        (RoleId(1), Permissions::VIEW_CHANNEL),
+        (RoleId(2), Permissions::SEND_MESSAGES),
+        (RoleId(3), Permissions::KICK_MEMBERS),
+    ];
+
+    #[test]
+    fn test_member() {
+        let calculator = Calculator::new(GuildId(1), UserId(4), ROLES);
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        }];
+
+        let member = calculator.member(UserId(5), &[RoleId(2), RoleId(6)]);
+        assert_eq!(
+            Ok(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            member.root()
+        );
+        assert_eq!(
+            Ok(Permissions::VIEW_CHANNEL),
+            member.in_channel(ChannelType::GuildText, overwrites)
+        );
+        assert_eq!(
+            crate::Calculator::new(GuildId(1), UserId(5), &[ROLES[0], ROLES[1]][..])
+                .owner_id(UserId(4)),
+            member.into(),
+        );
+
+        let owner = calculator.member(UserId(4), &[]);
+        assert_eq!(Ok(Permissions::all()), owner.root());
+    }
+
+    #[test]
+    fn test_role() {
+        let calculator = Calculator::new(GuildId(1), UserId(4), ROLES);
+
+        assert_eq!(
+            Permissions::KICK_MEMBERS | Permissions::VIEW_CHANNEL,
+            calculator.role(RoleId(3)).unwrap().root()
+        );
+        assert_eq!(
+            Err(CalculatorError::RoleMissing { role_id: RoleId(6) }),
+            calculator.role(RoleId(6))
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! Inputs of permission calculations bundled into reusable structs.
+//!
+//! A [`Context`] holds everything known about a member of a guild, and a
+//! [`ChannelContext`] holds everything known about a channel. Both are
+//! configured via builders with defaults, so that new inputs can be added to
+//! them without changing the signatures of the methods that accept them.
+//!
+//! Channels are separate from members since a member is usually checked
+//! against many channels, and a channel against many members.
+//!
+//! [`ChannelContext`]: struct.ChannelContext.html
+//! [`Context`]: struct.Context.html
+
+use super::{version::DataVersion, Calculator, CalculatorError};
+use std::time::SystemTime;
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Information about a member of a guild that their permissions are
+/// calculated from.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::ChannelType,
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::context::{ChannelContext, Context};
+///
+/// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+/// let context = Context::new(GuildId(1), UserId(2), member_roles).owner_id(UserId(3));
+/// let channel = ChannelContext::new(ChannelId(4), ChannelType::GuildText).parent_id(ChannelId(5));
+///
+/// assert!(context.in_channel(&channel)?.contains(Permissions::SEND_MESSAGES));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the context isn't useful if you don't calculate permissions with it"]
+pub struct Context<'a> {
+    communication_disabled_until: Option<SystemTime>,
+    data_version: DataVersion,
+    guild_id: GuildId,
+    member_roles: &'a [(RoleId, Permissions)],
+    owner_id: Option<UserId>,
+    user_id: UserId,
+}
+
+impl<'a> Context<'a> {
+    /// Create a context for a member of a guild.
+    ///
+    /// Member roles are given like they are to [`Calculator::new`].
+    ///
+    /// [`Calculator::new`]: ../struct.Calculator.html#method.new
+    pub fn new(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: &'a [(RoleId, Permissions)],
+    ) -> Self {
+        Self {
+            communication_disabled_until: None,
+            data_version: DataVersion::default(),
+            guild_id,
+            member_roles,
+            owner_id: None,
+            user_id,
+        }
+    }
+
+    /// Set when the member's timeout expires.
+    ///
+    /// Refer to the documentation for
+    /// [`Calculator::communication_disabled_until`].
+    ///
+    /// [`Calculator::communication_disabled_until`]: ../struct.Calculator.html#method.communication_disabled_until
+    pub fn communication_disabled_until(
+        mut self,
+        communication_disabled_until: SystemTime,
+    ) -> Self {
+        self.communication_disabled_until
+            .replace(communication_disabled_until);
+
+        self
+    }
+
+    /// Set the version of the data the context was created from.
+    ///
+    /// Refer to the documentation for [`Calculator::data_version`].
+    ///
+    /// [`Calculator::data_version`]: ../struct.Calculator.html#method.data_version
+    pub fn data_version(mut self, data_version: DataVersion) -> Self {
+        self.data_version = data_version;
+
+        self
+    }
+
+    /// Set the ID of the owner of the guild.
+    ///
+    /// Refer to the documentation for [`Calculator::owner_id`].
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id.replace(owner_id);
+
+        self
+    }
+
+    /// ID of the guild.
+    pub fn guild_id(&self) -> GuildId {
+        self.guild_id
+    }
+
+    /// Roles of the member, along with their permissions.
+    pub fn member_roles(&self) -> &'a [(RoleId, Permissions)] {
+        self.member_roles
+    }
+
+    /// ID of the member.
+    pub fn user_id(&self) -> UserId {
+        self.user_id
+    }
+
+    /// Create a calculator configured with the context.
+    pub fn calculator(&self) -> Calculator<'a> {
+        let mut calculator = Calculator::new(self.guild_id, self.user_id, self.member_roles)
+            .data_version(self.data_version);
+        calculator.communication_disabled_until = self.communication_disabled_until;
+        calculator.owner_id = self.owner_id;
+
+        calculator
+    }
+
+    /// Calculate the guild-level permissions of the member.
+    ///
+    /// Refer to [`Calculator::root`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::root`].
+    ///
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    pub fn root(&self) -> Result<Permissions, CalculatorError> {
+        self.calculator().root()
+    }
+
+    /// Calculate the permissions of the member in a channel.
+    ///
+    /// Refer to [`Calculator::in_channel`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    pub fn in_channel(&self, channel: &ChannelContext<'_>) -> Result<Permissions, CalculatorError> {
+        self.calculator()
+            .in_channel(channel.channel_type, channel.overwrites)
+    }
+}
+
+/// Information about a channel that permissions in it are calculated from.
+///
+/// Channels have no permission overwrites by default.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the channel context isn't useful if you don't calculate permissions with it"]
+pub struct ChannelContext<'a> {
+    channel_id: ChannelId,
+    channel_type: ChannelType,
+    overwrites: &'a [PermissionOverwrite],
+    parent_id: Option<ChannelId>,
+}
+
+impl<'a> ChannelContext<'a> {
+    /// Create a context for a channel.
+    pub fn new(channel_id: ChannelId, channel_type: ChannelType) -> Self {
+        Self {
+            channel_id,
+            channel_type,
+            overwrites: &[],
+            parent_id: None,
+        }
+    }
+
+    /// Set the permission overwrites of the channel.
+    pub fn overwrites(mut self, overwrites: &'a [PermissionOverwrite]) -> Self {
+        self.overwrites = overwrites;
+
+        self
+    }
+
+    /// Set the ID of the category the channel is in.
+    pub fn parent_id(mut self, parent_id: ChannelId) -> Self {
+        self.parent_id.replace(parent_id);
+
+        self
+    }
+
+    /// ID of the channel.
+    pub fn id(&self) -> ChannelId {
+        self.channel_id
+    }
+
+    /// Type of the channel.
+    pub fn kind(&self) -> ChannelType {
+        self.channel_type
+    }
+
+    /// ID of the category the channel is in, if any.
+    pub fn parent(&self) -> Option<ChannelId> {
+        self.parent_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChannelContext, Context};
+    use crate::{version::DataVersion, Calculator};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash, time::SystemTime};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(ChannelContext<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Context<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_calculator() {
+        let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+        let context = Context::new(GuildId(1), UserId(2), member_roles)
+            .communication_disabled_until(SystemTime::UNIX_EPOCH)
+            .data_version(DataVersion::new(4))
+            .owner_id(UserId(3));
+
+        assert_eq!(
+            Calculator::new(GuildId(1), UserId(2), member_roles)
+                .communication_disabled_until(SystemTime::UNIX_EPOCH)
+                .data_version(DataVersion::new(4))
+                .owner_id(UserId(3)),
+            context.calculator(),
+        );
+    }
+
+    #[test]
+    fn test_in_channel() {
+        let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let context = Context::new(GuildId(1), UserId(2), member_roles);
+        let channel = ChannelContext::new(ChannelId(3), ChannelType::GuildText)
+            .overwrites(overwrites)
+            .parent_id(ChannelId(4));
+
+        assert_eq!(ChannelId(3), channel.id());
+        assert_eq!(ChannelType::GuildText, channel.kind());
+        assert_eq!(Some(ChannelId(4)), channel.parent());
+        assert_eq!(Ok(Permissions::VIEW_CHANNEL), context.root());
+        assert_eq!(
+            Ok(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            context.in_channel(&channel),
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Finding what's responsible for a member missing a permission in a channel.
+//!
+//! Moderation bots are often asked why a member can't do something in a
+//! channel, and admins want to know exactly what to change.
+//! [`Calculator::why_denied`] [traces] the calculation and points to the
+//! overwrite, channel type, or missing role permission that left the
+//! permission out.
+//!
+//! [`Calculator::why_denied`]: ../struct.Calculator.html#method.why_denied
+//! [traces]: ../trace/index.html
+
+use super::{
+    trace::{Step, Trace},
+    PERMISSIONS_ROOT,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{GuildId, RoleId, UserId},
+};
+
+/// What's responsible for a member missing a permission.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case", tag = "type"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Culprit {
+    /// Permission can't be used in channels of the type.
+    ChannelType {
+        /// Type of the channel.
+        kind: ChannelType,
+    },
+    /// Overwrite of the `@everyone` role in the channel denies it.
+    EveryoneOverwrite,
+    /// Permission is a guild-level permission, which can't be used in a
+    /// channel.
+    GuildPermission,
+    /// Overwrite of the member in the channel denies it.
+    MemberOverwrite,
+    /// None of the member's roles grant the permission, and no overwrite
+    /// allows it.
+    NotGranted,
+    /// Overwrite of one of the member's roles in the channel denies it.
+    RoleOverwrite {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Member is timed out.
+    TimedOut,
+}
+
+/// Missing permission and what's responsible for it, created via
+/// [`Calculator::why_denied`].
+///
+/// [`Calculator::why_denied`]: ../struct.Calculator.html#method.why_denied
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Denial {
+    /// What's responsible for the permission missing.
+    pub culprit: Culprit,
+    /// Permission that the culprit denies.
+    ///
+    /// This is usually the missing permission itself. When an overwrite
+    /// denies a permission such as View Channel or Send Messages, it also
+    /// removes the permissions that [depend] on it, in which case this is the
+    /// permission that the overwrite denies.
+    ///
+    /// [depend]: ../dependency/index.html
+    pub denied: Permissions,
+    /// Permission that the member is missing.
+    pub permission: Permissions,
+}
+
+/// Find what's responsible for a member missing a permission, given the trace
+/// of the calculation of their permissions in the channel.
+///
+/// Returns `None` if the member isn't missing the permission. If several
+/// permissions are given, then only the first missing one in order of bit
+/// position is considered.
+pub(crate) fn find<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+    guild_id: GuildId,
+    user_id: UserId,
+    member_roles: &[(RoleId, Permissions)],
+    channel_type: ChannelType,
+    channel_overwrites: U,
+    trace: &Trace,
+    permission: Permissions,
+) -> Option<Denial> {
+    let missing = permission - trace.permissions;
+
+    if missing.is_empty() {
+        return None;
+    }
+
+    let permission = Permissions::from_bits_truncate(1 << missing.bits().trailing_zeros());
+
+    let denial = |culprit, denied| Denial {
+        culprit,
+        denied,
+        permission,
+    };
+
+    // Permissions that can't be used in the channel at all are reported as
+    // such, even if nothing would grant them in the first place.
+    if PERMISSIONS_ROOT.contains(permission) {
+        return Some(denial(Culprit::GuildPermission, permission));
+    }
+
+    let applicable = super::channel_permissions(
+        guild_id,
+        None,
+        &[],
+        Permissions::all() - Permissions::ADMINISTRATOR,
+        channel_type,
+        &[],
+    );
+
+    if !applicable.contains(permission) {
+        return Some(denial(
+            Culprit::ChannelType { kind: channel_type },
+            permission,
+        ));
+    }
+
+    // The permission isn't in the result, so the last step that removed it is
+    // responsible.
+    let step = trace
+        .steps
+        .iter()
+        .rev()
+        .find(|step| step.removed().contains(permission))
+        .map(|step| step.step);
+
+    let found = match step {
+        Some(Step::ChannelType { kind }) => denial(Culprit::ChannelType { kind }, permission),
+        Some(Step::DependencyDenied {
+            permission: dependency,
+        }) => denial(
+            denying_overwrite(
+                guild_id,
+                user_id,
+                member_roles,
+                channel_overwrites,
+                dependency,
+                true,
+            )
+            .unwrap_or(Culprit::NotGranted),
+            dependency,
+        ),
+        Some(Step::EveryoneOverwrite) => denial(Culprit::EveryoneOverwrite, permission),
+        Some(Step::GuildPermissions) => denial(Culprit::GuildPermission, permission),
+        Some(Step::MemberOverwrite) => denial(Culprit::MemberOverwrite, permission),
+        Some(Step::RoleOverwrites) => denial(
+            denying_overwrite(
+                guild_id,
+                user_id,
+                member_roles,
+                channel_overwrites,
+                permission,
+                false,
+            )
+            .unwrap_or(Culprit::NotGranted),
+            permission,
+        ),
+        Some(Step::SendMessagesDenied) => denial(
+            denying_overwrite(
+                guild_id,
+                user_id,
+                member_roles,
+                channel_overwrites,
+                Permissions::SEND_MESSAGES,
+                true,
+            )
+            .unwrap_or(Culprit::NotGranted),
+            Permissions::SEND_MESSAGES,
+        ),
+        Some(Step::TimedOut) => denial(Culprit::TimedOut, permission),
+        Some(Step::ViewChannelDenied) => denial(
+            denying_overwrite(
+                guild_id,
+                user_id,
+                member_roles,
+                channel_overwrites,
+                Permissions::VIEW_CHANNEL,
+                true,
+            )
+            .unwrap_or(Culprit::NotGranted),
+            Permissions::VIEW_CHANNEL,
+        ),
+        _ => denial(Culprit::NotGranted, permission),
+    };
+
+    Some(found)
+}
+
+/// Find the overwrite of the member or one of their roles that denies a
+/// permission without allowing it back.
+///
+/// The member's overwrite is only considered if `include_member` is set.
+fn denying_overwrite<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+    guild_id: GuildId,
+    user_id: UserId,
+    member_roles: &[(RoleId, Permissions)],
+    channel_overwrites: U,
+    permission: Permissions,
+    include_member: bool,
+) -> Option<Culprit> {
+    let member_denies = include_member
+        && channel_overwrites.clone().into_iter().any(|overwrite| {
+            overwrite.kind == PermissionOverwriteType::Member(user_id)
+                && overwrite.deny.contains(permission)
+                && !overwrite.allow.contains(permission)
+        });
+
+    if member_denies {
+        return Some(Culprit::MemberOverwrite);
+    }
+
+    channel_overwrites
+        .into_iter()
+        .find_map(|overwrite| match overwrite.kind {
+            PermissionOverwriteType::Role(role_id)
+                if role_id.0 != guild_id.0
+                    && overwrite.deny.contains(permission)
+                    && member_roles.iter().any(|(id, _)| *id == role_id) =>
+            {
+                Some(Culprit::RoleOverwrite { role_id })
+            }
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Culprit, Denial};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_fields!(Culprit::ChannelType: kind);
+    assert_fields!(Culprit::RoleOverwrite: role_id);
+    assert_fields!(Denial: culprit, denied, permission);
+    assert_impl_all!(Culprit: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Denial: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+}
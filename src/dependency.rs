@@ -0,0 +1,179 @@
+//! Permissions that are implicitly denied when the permissions they depend on
+//! are denied.
+//!
+//! When an overwrite in a channel denies a permission and no overwrite
+//! applied after it allows it back, the [`Calculator`] also denies the
+//! permissions that depend on it, like Discord's clients do. Which
+//! permissions depend on which is configured with [`Dependencies`], which
+//! defaults to the [`PRACTICAL`] preset. Each [`CascadeMode`] is one of the
+//! presets.
+//!
+//! [`CascadeMode`]: ../enum.CascadeMode.html
+//! [`Calculator`]: ../struct.Calculator.html
+//! [`Dependencies`]: struct.Dependencies.html
+//! [`PRACTICAL`]: struct.Dependencies.html#associatedconstant.PRACTICAL
+
+use super::PERMISSIONS_MESSAGING;
+use std::borrow::Cow;
+use twilight_model::guild::Permissions;
+
+/// Permission along with the permissions that are implicitly denied when it's
+/// denied.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Dependency {
+    /// Permissions that are denied along with the permission.
+    pub dependents: Permissions,
+    /// Permission that the dependents depend on.
+    pub permission: Permissions,
+}
+
+impl Dependency {
+    /// Create a dependency of permissions on a permission.
+    pub const fn new(permission: Permissions, dependents: Permissions) -> Self {
+        Self {
+            dependents,
+            permission,
+        }
+    }
+}
+
+/// Permissions that are implicitly denied when the permissions they depend on
+/// are denied in a channel.
+///
+/// Dependencies are applied in the order they were added. Denying a
+/// permission whose dependents are every permission removes all permissions,
+/// like denying View Channel does by default.
+///
+/// # Examples
+///
+/// Also deny speaking and streaming to members who can't connect to a voice
+/// channel:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::{dependency::Dependencies, Calculator};
+///
+/// let member_roles = &[(
+///     RoleId(1),
+///     Permissions::CONNECT | Permissions::SPEAK | Permissions::VIEW_CHANNEL,
+/// )];
+/// let overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::CONNECT,
+///     kind: PermissionOverwriteType::Member(UserId(2)),
+/// }];
+/// let dependencies = Dependencies::PRACTICAL.add(
+///     Permissions::CONNECT,
+///     Permissions::SPEAK | Permissions::STREAM,
+/// );
+///
+/// let permissions = Calculator::new(GuildId(1), UserId(2), member_roles)
+///     .dependencies(dependencies)
+///     .in_channel(ChannelType::GuildVoice, overwrites)?;
+///
+/// assert_eq!(Permissions::VIEW_CHANNEL, permissions);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Dependencies(Cow<'static, [Dependency]>);
+
+impl Dependencies {
+    /// No dependencies, so no permissions are implicitly denied.
+    pub const NONE: Self = Self(Cow::Borrowed(&[]));
+
+    /// Dependencies that Discord's clients apply, which is the default.
+    ///
+    /// Denying View Channel denies every permission, and denying Send
+    /// Messages denies Attach Files, Embed Links, Mention Everyone, and Send
+    /// TTS Messages.
+    pub const PRACTICAL: Self = Self(Cow::Borrowed(&[
+        Dependency::new(Permissions::VIEW_CHANNEL, Permissions::all()),
+        Dependency::new(Permissions::SEND_MESSAGES, PERMISSIONS_MESSAGING),
+    ]));
+
+    /// Add a dependency of permissions on a permission, applied after the
+    /// existing dependencies.
+    pub fn add(mut self, permission: Permissions, dependents: Permissions) -> Self {
+        self.0
+            .to_mut()
+            .push(Dependency::new(permission, dependents));
+
+        self
+    }
+
+    /// Permissions that are denied along with a permission.
+    pub fn dependents(&self, permission: Permissions) -> Permissions {
+        self.0
+            .iter()
+            .filter(|dependency| dependency.permission == permission)
+            .fold(Permissions::empty(), |dependents, dependency| {
+                dependents | dependency.dependents
+            })
+    }
+
+    /// Iterate over the dependencies in the order they're applied.
+    pub fn iter(&self) -> impl Iterator<Item = &Dependency> {
+        self.0.iter()
+    }
+}
+
+impl Default for Dependencies {
+    fn default() -> Self {
+        Self::PRACTICAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dependencies, Dependency};
+    use crate::PERMISSIONS_MESSAGING;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::Permissions;
+
+    assert_fields!(Dependency: dependents, permission);
+    assert_impl_all!(Dependency: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        Dependencies: Clone,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_dependents() {
+        let dependencies = Dependencies::default();
+        assert_eq!(Dependencies::PRACTICAL, dependencies);
+        assert_eq!(
+            Permissions::all(),
+            dependencies.dependents(Permissions::VIEW_CHANNEL)
+        );
+        assert_eq!(
+            PERMISSIONS_MESSAGING,
+            dependencies.dependents(Permissions::SEND_MESSAGES)
+        );
+        assert!(dependencies.dependents(Permissions::CONNECT).is_empty());
+
+        let dependencies = dependencies
+            .add(Permissions::CONNECT, Permissions::SPEAK)
+            .add(Permissions::CONNECT, Permissions::STREAM);
+        assert_eq!(
+            Permissions::SPEAK | Permissions::STREAM,
+            dependencies.dependents(Permissions::CONNECT)
+        );
+        assert_eq!(4, dependencies.iter().count());
+        assert_eq!(0, Dependencies::NONE.iter().count());
+    }
+}
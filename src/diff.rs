@@ -0,0 +1,204 @@
+//! Differences between two sets of permissions.
+
+use super::names;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use twilight_model::guild::Permissions;
+
+/// Difference between two sets of permissions, such as a member's permissions
+/// in a channel before and after a role update.
+///
+/// # Examples
+///
+/// Render the difference into text that can be used as the value of an embed
+/// field:
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::diff::PermissionDiff;
+///
+/// let before = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+/// let after = Permissions::ATTACH_FILES | Permissions::VIEW_CHANNEL;
+/// let diff = PermissionDiff::new(before, after);
+///
+/// assert_eq!("➕ Attach Files\n➖ Send Messages", diff.display().to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct PermissionDiff {
+    /// Permissions that are present after the change but weren't before.
+    pub added: Permissions,
+    /// Permissions that were present before the change but aren't after.
+    pub removed: Permissions,
+}
+
+impl PermissionDiff {
+    /// Calculate the difference between permissions before and after a change.
+    pub fn new(before: Permissions, after: Permissions) -> Self {
+        Self {
+            added: after - before,
+            removed: before - after,
+        }
+    }
+
+    /// Whether no permissions were added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Create a formatter rendering the difference as lines of permission
+    /// names, one permission per line, with added permissions listed before
+    /// removed permissions.
+    ///
+    /// The result can be placed directly in an embed field. Refer to
+    /// [`PermissionDiffDisplay`] for customizing the markers.
+    ///
+    /// [`PermissionDiffDisplay`]: struct.PermissionDiffDisplay.html
+    pub fn display(&self) -> PermissionDiffDisplay<'_> {
+        PermissionDiffDisplay {
+            added_marker: "➕",
+            diff: self,
+            empty: "No changes",
+            removed_marker: "➖",
+        }
+    }
+}
+
+/// Formatter for a [`PermissionDiff`], created via [`PermissionDiff::display`].
+///
+/// Permission names are retrieved via the [`names`] module and are safe to
+/// use in Discord markdown. Markers are written as-is, so they may be emojis,
+/// custom emoji mentions, or plain text prefixes such as `+` and `-`.
+///
+/// If the difference is empty then a placeholder is written instead, since
+/// embed fields may not be empty.
+///
+/// [`PermissionDiff`]: struct.PermissionDiff.html
+/// [`PermissionDiff::display`]: struct.PermissionDiff.html#method.display
+/// [`names`]: ../names/index.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "the formatter isn't useful if it isn't displayed"]
+pub struct PermissionDiffDisplay<'a> {
+    added_marker: &'a str,
+    diff: &'a PermissionDiff,
+    empty: &'a str,
+    removed_marker: &'a str,
+}
+
+impl<'a> PermissionDiffDisplay<'a> {
+    /// Set the marker written before the names of added permissions.
+    ///
+    /// Defaults to "➕".
+    pub fn added_marker(mut self, added_marker: &'a str) -> Self {
+        self.added_marker = added_marker;
+
+        self
+    }
+
+    /// Set the placeholder written when no permissions were added or removed.
+    ///
+    /// Defaults to "No changes".
+    pub fn empty(mut self, empty: &'a str) -> Self {
+        self.empty = empty;
+
+        self
+    }
+
+    /// Set the marker written before the names of removed permissions.
+    ///
+    /// Defaults to "➖".
+    pub fn removed_marker(mut self, removed_marker: &'a str) -> Self {
+        self.removed_marker = removed_marker;
+
+        self
+    }
+}
+
+impl Display for PermissionDiffDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.diff.is_empty() {
+            return f.write_str(self.empty);
+        }
+
+        let added = names::iter(self.diff.added).map(|(_, name)| (self.added_marker, name));
+        let removed = names::iter(self.diff.removed).map(|(_, name)| (self.removed_marker, name));
+
+        for (idx, (marker, name)) in added.chain(removed).enumerate() {
+            if idx > 0 {
+                f.write_str("\n")?;
+            }
+
+            f.write_str(marker)?;
+            f.write_str(" ")?;
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PermissionDiff, PermissionDiffDisplay};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::guild::Permissions;
+
+    assert_fields!(PermissionDiff: added, removed);
+    assert_impl_all!(
+        PermissionDiff: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        PermissionDiffDisplay<'_>: Clone,
+        Debug,
+        Display,
+        Eq,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_new() {
+        let diff = PermissionDiff::new(
+            Permissions::SPEAK | Permissions::CONNECT,
+            Permissions::CONNECT | Permissions::STREAM,
+        );
+
+        assert_eq!(Permissions::STREAM, diff.added);
+        assert_eq!(Permissions::SPEAK, diff.removed);
+        assert!(!diff.is_empty());
+        assert!(PermissionDiff::new(Permissions::SPEAK, Permissions::SPEAK).is_empty());
+    }
+
+    #[test]
+    fn test_display_markers() {
+        let diff = PermissionDiff::new(
+            Permissions::KICK_MEMBERS,
+            Permissions::BAN_MEMBERS | Permissions::MANAGE_ROLES,
+        );
+        let text = diff
+            .display()
+            .added_marker("+")
+            .removed_marker("-")
+            .to_string();
+
+        assert_eq!("+ Ban Members\n+ Manage Roles\n- Kick Members", text);
+    }
+
+    #[test]
+    fn test_display_empty() {
+        let diff = PermissionDiff::new(Permissions::empty(), Permissions::empty());
+
+        assert_eq!("No changes", diff.display().to_string());
+        assert_eq!("-", diff.display().empty("-").to_string());
+    }
+}
@@ -0,0 +1,279 @@
+//! Explaining where a member's permissions in a channel come from.
+//!
+//! Support tooling is mostly asked why a member can or can't do something.
+//! [`Calculator::explain`] calculates the member's permissions in a channel
+//! like [`Calculator::in_channel`] does, and attributes each of them to the
+//! role or overwrite that granted it.
+//!
+//! [`Calculator::explain`]: ../struct.Calculator.html#method.explain
+//! [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+
+use super::names;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use twilight_model::{
+    channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    guild::Permissions,
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Where a permission of a member comes from.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case", tag = "type"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Source {
+    /// Member is an administrator, and so has every permission.
+    Administrator,
+    /// Guild-level permissions of the `@everyone` role.
+    Everyone,
+    /// Overwrite of the `@everyone` role in the channel.
+    EveryoneOverwrite,
+    /// Overwrite of the member in the channel.
+    MemberOverwrite,
+    /// Member is the owner of the guild, and so has every permission.
+    Owner,
+    /// Guild-level permissions of one of the member's roles.
+    Role {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Overwrite of one of the member's roles in the channel.
+    RoleOverwrite {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+}
+
+/// Permissions of a member in a channel along with where each of them comes
+/// from, created via [`Calculator::explain`].
+///
+/// [`Calculator::explain`]: ../struct.Calculator.html#method.explain
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Explanation {
+    /// Permissions of the member in the channel, as returned by
+    /// [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    pub permissions: Permissions,
+    /// Every permission the member has and where it comes from, in order of
+    /// the permissions' bit positions.
+    ///
+    /// When a permission is granted in several places, the source that takes
+    /// precedence is given: the member's overwrite, then the overwrites of the
+    /// member's roles, then the overwrite of the `@everyone` role, and finally
+    /// the guild-level permissions of the `@everyone` role and of the member's
+    /// other roles.
+    ///
+    /// When the [administrator bypass] is disabled, roles with the
+    /// Administrator permission are the source of every guild-level
+    /// permission.
+    ///
+    /// [administrator bypass]: ../struct.Calculator.html#method.administrator_bypass
+    pub sources: Vec<(Permissions, Source)>,
+}
+
+impl Explanation {
+    /// Attribute permissions calculated from a member's roles and a channel's
+    /// overwrites to their sources.
+    pub(crate) fn new<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: &[(RoleId, Permissions)],
+        channel_overwrites: U,
+        permissions: Permissions,
+        bypass: Option<Source>,
+    ) -> Self {
+        if let Some(source) = bypass {
+            return Self {
+                permissions,
+                sources: names::iter(permissions)
+                    .map(|(permission, _)| (permission, source))
+                    .collect(),
+            };
+        }
+
+        let mut everyone_allow = Permissions::empty();
+        let mut member_allow = Permissions::empty();
+        let mut role_allows = Vec::new();
+
+        for overwrite in channel_overwrites {
+            match overwrite.kind {
+                PermissionOverwriteType::Member(id) if id == user_id => {
+                    member_allow.insert(overwrite.allow);
+                }
+                PermissionOverwriteType::Member(_) => {}
+                PermissionOverwriteType::Role(role_id) if role_id.0 == guild_id.0 => {
+                    everyone_allow.insert(overwrite.allow);
+                }
+                PermissionOverwriteType::Role(role_id) => {
+                    if member_roles.iter().any(|(id, _)| *id == role_id) {
+                        role_allows.push((role_id, overwrite.allow));
+                    }
+                }
+            }
+        }
+
+        let sources = names::iter(permissions)
+            .filter_map(|(permission, _)| {
+                let source = if member_allow.contains(permission) {
+                    Source::MemberOverwrite
+                } else if let Some((role_id, _)) = role_allows
+                    .iter()
+                    .find(|(_, allow)| allow.contains(permission))
+                {
+                    Source::RoleOverwrite { role_id: *role_id }
+                } else if everyone_allow.contains(permission) {
+                    Source::EveryoneOverwrite
+                } else {
+                    member_roles
+                        .iter()
+                        .filter(|(_, role_permissions)| {
+                            role_permissions.intersects(permission | Permissions::ADMINISTRATOR)
+                        })
+                        .map(|(role_id, _)| {
+                            if role_id.0 == guild_id.0 {
+                                Source::Everyone
+                            } else {
+                                Source::Role { role_id: *role_id }
+                            }
+                        })
+                        .min_by_key(|source| *source != Source::Everyone)?
+                };
+
+                Some((permission, source))
+            })
+            .collect();
+
+        Self {
+            permissions,
+            sources,
+        }
+    }
+
+    /// Where a single permission comes from.
+    ///
+    /// Returns `None` if the member doesn't have the permission, or if the
+    /// given value isn't exactly one known permission.
+    pub fn source(&self, permission: Permissions) -> Option<Source> {
+        self.sources
+            .iter()
+            .find(|(granted, _)| *granted == permission)
+            .map(|(_, source)| *source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Explanation, Source};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Explanation: permissions, sources);
+    assert_fields!(Source::Role: role_id);
+    assert_fields!(Source::RoleOverwrite: role_id);
+    assert_impl_all!(Explanation: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Source: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_sources() {
+        let member_roles = &[
+            (RoleId(3), Permissions::VIEW_CHANNEL),
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+        ];
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::ATTACH_FILES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(4)),
+            },
+        ];
+        let permissions =
+            Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+
+        let explanation = Explanation::new(
+            GuildId(1),
+            UserId(2),
+            member_roles,
+            overwrites,
+            permissions,
+            None,
+        );
+
+        assert_eq!(permissions, explanation.permissions);
+        assert_eq!(
+            vec![
+                (Permissions::ADD_REACTIONS, Source::MemberOverwrite),
+                (Permissions::VIEW_CHANNEL, Source::Everyone),
+                (
+                    Permissions::SEND_MESSAGES,
+                    Source::RoleOverwrite { role_id: RoleId(3) }
+                ),
+            ],
+            explanation.sources,
+        );
+        assert!(explanation.source(Permissions::ATTACH_FILES).is_none());
+    }
+
+    #[test]
+    fn test_bypass() {
+        let explanation = Explanation::new(
+            GuildId(1),
+            UserId(2),
+            &[],
+            &[],
+            Permissions::all(),
+            Some(Source::Owner),
+        );
+
+        assert_eq!(
+            Some(Source::Owner),
+            explanation.source(Permissions::BAN_MEMBERS)
+        );
+        assert_eq!(
+            explanation.sources.len(),
+            explanation
+                .sources
+                .iter()
+                .filter(|(_, source)| *source == Source::Owner)
+                .count(),
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let explanation = Explanation {
+            permissions: Permissions::SEND_MESSAGES,
+            sources: vec![(
+                Permissions::SEND_MESSAGES,
+                Source::RoleOverwrite { role_id: RoleId(3) },
+            )],
+        };
+
+        let value = serde_json::to_value(&explanation).unwrap();
+        assert_eq!("role_overwrite", value["sources"][0][1]["type"]);
+        assert_eq!(explanation, serde_json::from_value(value).unwrap());
+    }
+}
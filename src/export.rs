@@ -0,0 +1,240 @@
+//! Exporting permission audits as tables that can be opened in a spreadsheet.
+//!
+//! Tables are written as CSV or TSV. Each row is a member or role, labelled by
+//! anything that can be displayed such as an ID or a name, and the columns are
+//! either every permission or a list of channels. Rows are written as they're
+//! consumed, so the results of batch calculations such as the iterators of
+//! the `member_chunk` module can be exported without collecting them first.
+
+use super::names;
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    io::{Result as IoResult, Write},
+};
+use twilight_model::{guild::Permissions, id::ChannelId};
+
+/// Format of an exported table.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// Comma-separated values.
+    ///
+    /// Fields containing commas, quotes, or line breaks are quoted.
+    Csv,
+    /// Tab-separated values.
+    ///
+    /// TSV has no way of escaping fields, so tabs and line breaks in fields
+    /// are replaced with spaces.
+    Tsv,
+}
+
+impl Format {
+    /// Prepare a field to be written, escaping it if needed.
+    fn field<'a>(self, field: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Csv if field.contains(&[',', '"', '\n', '\r'][..]) => {
+                Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+            }
+            Self::Tsv if field.contains(&['\t', '\n', '\r'][..]) => {
+                Cow::Owned(field.replace(&['\t', '\n', '\r'][..], " "))
+            }
+            _ => Cow::Borrowed(field),
+        }
+    }
+
+    /// Write a row of fields after the row's label, followed by a line break.
+    fn write_row<W: Write, F: AsRef<str>>(
+        self,
+        writer: &mut W,
+        label: &str,
+        fields: impl IntoIterator<Item = F>,
+    ) -> IoResult<()> {
+        let delimiter = match self {
+            Self::Csv => b",",
+            Self::Tsv => b"\t",
+        };
+
+        writer.write_all(self.field(label).as_bytes())?;
+
+        for field in fields {
+            writer.write_all(delimiter)?;
+            writer.write_all(self.field(field.as_ref()).as_bytes())?;
+        }
+
+        writer.write_all(b"\n")
+    }
+}
+
+/// Write a table with a column for every permission.
+///
+/// The header row contains the name of each permission. Cells are `true` if
+/// the row has the permission and `false` otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{guild::Permissions, id::UserId};
+/// use twilight_permission_calculator::export::{self, Format};
+///
+/// let members = vec![
+///     (UserId(1), Permissions::all()),
+///     (UserId(2), Permissions::VIEW_CHANNEL),
+/// ];
+///
+/// let mut csv = Vec::new();
+/// export::permissions(&mut csv, Format::Csv, members)?;
+///
+/// let csv = String::from_utf8(csv)?;
+/// assert!(csv.starts_with("id,Create Invite,Kick Members,"));
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if writing to the writer fails.
+pub fn permissions<W: Write, R: Display, I: IntoIterator<Item = (R, Permissions)>>(
+    writer: &mut W,
+    format: Format,
+    rows: I,
+) -> IoResult<()> {
+    let header = names::iter(Permissions::all()).map(|(_, name)| name);
+    format.write_row(writer, "id", header)?;
+
+    for (label, permissions) in rows {
+        let cells = names::iter(Permissions::all()).map(|(permission, _)| {
+            if permissions.contains(permission) {
+                "true"
+            } else {
+                "false"
+            }
+        });
+
+        format.write_row(writer, &label.to_string(), cells)?;
+    }
+
+    Ok(())
+}
+
+/// Write a table with a column for every channel.
+///
+/// The header row contains the ID of each channel. Each row contains the
+/// row's permissions in each channel, in the same order as the channels, as a
+/// list of permission names separated by commas.
+///
+/// # Examples
+///
+/// Export which channels roles are special-cased in:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{ChannelId, RoleId},
+/// };
+/// use twilight_permission_calculator::export::{self, Format};
+///
+/// let channels = &[ChannelId(3), ChannelId(4)];
+/// let roles = vec![(
+///     RoleId(2),
+///     vec![Permissions::VIEW_CHANNEL, Permissions::empty()],
+/// )];
+///
+/// let mut tsv = Vec::new();
+/// export::channels(&mut tsv, Format::Tsv, channels, roles)?;
+///
+/// assert_eq!("id\t3\t4\n2\tView Channel\t\n", String::from_utf8(tsv)?);
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if writing to the writer fails.
+pub fn channels<
+    W: Write,
+    R: Display,
+    C: IntoIterator<Item = Permissions>,
+    I: IntoIterator<Item = (R, C)>,
+>(
+    writer: &mut W,
+    format: Format,
+    channels: &[ChannelId],
+    rows: I,
+) -> IoResult<()> {
+    let header = channels.iter().map(ToString::to_string);
+    format.write_row(writer, "id", header)?;
+
+    for (label, cells) in rows {
+        let cells = cells.into_iter().map(|permissions| {
+            names::iter(permissions)
+                .map(|(_, name)| name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+
+        format.write_row(writer, &label.to_string(), cells)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        guild::Permissions,
+        id::{ChannelId, RoleId},
+    };
+
+    assert_impl_all!(Format: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("plain", Format::Csv.field("plain"));
+        assert_eq!("\"a, \"\"b\"\"\"", Format::Csv.field("a, \"b\""));
+        assert_eq!("a, \"b\"", Format::Tsv.field("a, \"b\""));
+        assert_eq!("a b c", Format::Tsv.field("a\tb\nc"));
+    }
+
+    #[test]
+    fn test_permissions() {
+        let mut csv = Vec::new();
+        super::permissions(
+            &mut csv,
+            Format::Csv,
+            vec![("Mods, Admins", Permissions::KICK_MEMBERS)],
+        )
+        .unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        assert_eq!(2, lines.len());
+        assert_eq!(
+            Permissions::all().bits().count_ones() as usize + 1,
+            lines[0].split(',').count()
+        );
+        assert!(lines[1].starts_with("\"Mods, Admins\",false,true,false,"));
+    }
+
+    #[test]
+    fn test_channels() {
+        let mut csv = Vec::new();
+        let rows = vec![(
+            RoleId(1),
+            vec![
+                Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+                Permissions::SPEAK,
+            ],
+        )];
+
+        super::channels(&mut csv, Format::Csv, &[ChannelId(2), ChannelId(3)], rows).unwrap();
+
+        assert_eq!(
+            "id,2,3\n1,\"View Channel, Send Messages\",Speak\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Extension traits on `twilight-model` types.
+//!
+//! [`MemberExt`] goes from a [`Member`] and the roles of their guild to their
+//! permissions in one call, for bots that have both at hand and don't need to
+//! configure a calculator.
+//!
+//! [`Member`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Member.html
+//! [`MemberExt`]: trait.MemberExt.html
+
+use super::{model, Calculator, CalculatorError};
+use twilight_model::{
+    channel::GuildChannel,
+    guild::{Member, Permissions, Role},
+};
+
+/// Calculate the permissions of a [`Member`].
+///
+/// The owner of the guild isn't known from a member and its roles, so the
+/// owner's permissions are calculated from their roles like any other
+/// member's. Use [`GuildMember`] when the owner matters.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::GuildChannel,
+///     guild::{Member, Permissions, Role},
+/// };
+/// use twilight_permission_calculator::ext::MemberExt;
+///
+/// # let (member, guild_roles, channel): (Member, Vec<Role>, GuildChannel) = unimplemented!();
+/// if member.permissions_in(&guild_roles, &channel)?.contains(Permissions::SEND_MESSAGES) {
+///     println!("member can send messages in the channel");
+/// }
+/// # Ok(()) }
+/// ```
+///
+/// [`GuildMember`]: ../model/struct.GuildMember.html
+/// [`Member`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Member.html
+pub trait MemberExt {
+    /// Calculate the guild-level permissions of the member from the roles of
+    /// their guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::root`].
+    ///
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    fn guild_permissions(&self, guild_roles: &[Role]) -> Result<Permissions, CalculatorError>;
+
+    /// Calculate the permissions of the member in a channel from the roles of
+    /// their guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    fn permissions_in(
+        &self,
+        guild_roles: &[Role],
+        channel: &GuildChannel,
+    ) -> Result<Permissions, CalculatorError>;
+}
+
+impl MemberExt for Member {
+    fn guild_permissions(&self, guild_roles: &[Role]) -> Result<Permissions, CalculatorError> {
+        let member_roles = model::member_roles(self.guild_id, guild_roles, self);
+
+        Calculator::new(self.guild_id, self.user.id, &member_roles).root()
+    }
+
+    fn permissions_in(
+        &self,
+        guild_roles: &[Role],
+        channel: &GuildChannel,
+    ) -> Result<Permissions, CalculatorError> {
+        let member_roles = model::member_roles(self.guild_id, guild_roles, self);
+
+        Calculator::new(self.guild_id, self.user.id, &member_roles).in_guild_channel(channel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemberExt;
+    use crate::{test_util::role, CalculatorError};
+    use static_assertions::{assert_impl_all, assert_obj_safe};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            CategoryChannel, ChannelType, GuildChannel,
+        },
+        guild::{Member, Permissions},
+        id::{ChannelId, GuildId, UserId},
+    };
+
+    assert_impl_all!(Member: MemberExt);
+    assert_obj_safe!(MemberExt);
+
+    #[test]
+    fn test_permissions_in() {
+        let guild_roles = &[
+            role(1, Permissions::VIEW_CHANNEL),
+            role(2, Permissions::MANAGE_CHANNELS),
+        ];
+        let member: Member = serde_json::from_str(
+            r#"{"deaf":false,"guild_id":"1","joined_at":"2021-01-01T00:00:00.000000+00:00","mute":false,"roles":["2"],"user":{"avatar":null,"discriminator":"0001","id":"3","username":"user"}}"#,
+        )
+        .unwrap();
+        let channel = GuildChannel::Category(CategoryChannel {
+            guild_id: Some(GuildId(1)),
+            id: ChannelId(4),
+            kind: ChannelType::GuildCategory,
+            name: "category".to_owned(),
+            permission_overwrites: vec![PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::MANAGE_CHANNELS,
+                kind: PermissionOverwriteType::Member(UserId(3)),
+            }],
+            position: 0,
+        });
+
+        assert_eq!(
+            Permissions::MANAGE_CHANNELS | Permissions::VIEW_CHANNEL,
+            member.guild_permissions(guild_roles).unwrap(),
+        );
+        assert_eq!(
+            Permissions::VIEW_CHANNEL,
+            member.permissions_in(guild_roles, &channel).unwrap(),
+        );
+        assert!(matches!(
+            member.permissions_in(&guild_roles[1..], &channel),
+            Err(CalculatorError::EveryoneRoleMissing { .. })
+        ));
+    }
+}
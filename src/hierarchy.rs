@@ -0,0 +1,776 @@
+//! Checks based on the hierarchy of roles.
+//!
+//! Besides permissions, what members can do to other members and to roles is
+//! limited by the positions of their roles. Roles that are managed by a bot,
+//! an integration, or server boosting are further limited: they're assigned
+//! and removed by Discord, so no member can assign, remove, or delete them.
+//!
+//! Roles are given as [`HierarchyRole`]s, which can be created from a
+//! `twilight-model` [`Role`]. [`HierarchyCalculator`] checks what a member
+//! can do to other members and to roles based on their roles.
+//!
+//! [`HierarchyCalculator`]: struct.HierarchyCalculator.html
+//! [`HierarchyRole`]: struct.HierarchyRole.html
+//! [`Role`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Role.html
+
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    guild::{Permissions, Role},
+    id::{GuildId, RoleId, UserId},
+};
+
+/// What manages a role.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ManagedBy {
+    /// Role of a bot, created when it joined the guild.
+    Bot,
+    /// Role of an integration, such as a Twitch subscriber role.
+    Integration,
+    /// Role given to members boosting the guild.
+    PremiumSubscriber,
+    /// Role is managed, but its tags don't say by what.
+    Unknown,
+}
+
+/// Role of a guild, as needed for hierarchy checks.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct HierarchyRole {
+    /// Color of the role, or 0 if it doesn't have one.
+    pub color: u32,
+    /// Whether members with the role are displayed separately from others.
+    pub hoist: bool,
+    /// ID of the role.
+    pub id: RoleId,
+    /// What manages the role, if anything.
+    pub managed: Option<ManagedBy>,
+    /// Position of the role.
+    pub position: i64,
+}
+
+impl HierarchyRole {
+    /// Create a role that isn't managed, hoisted, or colored.
+    pub const fn new(id: RoleId, position: i64) -> Self {
+        Self {
+            color: 0,
+            hoist: false,
+            id,
+            managed: None,
+            position,
+        }
+    }
+
+    /// Whether the role is managed by a bot, an integration, or server
+    /// boosting.
+    pub fn is_managed(&self) -> bool {
+        self.managed.is_some()
+    }
+}
+
+impl From<&Role> for HierarchyRole {
+    fn from(role: &Role) -> Self {
+        let managed = if role.managed {
+            Some(match &role.tags {
+                Some(tags) if tags.bot_id.is_some() => ManagedBy::Bot,
+                Some(tags) if tags.integration_id.is_some() => ManagedBy::Integration,
+                Some(tags) if tags.premium_subscriber => ManagedBy::PremiumSubscriber,
+                _ => ManagedBy::Unknown,
+            })
+        } else {
+            None
+        };
+
+        Self {
+            color: role.color,
+            hoist: role.hoist,
+            id: role.id,
+            managed,
+            position: role.position,
+        }
+    }
+}
+
+/// Compare the positions of two roles like Discord does.
+///
+/// Roles are ordered by their positions, and roles with the same position are
+/// ordered by their IDs, where the role with the lower ID is higher. Sorting
+/// roles with this ordering and reversing them gives the order that the
+/// Discord client displays them in.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+/// use twilight_model::id::RoleId;
+/// use twilight_permission_calculator::hierarchy::{self, HierarchyRole};
+///
+/// let older = HierarchyRole::new(RoleId(2), 1);
+/// let newer = HierarchyRole::new(RoleId(3), 1);
+///
+/// assert_eq!(Ordering::Greater, hierarchy::cmp_roles(&older, &newer));
+/// ```
+pub fn cmp_roles(a: &HierarchyRole, b: &HierarchyRole) -> Ordering {
+    a.position.cmp(&b.position).then_with(|| b.id.cmp(&a.id))
+}
+
+/// Highest of a member's roles.
+///
+/// Returns `None` if there are no roles.
+pub fn highest(roles: &[HierarchyRole]) -> Option<&HierarchyRole> {
+    top(roles.iter())
+}
+
+/// Highest of a member's roles that is hoisted, which is the role that the
+/// member is grouped under in the member list.
+///
+/// Returns `None` if none of the roles are hoisted.
+pub fn highest_hoisted(roles: &[HierarchyRole]) -> Option<&HierarchyRole> {
+    top(roles.iter().filter(|role| role.hoist))
+}
+
+/// Highest of a member's roles that has a color, which is the role that
+/// provides the color of the member's name.
+///
+/// Returns `None` if none of the roles have a color.
+pub fn color_role(roles: &[HierarchyRole]) -> Option<&HierarchyRole> {
+    top(roles.iter().filter(|role| role.color != 0))
+}
+
+/// Highest of some roles, as ordered by [`cmp_roles`].
+///
+/// [`cmp_roles`]: fn.cmp_roles.html
+fn top<'a, I: Iterator<Item = &'a HierarchyRole>>(roles: I) -> Option<&'a HierarchyRole> {
+    roles.max_by(|a, b| cmp_roles(a, b))
+}
+
+/// Member acting on other members or on roles.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Actor<'a> {
+    /// Guild-level permissions of the member, such as calculated via
+    /// [`Calculator::root`].
+    ///
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    pub permissions: Permissions,
+    /// Roles of the member, not including the `@everyone` role.
+    pub roles: &'a [HierarchyRole],
+    /// ID of the member.
+    pub user_id: UserId,
+}
+
+/// Moderation action taken by a member on another member.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ModerationAction {
+    /// Banning the member from the guild.
+    Ban,
+    /// Kicking the member from the guild.
+    Kick,
+}
+
+impl ModerationAction {
+    /// Permission required to take the action.
+    pub fn permission(self) -> Permissions {
+        match self {
+            Self::Ban => Permissions::BAN_MEMBERS,
+            Self::Kick => Permissions::KICK_MEMBERS,
+        }
+    }
+}
+
+/// Reason that a member can't take a moderation action on another member.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ModerateError {
+    /// Member doesn't have the permission required for the action.
+    MissingPermission {
+        /// Required permission.
+        permission: Permissions,
+    },
+    /// Member's highest role isn't higher than the target's highest role.
+    NotHigher {
+        /// Highest role of the member, or `None` if they only have the
+        /// `@everyone` role.
+        actor_highest: Option<HierarchyRole>,
+        /// Highest role of the target, or `None` if they only have the
+        /// `@everyone` role.
+        target_highest: Option<HierarchyRole>,
+    },
+    /// Target is the owner of the guild.
+    TargetOwner,
+}
+
+impl Display for ModerateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingPermission { .. } => {
+                f.write_str("member doesn't have the permission required for the action")
+            }
+            Self::NotHigher { .. } => {
+                f.write_str("member's highest role isn't higher than the target's")
+            }
+            Self::TargetOwner => f.write_str("target is the owner of the guild"),
+        }
+    }
+}
+
+impl Error for ModerateError {}
+
+/// Reason that a member can't manage a role.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ManageRoleError {
+    /// Role is the `@everyone` role, which every member has.
+    Everyone {
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Role is managed, so only Discord assigns and removes it.
+    Managed {
+        /// What manages the role.
+        managed_by: ManagedBy,
+        /// ID of the role.
+        role_id: RoleId,
+    },
+    /// Member doesn't have the Manage Roles permission.
+    MissingPermission,
+    /// Role isn't below the member's highest role.
+    NotBelow {
+        /// Highest role of the member, or `None` if they only have the
+        /// `@everyone` role.
+        actor_highest: Option<HierarchyRole>,
+        /// ID of the role.
+        role_id: RoleId,
+    },
+}
+
+impl Display for ManageRoleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Everyone { .. } => f.write_str("role is the @everyone role"),
+            Self::Managed { role_id, .. } => {
+                f.write_fmt(format_args!("role {} is managed", role_id))
+            }
+            Self::MissingPermission => {
+                f.write_str("member doesn't have the manage roles permission")
+            }
+            Self::NotBelow { role_id, .. } => f.write_fmt(format_args!(
+                "role {} isn't below the member's highest role",
+                role_id
+            )),
+        }
+    }
+}
+
+impl Error for ManageRoleError {}
+
+/// Check whether a role can be assigned to and removed from members.
+///
+/// # Errors
+///
+/// Returns [`ManageRoleError::Everyone`] if the role is the `@everyone` role.
+///
+/// Returns [`ManageRoleError::Managed`] if the role is managed.
+///
+/// [`ManageRoleError::Everyone`]: enum.ManageRoleError.html#variant.Everyone
+/// [`ManageRoleError::Managed`]: enum.ManageRoleError.html#variant.Managed
+pub fn check_assignable(guild_id: GuildId, role: &HierarchyRole) -> Result<(), ManageRoleError> {
+    if role.id.0 == guild_id.0 {
+        return Err(ManageRoleError::Everyone { role_id: role.id });
+    }
+
+    if let Some(managed_by) = role.managed {
+        return Err(ManageRoleError::Managed {
+            managed_by,
+            role_id: role.id,
+        });
+    }
+
+    Ok(())
+}
+
+/// Check what members can do to other members based on the hierarchy of
+/// their roles.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::hierarchy::{
+///     Actor, HierarchyCalculator, HierarchyRole, ModerationAction,
+/// };
+///
+/// let moderator_roles = &[HierarchyRole::new(RoleId(2), 5)];
+/// let moderator = Actor {
+///     permissions: Permissions::KICK_MEMBERS,
+///     roles: moderator_roles,
+///     user_id: UserId(3),
+/// };
+/// let target_roles = &[HierarchyRole::new(RoleId(4), 1)];
+///
+/// let calculator = HierarchyCalculator::new(GuildId(1), UserId(6));
+///
+/// assert!(calculator
+///     .can_moderate(&moderator, UserId(7), target_roles, ModerationAction::Kick)
+///     .is_ok());
+/// assert!(calculator
+///     .can_moderate(&moderator, UserId(7), target_roles, ModerationAction::Ban)
+///     .is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[must_use = "calculators aren't useful if you don't check anything"]
+pub struct HierarchyCalculator {
+    guild_id: GuildId,
+    owner_id: UserId,
+}
+
+impl HierarchyCalculator {
+    /// Create a calculator for a guild.
+    pub const fn new(guild_id: GuildId, owner_id: UserId) -> Self {
+        Self { guild_id, owner_id }
+    }
+
+    /// Check whether a member can take a moderation action on a target.
+    ///
+    /// The owner of the guild can moderate every other member and can't be
+    /// moderated. Otherwise, the member needs the action's permission and
+    /// their highest role must be higher than the target's highest role.
+    ///
+    /// Timing out members isn't included, since the Moderate Members
+    /// permission isn't known to this version of `twilight-model`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ModerateError::TargetOwner`] if the target is the owner.
+    ///
+    /// Returns [`ModerateError::MissingPermission`] if the member doesn't
+    /// have the action's permission.
+    ///
+    /// Returns [`ModerateError::NotHigher`] if the member's highest role isn't
+    /// higher than the target's.
+    ///
+    /// [`ModerateError::MissingPermission`]: enum.ModerateError.html#variant.MissingPermission
+    /// [`ModerateError::NotHigher`]: enum.ModerateError.html#variant.NotHigher
+    /// [`ModerateError::TargetOwner`]: enum.ModerateError.html#variant.TargetOwner
+    pub fn can_moderate(
+        &self,
+        actor: &Actor<'_>,
+        target_id: UserId,
+        target_roles: &[HierarchyRole],
+        action: ModerationAction,
+    ) -> Result<(), ModerateError> {
+        if target_id == self.owner_id {
+            return Err(ModerateError::TargetOwner);
+        }
+
+        if actor.user_id == self.owner_id {
+            return Ok(());
+        }
+
+        let permission = action.permission();
+
+        if !actor.permissions.contains(permission) {
+            return Err(ModerateError::MissingPermission { permission });
+        }
+
+        let actor_highest = self.highest(actor.roles);
+        let target_highest = self.highest(target_roles);
+
+        if cmp_highest(actor_highest.as_ref(), target_highest.as_ref()) != Ordering::Greater {
+            return Err(ModerateError::NotHigher {
+                actor_highest,
+                target_highest,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a member can edit or delete a role.
+    ///
+    /// The owner of the guild can manage every role. Otherwise, the member
+    /// needs the Manage Roles permission and the role must be below their
+    /// highest role. The `@everyone` role is below every other role.
+    ///
+    /// Managed roles can be edited, but they can't be deleted; check
+    /// [`check_assignable`] as well before deleting a role.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManageRoleError::MissingPermission`] if the member doesn't
+    /// have the Manage Roles permission.
+    ///
+    /// Returns [`ManageRoleError::NotBelow`] if the role isn't below the
+    /// member's highest role.
+    ///
+    /// [`ManageRoleError::MissingPermission`]: enum.ManageRoleError.html#variant.MissingPermission
+    /// [`ManageRoleError::NotBelow`]: enum.ManageRoleError.html#variant.NotBelow
+    /// [`check_assignable`]: fn.check_assignable.html
+    pub fn can_manage_role(
+        &self,
+        actor: &Actor<'_>,
+        role: &HierarchyRole,
+    ) -> Result<(), ManageRoleError> {
+        if actor.user_id == self.owner_id {
+            return Ok(());
+        }
+
+        if !actor.permissions.contains(Permissions::MANAGE_ROLES) {
+            return Err(ManageRoleError::MissingPermission);
+        }
+
+        if role.id.0 == self.guild_id.0 {
+            return Ok(());
+        }
+
+        let actor_highest = self.highest(actor.roles);
+
+        if cmp_highest(actor_highest.as_ref(), Some(role)) != Ordering::Greater {
+            return Err(ManageRoleError::NotBelow {
+                actor_highest,
+                role_id: role.id,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a member can assign a role to or remove it from members.
+    ///
+    /// In addition to the checks of [`can_manage_role`], the role can't be
+    /// the `@everyone` role or managed, even for the owner.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`check_assignable`] and
+    /// [`can_manage_role`].
+    ///
+    /// [`can_manage_role`]: #method.can_manage_role
+    /// [`check_assignable`]: fn.check_assignable.html
+    pub fn can_assign_role(
+        &self,
+        actor: &Actor<'_>,
+        role: &HierarchyRole,
+    ) -> Result<(), ManageRoleError> {
+        check_assignable(self.guild_id, role)?;
+
+        self.can_manage_role(actor, role)
+    }
+
+    /// Highest of a member's roles, not counting the `@everyone` role.
+    fn highest(&self, roles: &[HierarchyRole]) -> Option<HierarchyRole> {
+        top(roles.iter().filter(|role| role.id.0 != self.guild_id.0)).copied()
+    }
+}
+
+/// Compare the highest roles of members with [`cmp_roles`], where members
+/// with only the `@everyone` role are at the bottom.
+///
+/// [`cmp_roles`]: fn.cmp_roles.html
+fn cmp_highest(a: Option<&HierarchyRole>, b: Option<&HierarchyRole>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => cmp_roles(a, b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Actor, HierarchyCalculator, HierarchyRole, ManageRoleError, ManagedBy, ModerateError,
+        ModerationAction,
+    };
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        guild::{Permissions, Role, RoleTags},
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Actor<'_>: permissions, roles, user_id);
+    assert_fields!(HierarchyRole: color, hoist, id, managed, position);
+    assert_fields!(ManageRoleError::Everyone: role_id);
+    assert_fields!(ManageRoleError::Managed: managed_by, role_id);
+    assert_fields!(ManageRoleError::NotBelow: actor_highest, role_id);
+    assert_fields!(ModerateError::MissingPermission: permission);
+    assert_fields!(ModerateError::NotHigher: actor_highest, target_highest);
+    assert_impl_all!(Actor<'_>: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        HierarchyCalculator: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        HierarchyRole: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        ManageRoleError: Clone,
+        Copy,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(ManagedBy: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        ModerateError: Clone,
+        Copy,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        ModerationAction: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_from_role() {
+        let mut role = Role {
+            color: 0,
+            hoist: false,
+            id: RoleId(2),
+            managed: false,
+            mentionable: false,
+            name: "bot".to_owned(),
+            permissions: Permissions::empty(),
+            position: 3,
+            tags: Some(RoleTags {
+                bot_id: Some(UserId(4)),
+                integration_id: None,
+                premium_subscriber: false,
+            }),
+        };
+        assert_eq!(HierarchyRole::new(RoleId(2), 3), HierarchyRole::from(&role));
+
+        role.managed = true;
+        assert_eq!(Some(ManagedBy::Bot), HierarchyRole::from(&role).managed);
+
+        role.tags = None;
+        assert_eq!(Some(ManagedBy::Unknown), HierarchyRole::from(&role).managed);
+    }
+
+    #[test]
+    fn test_cmp_roles() {
+        let mut roles = [
+            HierarchyRole::new(RoleId(5), 1),
+            HierarchyRole::new(RoleId(2), 2),
+            HierarchyRole::new(RoleId(4), 1),
+        ];
+        roles.sort_by(super::cmp_roles);
+
+        assert_eq!(
+            [RoleId(5), RoleId(4), RoleId(2)],
+            [roles[0].id, roles[1].id, roles[2].id],
+        );
+
+        // Members with tied highest roles are ordered by ID too.
+        let calculator = HierarchyCalculator::new(GuildId(1), UserId(6));
+        let moderator = Actor {
+            permissions: Permissions::KICK_MEMBERS | Permissions::MANAGE_ROLES,
+            roles: &roles[1..2],
+            user_id: UserId(7),
+        };
+        assert!(calculator
+            .can_moderate(&moderator, UserId(8), &roles[..1], ModerationAction::Kick)
+            .is_ok());
+        assert!(calculator.can_manage_role(&moderator, &roles[0]).is_ok());
+
+        let moderator = Actor {
+            roles: &roles[..1],
+            ..moderator
+        };
+        assert!(calculator
+            .can_moderate(&moderator, UserId(8), &roles[1..2], ModerationAction::Kick)
+            .is_err());
+    }
+
+    #[test]
+    fn test_highest() {
+        let roles = &[
+            HierarchyRole {
+                color: 0x00ff_0000,
+                ..HierarchyRole::new(RoleId(2), 1)
+            },
+            HierarchyRole {
+                hoist: true,
+                ..HierarchyRole::new(RoleId(3), 2)
+            },
+            HierarchyRole::new(RoleId(4), 3),
+        ];
+
+        assert_eq!(Some(RoleId(4)), super::highest(roles).map(|role| role.id));
+        assert_eq!(
+            Some(RoleId(3)),
+            super::highest_hoisted(roles).map(|role| role.id),
+        );
+        assert_eq!(
+            Some(RoleId(2)),
+            super::color_role(roles).map(|role| role.id)
+        );
+        assert!(super::highest(&[]).is_none());
+        assert!(super::color_role(&roles[1..]).is_none());
+    }
+
+    #[test]
+    fn test_check_assignable() {
+        let guild_id = GuildId(1);
+        let mut role = HierarchyRole::new(RoleId(2), 1);
+        assert!(super::check_assignable(guild_id, &role).is_ok());
+
+        role.managed = Some(ManagedBy::PremiumSubscriber);
+        assert_eq!(
+            Err(ManageRoleError::Managed {
+                managed_by: ManagedBy::PremiumSubscriber,
+                role_id: RoleId(2),
+            }),
+            super::check_assignable(guild_id, &role),
+        );
+        assert_eq!(
+            Err(ManageRoleError::Everyone { role_id: RoleId(1) }),
+            super::check_assignable(guild_id, &HierarchyRole::new(RoleId(1), 0)),
+        );
+    }
+
+    #[test]
+    fn test_can_moderate() {
+        let calculator = HierarchyCalculator::new(GuildId(1), UserId(2));
+        let moderator_roles = &[
+            HierarchyRole::new(RoleId(1), 0),
+            HierarchyRole::new(RoleId(3), 2),
+            HierarchyRole::new(RoleId(4), 5),
+        ];
+        let moderator = Actor {
+            permissions: Permissions::BAN_MEMBERS,
+            roles: moderator_roles,
+            user_id: UserId(5),
+        };
+        let lower = &[HierarchyRole::new(RoleId(3), 2)];
+        let same = &[HierarchyRole::new(RoleId(4), 5)];
+
+        assert!(calculator
+            .can_moderate(&moderator, UserId(7), lower, ModerationAction::Ban)
+            .is_ok());
+        assert!(calculator
+            .can_moderate(&moderator, UserId(7), &[], ModerationAction::Ban)
+            .is_ok());
+        assert_eq!(
+            Err(ModerateError::MissingPermission {
+                permission: Permissions::KICK_MEMBERS,
+            }),
+            calculator.can_moderate(&moderator, UserId(7), lower, ModerationAction::Kick),
+        );
+        assert_eq!(
+            Err(ModerateError::NotHigher {
+                actor_highest: Some(HierarchyRole::new(RoleId(4), 5)),
+                target_highest: Some(HierarchyRole::new(RoleId(4), 5)),
+            }),
+            calculator.can_moderate(&moderator, UserId(7), same, ModerationAction::Ban),
+        );
+        assert_eq!(
+            Err(ModerateError::TargetOwner),
+            calculator.can_moderate(&moderator, UserId(2), &[], ModerationAction::Ban),
+        );
+
+        // The owner needs neither permissions nor roles.
+        let owner = Actor {
+            permissions: Permissions::empty(),
+            roles: &[],
+            user_id: UserId(2),
+        };
+        assert!(calculator
+            .can_moderate(&owner, UserId(5), moderator_roles, ModerationAction::Ban)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_can_manage_role() {
+        let calculator = HierarchyCalculator::new(GuildId(1), UserId(2));
+        let manager_roles = &[HierarchyRole::new(RoleId(3), 4)];
+        let manager = Actor {
+            permissions: Permissions::MANAGE_ROLES,
+            roles: manager_roles,
+            user_id: UserId(5),
+        };
+        let lower = HierarchyRole::new(RoleId(6), 3);
+        let higher = HierarchyRole::new(RoleId(7), 8);
+        let managed = HierarchyRole {
+            managed: Some(ManagedBy::Bot),
+            ..lower
+        };
+
+        assert!(calculator.can_manage_role(&manager, &lower).is_ok());
+        assert!(calculator.can_assign_role(&manager, &lower).is_ok());
+        assert!(calculator
+            .can_manage_role(&manager, &HierarchyRole::new(RoleId(1), 0))
+            .is_ok());
+        assert_eq!(
+            Err(ManageRoleError::NotBelow {
+                actor_highest: Some(HierarchyRole::new(RoleId(3), 4)),
+                role_id: RoleId(3),
+            }),
+            calculator.can_manage_role(&manager, &manager_roles[0]),
+        );
+        assert!(calculator.can_manage_role(&manager, &managed).is_ok());
+        assert!(matches!(
+            calculator.can_assign_role(&manager, &managed),
+            Err(ManageRoleError::Managed { .. })
+        ));
+
+        let member = Actor {
+            permissions: Permissions::empty(),
+            ..manager
+        };
+        assert_eq!(
+            Err(ManageRoleError::MissingPermission),
+            calculator.can_manage_role(&member, &lower),
+        );
+
+        let owner = Actor {
+            permissions: Permissions::empty(),
+            roles: &[],
+            user_id: UserId(2),
+        };
+        assert!(calculator.can_assign_role(&owner, &higher).is_ok());
+        assert!(calculator.can_assign_role(&owner, &managed).is_err());
+    }
+}
@@ -0,0 +1,664 @@
+//! Long-lived index of a guild's roles and channel overwrites.
+//!
+//! Bots that answer many permission queries for the same guild would
+//! otherwise pass every role and scan every overwrite of a channel on each
+//! query. [`GuildPermissionIndex`] owns the guild's roles keyed by ID and each
+//! channel's overwrites grouped by role and member, with the `@everyone`
+//! role's split out, so that each query only looks at the roles and
+//! overwrites of the member being queried.
+//!
+//! The index is kept up to date by inserting and removing roles and channels
+//! as they change.
+//!
+//! [`GuildPermissionIndex`]: struct.GuildPermissionIndex.html
+
+use super::{
+    channel::ChannelCalculator, model::channel_parts, require::MissingInChannel, CalculatorError,
+    InfallibleCalculator,
+};
+use std::collections::HashMap;
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType, GuildChannel},
+    guild::{Guild, Permissions},
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Roles and members of a guild that effectively have the Administrator
+/// permission.
+///
+/// Created via [`GuildPermissionIndex::administrators`].
+///
+/// [`GuildPermissionIndex::administrators`]: struct.GuildPermissionIndex.html#method.administrators
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Administrators {
+    /// Members who have the Administrator permission via any of their roles,
+    /// or because they're the owner, in the order they were given.
+    pub members: Vec<UserId>,
+    /// ID of the owner of the guild, who has every permission, if known.
+    pub owner_id: Option<UserId>,
+    /// Roles that grant the Administrator permission, in ascending order of
+    /// ID.
+    ///
+    /// Includes the `@everyone` role if it grants the permission, in which
+    /// case every member is an administrator.
+    pub roles: Vec<RoleId>,
+}
+
+/// Index of a guild's roles and channel overwrites for calculating the
+/// permissions of its members.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::index::GuildPermissionIndex;
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+///     (RoleId(2), Permissions::empty()),
+/// ];
+/// let announcements = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+/// let channels = vec![(ChannelId(3), ChannelType::GuildText, &announcements[..])];
+///
+/// let mut index = GuildPermissionIndex::new(GuildId(1), guild_roles, channels)?;
+///
+/// assert_eq!(
+///     Some(Permissions::VIEW_CHANNEL),
+///     index.in_channel(UserId(4), &[RoleId(2)], ChannelId(3)),
+/// );
+///
+/// // The channel's overwrite is removed.
+/// index.insert_channel(ChannelId(3), ChannelType::GuildText, &[]);
+///
+/// assert!(index
+///     .in_channel(UserId(4), &[RoleId(2)], ChannelId(3))
+///     .unwrap()
+///     .contains(Permissions::SEND_MESSAGES));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuildPermissionIndex {
+    channels: HashMap<ChannelId, ChannelCalculator>,
+    everyone: Permissions,
+    guild_id: GuildId,
+    owner_id: Option<UserId>,
+    roles: HashMap<RoleId, Permissions>,
+}
+
+impl GuildPermissionIndex {
+    /// Create an index of a guild's roles and channels.
+    ///
+    /// The guild's roles must include the `@everyone` role, which has the same
+    /// ID as the guild. Channels are given as tuples of the channel's ID,
+    /// type, and permission overwrites.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the guild's roles.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn new<'b, U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>>(
+        guild_id: GuildId,
+        guild_roles: &[(RoleId, Permissions)],
+        channels: U,
+    ) -> Result<Self, CalculatorError> {
+        let everyone = guild_roles
+            .iter()
+            .find(|(id, _)| id.0 == guild_id.0)
+            .map(|(_, permissions)| *permissions)
+            .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+
+        let mut index = Self {
+            channels: HashMap::new(),
+            everyone,
+            guild_id,
+            owner_id: None,
+            roles: guild_roles
+                .iter()
+                .copied()
+                .filter(|(id, _)| id.0 != guild_id.0)
+                .collect(),
+        };
+
+        for (channel_id, channel_type, channel_overwrites) in channels {
+            index.insert_channel(channel_id, channel_type, channel_overwrites);
+        }
+
+        Ok(index)
+    }
+
+    /// Create an index from a guild and its channels, such as the responses of
+    /// the REST API's get guild and get guild channels routes.
+    ///
+    /// Guilds fetched over REST don't include their channels, so they're
+    /// passed separately; the guild's own channels are ignored. The owner of
+    /// the guild is configured from the guild. Members aren't indexed and are
+    /// given to each query instead, or inserted into a [`PermissionCache`] page
+    /// by page.
+    ///
+    /// # Examples
+    ///
+    /// Load a guild fetched over REST into a cache:
+    ///
+    /// ```rust,no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::GuildChannel,
+    ///     guild::{Guild, Member},
+    ///     id::{ChannelId, GuildId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{
+    ///     cache::PermissionCache,
+    ///     index::GuildPermissionIndex,
+    /// };
+    ///
+    /// # fn guild() -> Guild { unimplemented!() }
+    /// # fn channels() -> Vec<GuildChannel> { Vec::new() }
+    /// # fn member_pages() -> Vec<Vec<Member>> { Vec::new() }
+    /// let (guild, channels) = (guild(), channels());
+    /// let mut cache = PermissionCache::new();
+    /// cache.insert_guild(GuildPermissionIndex::from_guild(&guild, &channels)?);
+    ///
+    /// for page in member_pages() {
+    ///     cache.insert_members(guild.id, &page);
+    /// }
+    ///
+    /// let permissions = cache.member_in_channel(GuildId(1), UserId(2), ChannelId(3));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the guild's roles, such as when the guild is unavailable.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    /// [`PermissionCache`]: ../cache/struct.PermissionCache.html
+    pub fn from_guild<'b, U: IntoIterator<Item = &'b GuildChannel>>(
+        guild: &Guild,
+        channels: U,
+    ) -> Result<Self, CalculatorError> {
+        let guild_roles = guild
+            .roles
+            .iter()
+            .map(|role| (role.id, role.permissions))
+            .collect::<Vec<_>>();
+        let channels = channels.into_iter().map(|channel| {
+            let (channel_id, _, channel_type, overwrites) = channel_parts(channel);
+
+            (channel_id, channel_type, overwrites)
+        });
+
+        Self::new(guild.id, &guild_roles, channels).map(|index| index.owner_id(guild.owner_id))
+    }
+
+    /// Configure the ID of the owner of the guild.
+    ///
+    /// Refer to the documentation for [`Calculator::owner_id`].
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id.replace(owner_id);
+
+        self
+    }
+
+    /// ID of the guild.
+    pub fn guild_id(&self) -> GuildId {
+        self.guild_id
+    }
+
+    /// Set the ID of the owner of the guild, such as when ownership is
+    /// transferred.
+    pub fn set_owner_id(&mut self, owner_id: Option<UserId>) {
+        self.owner_id = owner_id;
+    }
+
+    /// Insert a channel, replacing the channel's previous type and overwrites
+    /// if it was already indexed.
+    pub fn insert_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &mut self,
+        channel_id: ChannelId,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) {
+        self.channels.insert(
+            channel_id,
+            ChannelCalculator::new(self.guild_id, channel_type, channel_overwrites),
+        );
+    }
+
+    /// Remove a channel, returning whether it was indexed.
+    pub fn remove_channel(&mut self, channel_id: ChannelId) -> bool {
+        self.channels.remove(&channel_id).is_some()
+    }
+
+    /// Insert a role, replacing the role's previous permissions if it was
+    /// already indexed.
+    ///
+    /// The `@everyone` role's permissions are replaced if its ID is given.
+    pub fn insert_role(&mut self, role_id: RoleId, permissions: Permissions) {
+        if role_id.0 == self.guild_id.0 {
+            self.everyone = permissions;
+        } else {
+            self.roles.insert(role_id, permissions);
+        }
+    }
+
+    /// Remove a role, returning whether it was indexed.
+    ///
+    /// The `@everyone` role can't be removed.
+    pub fn remove_role(&mut self, role_id: RoleId) -> bool {
+        self.roles.remove(&role_id).is_some()
+    }
+
+    /// Remove the roles that don't match a predicate.
+    pub(crate) fn retain_roles<F: FnMut(RoleId) -> bool>(&mut self, mut f: F) {
+        self.roles.retain(|role_id, _| f(*role_id));
+    }
+
+    /// Calculate the guild-level permissions of a member.
+    ///
+    /// Members are given as their ID and the IDs of their roles, such as from
+    /// [`Member::roles`]; the `@everyone` role doesn't need to be included.
+    /// Roles that aren't indexed are ignored.
+    ///
+    /// [`Member::roles`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Member.html#structfield.roles
+    pub fn root(&self, user_id: UserId, role_ids: &[RoleId]) -> Permissions {
+        let member_roles = self.member_roles(role_ids);
+
+        self.calculator(user_id, &member_roles).root()
+    }
+
+    /// Calculate the permissions of a member in a channel.
+    ///
+    /// Members are given like in [`root`]. Returns `None` if the channel isn't
+    /// indexed.
+    ///
+    /// [`root`]: #method.root
+    pub fn in_channel(
+        &self,
+        user_id: UserId,
+        role_ids: &[RoleId],
+        channel_id: ChannelId,
+    ) -> Option<Permissions> {
+        let channel = self.channels.get(&channel_id)?;
+        let member_roles = self.member_roles(role_ids);
+        let calculator = self.calculator(user_id, &member_roles);
+
+        Some(channel.in_channel_with_root(&calculator.0, calculator.root()))
+    }
+
+    /// Find the members of a set whose guild-level permissions include
+    /// permissions, such as everyone who can ban members.
+    ///
+    /// Members are given like in [`root`], and their IDs are returned in the
+    /// order they were given. Members with the same roles have the same
+    /// guild-level permissions, so they're only calculated once for each
+    /// combination of roles.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::index::GuildPermissionIndex;
+    ///
+    /// let guild_roles = &[
+    ///     (RoleId(1), Permissions::VIEW_CHANNEL),
+    ///     (RoleId(2), Permissions::BAN_MEMBERS),
+    /// ];
+    /// let index = GuildPermissionIndex::new(GuildId(1), guild_roles, Vec::new())?;
+    ///
+    /// let members = vec![
+    ///     (UserId(3), &[][..]),
+    ///     (UserId(4), &[RoleId(2)][..]),
+    /// ];
+    ///
+    /// assert_eq!(
+    ///     vec![UserId(4)],
+    ///     index.members_with(members, Permissions::BAN_MEMBERS),
+    /// );
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`root`]: #method.root
+    pub fn members_with<'b, I: IntoIterator<Item = (UserId, &'b [RoleId])>>(
+        &self,
+        members: I,
+        permissions: Permissions,
+    ) -> Vec<UserId> {
+        let mut roots = HashMap::new();
+
+        members
+            .into_iter()
+            .filter(|(user_id, role_ids)| {
+                self.cached_root(&mut roots, *user_id, role_ids)
+                    .contains(permissions)
+            })
+            .map(|(user_id, _)| user_id)
+            .collect()
+    }
+
+    /// Find the members of a set whose permissions in a channel include
+    /// permissions.
+    ///
+    /// Members are given like in [`root`], and their IDs are returned in the
+    /// order they were given. Like [`Calculator::require_in_all`], required
+    /// guild-level permissions are checked against the members' guild-level
+    /// permissions, which are only calculated once for each combination of
+    /// roles. Returns `None` if the channel isn't indexed.
+    ///
+    /// [`Calculator::require_in_all`]: ../struct.Calculator.html#method.require_in_all
+    /// [`root`]: #method.root
+    pub fn members_with_in_channel<'b, I: IntoIterator<Item = (UserId, &'b [RoleId])>>(
+        &self,
+        members: I,
+        channel_id: ChannelId,
+        permissions: Permissions,
+    ) -> Option<Vec<UserId>> {
+        let channel = self.channels.get(&channel_id)?;
+        let mut roots = HashMap::new();
+
+        let user_ids = members
+            .into_iter()
+            .filter(|(user_id, role_ids)| {
+                let root = self.cached_root(&mut roots, *user_id, role_ids);
+                let member_roles = self.member_roles(role_ids);
+                let calculator = self.calculator(*user_id, &member_roles);
+                let calculated = channel.in_channel_with_root(&calculator.0, root);
+
+                MissingInChannel::new(channel_id, root, calculated, permissions).is_none()
+            })
+            .map(|(user_id, _)| user_id)
+            .collect();
+
+        Some(user_ids)
+    }
+
+    /// Find the roles and members that effectively have the Administrator
+    /// permission.
+    ///
+    /// Members are given like in [`root`]. The owner of the guild has every
+    /// permission, so they're included if they're given.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::index::GuildPermissionIndex;
+    ///
+    /// let guild_roles = &[
+    ///     (RoleId(1), Permissions::VIEW_CHANNEL),
+    ///     (RoleId(2), Permissions::ADMINISTRATOR),
+    /// ];
+    /// let index = GuildPermissionIndex::new(GuildId(1), guild_roles, Vec::new())?
+    ///     .owner_id(UserId(3));
+    ///
+    /// let members = vec![
+    ///     (UserId(3), &[][..]),
+    ///     (UserId(4), &[RoleId(2)][..]),
+    ///     (UserId(5), &[][..]),
+    /// ];
+    /// let administrators = index.administrators(members);
+    ///
+    /// assert_eq!(vec![UserId(3), UserId(4)], administrators.members);
+    /// assert_eq!(vec![RoleId(2)], administrators.roles);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`root`]: #method.root
+    pub fn administrators<'b, I: IntoIterator<Item = (UserId, &'b [RoleId])>>(
+        &self,
+        members: I,
+    ) -> Administrators {
+        let mut roles = self
+            .roles
+            .iter()
+            .filter(|(_, permissions)| permissions.contains(Permissions::ADMINISTRATOR))
+            .map(|(role_id, _)| *role_id)
+            .collect::<Vec<_>>();
+
+        if self.everyone.contains(Permissions::ADMINISTRATOR) {
+            roles.push(RoleId(self.guild_id.0));
+        }
+
+        roles.sort_unstable();
+
+        Administrators {
+            members: self.members_with(members, Permissions::ADMINISTRATOR),
+            owner_id: self.owner_id,
+            roles,
+        }
+    }
+
+    /// Calculate the guild-level permissions of a member, reusing those
+    /// calculated for members with the same roles.
+    fn cached_root(
+        &self,
+        roots: &mut HashMap<Vec<RoleId>, Permissions>,
+        user_id: UserId,
+        role_ids: &[RoleId],
+    ) -> Permissions {
+        if self.owner_id == Some(user_id) {
+            return Permissions::all();
+        }
+
+        let mut key = role_ids
+            .iter()
+            .copied()
+            .filter(|role_id| self.roles.contains_key(role_id))
+            .collect::<Vec<_>>();
+        key.sort_unstable();
+        key.dedup();
+
+        *roots
+            .entry(key)
+            .or_insert_with(|| self.root(user_id, role_ids))
+    }
+
+    /// Look up the permissions of the `@everyone` role and of a member's
+    /// roles.
+    fn member_roles(&self, role_ids: &[RoleId]) -> Vec<(RoleId, Permissions)> {
+        let mut member_roles = Vec::with_capacity(role_ids.len() + 1);
+        member_roles.push((RoleId(self.guild_id.0), self.everyone));
+        member_roles.extend(role_ids.iter().filter_map(|role_id| {
+            self.roles
+                .get(role_id)
+                .map(|permissions| (*role_id, *permissions))
+        }));
+
+        member_roles
+    }
+
+    fn calculator<'a>(
+        &self,
+        user_id: UserId,
+        member_roles: &'a [(RoleId, Permissions)],
+    ) -> InfallibleCalculator<'a> {
+        let calculator = InfallibleCalculator::new(self.guild_id, user_id, member_roles);
+
+        match self.owner_id {
+            Some(owner_id) => calculator.owner_id(owner_id),
+            None => calculator,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Administrators, GuildPermissionIndex};
+    use crate::{Calculator, CalculatorError};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Administrators: members, owner_id, roles);
+    assert_impl_all!(
+        Administrators: Clone,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(GuildPermissionIndex: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_index() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES),
+        ];
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Member(UserId(3)),
+            },
+        ];
+        let mut index = GuildPermissionIndex::new(
+            GuildId(1),
+            guild_roles,
+            vec![(ChannelId(4), ChannelType::GuildText, &overwrites[..])],
+        )
+        .unwrap()
+        .owner_id(UserId(5));
+
+        for user_id in [UserId(3), UserId(5), UserId(6)].iter().copied() {
+            let calculator = Calculator::new(GuildId(1), user_id, guild_roles).owner_id(UserId(5));
+
+            assert_eq!(
+                calculator.root().unwrap(),
+                index.root(user_id, &[RoleId(2), RoleId(7)])
+            );
+            assert_eq!(
+                calculator
+                    .in_channel(ChannelType::GuildText, overwrites)
+                    .ok(),
+                index.in_channel(user_id, &[RoleId(2), RoleId(7)], ChannelId(4)),
+            );
+        }
+
+        assert!(index.in_channel(UserId(3), &[], ChannelId(8)).is_none());
+
+        let members = vec![
+            (UserId(3), &[RoleId(2)][..]),
+            (UserId(5), &[][..]),
+            (UserId(6), &[RoleId(7), RoleId(2)][..]),
+            (UserId(8), &[][..]),
+        ];
+        assert_eq!(
+            vec![UserId(3), UserId(5), UserId(6)],
+            index.members_with(members.clone(), Permissions::SEND_MESSAGES),
+        );
+        assert_eq!(
+            Some(vec![UserId(5), UserId(6)]),
+            index.members_with_in_channel(
+                members.clone(),
+                ChannelId(4),
+                Permissions::SEND_MESSAGES
+            ),
+        );
+        assert_eq!(
+            Some(vec![UserId(5)]),
+            index.members_with_in_channel(members.clone(), ChannelId(4), Permissions::KICK_MEMBERS),
+        );
+        assert!(index
+            .members_with_in_channel(members, ChannelId(8), Permissions::empty())
+            .is_none());
+
+        index.insert_role(RoleId(1), Permissions::empty());
+        index.insert_role(RoleId(2), Permissions::KICK_MEMBERS);
+        assert_eq!(
+            Permissions::KICK_MEMBERS,
+            index.root(UserId(3), &[RoleId(2)])
+        );
+        assert!(index.remove_role(RoleId(2)));
+        assert!(!index.remove_role(RoleId(2)));
+        assert_eq!(Permissions::empty(), index.root(UserId(3), &[RoleId(2)]));
+
+        index.set_owner_id(Some(UserId(3)));
+        assert_eq!(Permissions::all(), index.root(UserId(3), &[]));
+
+        assert!(index.remove_channel(ChannelId(4)));
+        assert!(index.in_channel(UserId(3), &[], ChannelId(4)).is_none());
+
+        assert!(matches!(
+            GuildPermissionIndex::new(GuildId(1), &[], Vec::new()),
+            Err(CalculatorError::EveryoneRoleMissing { .. })
+        ));
+    }
+
+    #[test]
+    fn test_administrators() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::ADMINISTRATOR),
+            (
+                RoleId(3),
+                Permissions::ADMINISTRATOR | Permissions::BAN_MEMBERS,
+            ),
+            (RoleId(4), Permissions::BAN_MEMBERS),
+        ];
+        let mut index = GuildPermissionIndex::new(GuildId(1), guild_roles, Vec::new()).unwrap();
+        let members = vec![
+            (UserId(5), &[RoleId(4)][..]),
+            (UserId(6), &[RoleId(4), RoleId(3)][..]),
+            (UserId(7), &[][..]),
+        ];
+
+        assert_eq!(
+            Administrators {
+                members: vec![UserId(6)],
+                owner_id: None,
+                roles: vec![RoleId(2), RoleId(3)],
+            },
+            index.administrators(members.clone()),
+        );
+
+        index.set_owner_id(Some(UserId(7)));
+        index.insert_role(RoleId(1), Permissions::ADMINISTRATOR);
+        let administrators = index.administrators(members);
+        assert_eq!(
+            vec![UserId(5), UserId(6), UserId(7)],
+            administrators.members
+        );
+        assert_eq!(Some(UserId(7)), administrators.owner_id);
+        assert_eq!(RoleId(1), administrators.roles[0]);
+    }
+}
@@ -0,0 +1,530 @@
+//! Using the permissions that Discord sends with interactions.
+//!
+//! Interactions invoked in a guild include the invoking member's permissions
+//! in the channel the interaction was invoked in. Those permissions are what
+//! Discord itself calculated, so they're correct even when the bot's cache of
+//! roles and overwrites is cold or stale. [`InteractionPermissions`] uses them
+//! as the authoritative result, only calculating locally when they're missing
+//! or when cross-checking is enabled.
+//!
+//! Discord calculates them from the member's roles and the channel's
+//! overwrites alone: they include guild-level permissions such as Change
+//! Nickname and permissions for other types of channels, and permissions that
+//! depend on a denied permission aren't removed. They're compared with the
+//! [raw] permissions of a calculation that applies no dependencies, rather
+//! than with those of [`Calculator::in_channel`].
+//!
+//! Likewise, interactions include the bot's own permissions in the channel as
+//! `app_permissions`. [`AppPermissions`] checks them against what a command
+//! needs before responding, naming the permissions the bot is missing.
+//!
+//! [`AppPermissions`]: struct.AppPermissions.html
+//! [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+//! [`InteractionPermissions`]: struct.InteractionPermissions.html
+//! [raw]: ../mask/struct.Masked.html#structfield.raw
+
+use super::{
+    names,
+    require::{MissingInChannel, RequireError},
+    Calculator, CalculatorError,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::ChannelId,
+};
+
+/// Permissions of the bot sent with an interaction as `app_permissions`.
+///
+/// # Examples
+///
+/// Check that the bot can embed links in the channel before responding with an
+/// embed:
+///
+/// ```rust
+/// use twilight_model::{guild::Permissions, id::ChannelId};
+/// use twilight_permission_calculator::interaction::AppPermissions;
+///
+/// let app_permissions = AppPermissions::new(ChannelId(1), Permissions::SEND_MESSAGES);
+///
+/// if let Err(source) = app_permissions.require(Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES) {
+///     assert_eq!("bot is missing permissions: Embed Links", source.to_string());
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct AppPermissions {
+    channel_id: ChannelId,
+    permissions: Permissions,
+}
+
+impl AppPermissions {
+    /// Create from the bot's permissions in the channel the interaction was
+    /// invoked in.
+    pub fn new(channel_id: ChannelId, permissions: Permissions) -> Self {
+        Self {
+            channel_id,
+            permissions,
+        }
+    }
+
+    /// ID of the channel the interaction was invoked in.
+    pub fn channel_id(&self) -> ChannelId {
+        self.channel_id
+    }
+
+    /// Permissions of the bot in the channel.
+    pub fn permissions(&self) -> Permissions {
+        self.permissions
+    }
+
+    /// Require that the bot has permissions in the channel.
+    ///
+    /// The bot's permissions in the channel already include its guild-level
+    /// permissions, so every required permission is checked against them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MissingAppPermissions`] listing the required permissions that
+    /// the bot doesn't have.
+    ///
+    /// [`MissingAppPermissions`]: struct.MissingAppPermissions.html
+    pub fn require(&self, required: Permissions) -> Result<(), MissingAppPermissions> {
+        let missing = required - self.permissions;
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(MissingAppPermissions {
+                channel_id: self.channel_id,
+                missing,
+            })
+        }
+    }
+}
+
+/// Error returned when the bot is missing permissions required to handle an
+/// interaction.
+///
+/// Displays as the names of the missing permissions, such as "bot is missing
+/// permissions: Embed Links, Attach Files". Converts into
+/// [`RequireError::MissingPermissions`] to be handled alongside the errors of
+/// [`Calculator::require_in_all`].
+///
+/// [`Calculator::require_in_all`]: ../struct.Calculator.html#method.require_in_all
+/// [`RequireError::MissingPermissions`]: ../require/enum.RequireError.html#variant.MissingPermissions
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MissingAppPermissions {
+    /// ID of the channel the interaction was invoked in.
+    pub channel_id: ChannelId,
+    /// Required permissions that the bot doesn't have in the channel.
+    pub missing: Permissions,
+}
+
+impl Display for MissingAppPermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("bot is missing permissions: ")?;
+
+        names::write_list(f, self.missing)
+    }
+}
+
+impl Error for MissingAppPermissions {}
+
+impl From<MissingAppPermissions> for RequireError {
+    fn from(missing: MissingAppPermissions) -> Self {
+        Self::MissingPermissions {
+            channels: vec![MissingInChannel {
+                channel_id: missing.channel_id,
+                missing: missing.missing,
+            }],
+        }
+    }
+}
+
+/// Difference between the permissions sent with an interaction and the
+/// permissions calculated locally.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Discrepancy {
+    /// Permissions calculated locally.
+    pub calculated: Permissions,
+    /// Permissions sent with the interaction.
+    pub interaction: Permissions,
+}
+
+impl Discrepancy {
+    /// Permissions that the member has according to the interaction but not
+    /// according to the local calculation.
+    pub fn missing_locally(&self) -> Permissions {
+        self.interaction - self.calculated
+    }
+
+    /// Permissions that the member has according to the local calculation but
+    /// not according to the interaction.
+    pub fn extra_locally(&self) -> Permissions {
+        self.calculated - self.interaction
+    }
+}
+
+/// Permissions of a member resolved via [`InteractionPermissions::resolve`].
+///
+/// [`InteractionPermissions::resolve`]: struct.InteractionPermissions.html#method.resolve
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Resolution {
+    /// Difference between the interaction's permissions and the local
+    /// calculation, if they were cross-checked and differ.
+    pub discrepancy: Option<Discrepancy>,
+    /// Whether the permissions are the interaction's rather than calculated
+    /// locally.
+    pub from_interaction: bool,
+    /// Resolved permissions of the member in the channel.
+    ///
+    /// These are calculated like Discord does for interactions, so they
+    /// include guild-level permissions and permissions for other types of
+    /// channels. If they were calculated locally, then they're what the
+    /// calculation returned, which
+    /// [`InteractionPermissions::resolve_in_channel`] calculates the same way.
+    ///
+    /// [`InteractionPermissions::resolve_in_channel`]: struct.InteractionPermissions.html#method.resolve_in_channel
+    pub permissions: Permissions,
+}
+
+/// Permissions sent with an interaction, used in place of a local
+/// calculation.
+///
+/// # Examples
+///
+/// Use the interaction's permissions, falling back to calculating them when
+/// the interaction doesn't include them:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::ChannelType,
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::{interaction::InteractionPermissions, Calculator};
+///
+/// // The bot hasn't cached the member's roles yet.
+/// let member_roles = &[];
+/// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+///
+/// // Permissions sent in the interaction's member.
+/// let sent = Some(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL);
+///
+/// let resolution = InteractionPermissions::new(sent)
+///     .resolve_in_channel(calculator, ChannelType::GuildText, &[])?;
+///
+/// assert!(resolution.from_interaction);
+/// assert!(resolution.permissions.contains(Permissions::SEND_MESSAGES));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[must_use = "interaction permissions aren't useful if you don't resolve them"]
+pub struct InteractionPermissions {
+    cross_check: bool,
+    permissions: Option<Permissions>,
+}
+
+impl InteractionPermissions {
+    /// Create from the permissions of the member sent with an interaction.
+    ///
+    /// Interactions that weren't invoked in a guild don't include
+    /// permissions, in which case `None` is given.
+    pub fn new(permissions: Option<Permissions>) -> Self {
+        Self {
+            cross_check: false,
+            permissions,
+        }
+    }
+
+    /// Configure whether the interaction's permissions are cross-checked
+    /// against the local calculation.
+    ///
+    /// The interaction's permissions are still used, and a difference is
+    /// reported via [`Resolution::discrepancy`]. This is useful for finding
+    /// out when the bot's cache is stale. If the local calculation fails, then
+    /// there's nothing to cross-check against. Defaults to disabled.
+    ///
+    /// [`Resolution::discrepancy`]: struct.Resolution.html#structfield.discrepancy
+    pub fn cross_check(mut self, cross_check: bool) -> Self {
+        self.cross_check = cross_check;
+
+        self
+    }
+
+    /// Permissions sent with the interaction, if any.
+    pub fn permissions(&self) -> Option<Permissions> {
+        self.permissions
+    }
+
+    /// Resolve the permissions of the member in a channel, calculating them
+    /// with a calculator if needed.
+    ///
+    /// The permissions are calculated like Discord does, as the [raw]
+    /// permissions of [`Calculator::in_channel_masked`], so that they can be
+    /// compared with the interaction's. Refer to [`resolve`] for more
+    /// information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`Calculator::in_channel_masked`] if the
+    /// interaction didn't include permissions.
+    ///
+    /// [`Calculator::in_channel_masked`]: ../struct.Calculator.html#method.in_channel_masked
+    /// [`resolve`]: #method.resolve
+    /// [raw]: ../mask/struct.Masked.html#structfield.raw
+    pub fn resolve_in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        calculator: Calculator<'_>,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Resolution, CalculatorError> {
+        self.resolve(|| {
+            calculator
+                .in_channel_masked(channel_type, channel_overwrites)
+                .map(|masked| masked.raw)
+        })
+    }
+
+    /// Resolve the permissions of the member.
+    ///
+    /// The interaction's permissions are used if it included them, in which
+    /// case `calculate` is only called if cross-checking is enabled.
+    /// Otherwise the permissions are calculated with `calculate`.
+    ///
+    /// `calculate` must calculate permissions like Discord does for them to
+    /// be comparable with the interaction's, which [`resolve_in_channel`]
+    /// does with a [`Calculator`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error returned by `calculate` if the interaction didn't
+    /// include permissions.
+    ///
+    /// [`Calculator`]: ../struct.Calculator.html
+    /// [`resolve_in_channel`]: #method.resolve_in_channel
+    pub fn resolve<F: FnOnce() -> Result<Permissions, CalculatorError>>(
+        &self,
+        calculate: F,
+    ) -> Result<Resolution, CalculatorError> {
+        let interaction = match self.permissions {
+            Some(interaction) => interaction,
+            None => {
+                return Ok(Resolution {
+                    discrepancy: None,
+                    from_interaction: false,
+                    permissions: calculate()?,
+                })
+            }
+        };
+
+        let discrepancy = if self.cross_check {
+            calculate()
+                .ok()
+                .filter(|calculated| *calculated != interaction)
+                .map(|calculated| Discrepancy {
+                    calculated,
+                    interaction,
+                })
+        } else {
+            None
+        };
+
+        #[cfg(feature = "tracing")]
+        if let Some(discrepancy) = discrepancy {
+            tracing::debug!(
+                calculated = discrepancy.calculated.bits(),
+                interaction = discrepancy.interaction.bits(),
+                "Calculated permissions differ from the interaction's",
+            );
+        }
+
+        Ok(Resolution {
+            discrepancy,
+            from_interaction: true,
+            permissions: interaction,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AppPermissions, Discrepancy, InteractionPermissions, MissingAppPermissions, Resolution,
+    };
+    use crate::{
+        require::{MissingInChannel, RequireError},
+        Calculator, CalculatorError,
+    };
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Discrepancy: calculated, interaction);
+    assert_fields!(MissingAppPermissions: channel_id, missing);
+    assert_fields!(Resolution: discrepancy, from_interaction, permissions);
+    assert_impl_all!(AppPermissions: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Discrepancy: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        InteractionPermissions: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        MissingAppPermissions: Clone,
+        Copy,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(Resolution: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_app_permissions_require() {
+        let app_permissions = AppPermissions::new(
+            ChannelId(1),
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+        );
+        assert!(app_permissions.require(Permissions::SEND_MESSAGES).is_ok());
+        assert!(app_permissions.require(Permissions::empty()).is_ok());
+
+        let error = app_permissions
+            .require(
+                Permissions::ATTACH_FILES | Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            )
+            .unwrap_err();
+        assert_eq!(
+            MissingAppPermissions {
+                channel_id: ChannelId(1),
+                missing: Permissions::ATTACH_FILES | Permissions::EMBED_LINKS,
+            },
+            error,
+        );
+        assert_eq!(
+            "bot is missing permissions: Embed Links, Attach Files",
+            error.to_string(),
+        );
+        assert_eq!(
+            RequireError::MissingPermissions {
+                channels: vec![MissingInChannel {
+                    channel_id: ChannelId(1),
+                    missing: Permissions::ATTACH_FILES | Permissions::EMBED_LINKS,
+                }],
+            },
+            RequireError::from(error),
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let sent = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+        let missing = || {
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1),
+            })
+        };
+
+        // The calculation isn't needed without cross-checking.
+        let resolution = InteractionPermissions::new(Some(sent))
+            .resolve(|| panic!("calculated"))
+            .unwrap();
+        assert_eq!(
+            Resolution {
+                discrepancy: None,
+                from_interaction: true,
+                permissions: sent,
+            },
+            resolution,
+        );
+
+        let resolution = InteractionPermissions::new(Some(sent))
+            .cross_check(true)
+            .resolve(|| Ok(Permissions::VIEW_CHANNEL | Permissions::CONNECT))
+            .unwrap();
+        let discrepancy = resolution.discrepancy.unwrap();
+        assert_eq!(sent, resolution.permissions);
+        assert_eq!(Permissions::SEND_MESSAGES, discrepancy.missing_locally());
+        assert_eq!(Permissions::CONNECT, discrepancy.extra_locally());
+
+        let resolution = InteractionPermissions::new(Some(sent))
+            .cross_check(true)
+            .resolve(missing)
+            .unwrap();
+        assert!(resolution.discrepancy.is_none());
+
+        let resolution = InteractionPermissions::new(None)
+            .resolve(|| Ok(Permissions::VIEW_CHANNEL))
+            .unwrap();
+        assert!(!resolution.from_interaction);
+        assert_eq!(Permissions::VIEW_CHANNEL, resolution.permissions);
+        assert!(InteractionPermissions::new(None).resolve(missing).is_err());
+    }
+
+    /// Test that cross-checking against the permissions Discord sends for a
+    /// member with the default `@everyone` permissions finds no discrepancy.
+    #[test]
+    fn test_resolve_in_channel() {
+        let everyone = Permissions::ADD_REACTIONS
+            | Permissions::ATTACH_FILES
+            | Permissions::CHANGE_NICKNAME
+            | Permissions::CONNECT
+            | Permissions::CREATE_INVITE
+            | Permissions::EMBED_LINKS
+            | Permissions::MENTION_EVERYONE
+            | Permissions::READ_MESSAGE_HISTORY
+            | Permissions::SEND_MESSAGES
+            | Permissions::SPEAK
+            | Permissions::STREAM
+            | Permissions::USE_EXTERNAL_EMOJIS
+            | Permissions::USE_SLASH_COMMANDS
+            | Permissions::USE_VAD
+            | Permissions::VIEW_CHANNEL;
+        let member_roles = &[(RoleId(1), everyone)];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+
+        // Discord doesn't remove Change Nickname or Connect from the
+        // permissions, nor the permissions that depend on Send Messages.
+        let sent = everyone - Permissions::SEND_MESSAGES;
+        let resolution = InteractionPermissions::new(Some(sent))
+            .cross_check(true)
+            .resolve_in_channel(calculator.clone(), ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert!(resolution.discrepancy.is_none());
+
+        let resolution = InteractionPermissions::new(None)
+            .resolve_in_channel(calculator, ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert_eq!(sent, resolution.permissions);
+    }
+}
@@ -0,0 +1,150 @@
+//! Checking whether a member can create an invite to a channel.
+//!
+//! Invites can be created to any guild channel other than a category, and
+//! require the Create Invite permission in the channel along with View
+//! Channel. [`check`] returns why an invite can't be created so that it can be
+//! reported before the request is made.
+//!
+//! [`check`]: fn.check.html
+
+use super::{action::Action, CalculatorError};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{channel::ChannelType, guild::Permissions};
+
+/// Reason that a member can't create an invite to a channel.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum InviteError {
+    /// Permissions couldn't be calculated.
+    Calculating {
+        /// Reason the permissions couldn't be calculated.
+        source: CalculatorError,
+    },
+    /// Invites can't be created to channels of the type.
+    ChannelType {
+        /// Type of the channel.
+        kind: ChannelType,
+    },
+    /// Member is missing permissions required to create an invite.
+    MissingPermissions {
+        /// Missing permissions, out of Create Invite and View Channel.
+        missing: Permissions,
+    },
+}
+
+impl Display for InviteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Calculating { .. } => f.write_str("permissions couldn't be calculated"),
+            Self::ChannelType { kind } => f.write_fmt(format_args!(
+                "invites can't be created to channels of type {:?}",
+                kind
+            )),
+            Self::MissingPermissions { .. } => {
+                f.write_str("member is missing permissions required to create an invite")
+            }
+        }
+    }
+}
+
+impl Error for InviteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Calculating { source } => Some(source),
+            Self::ChannelType { .. } | Self::MissingPermissions { .. } => None,
+        }
+    }
+}
+
+/// Check whether a member with permissions in a channel can create an invite
+/// to it.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{channel::ChannelType, guild::Permissions};
+/// use twilight_permission_calculator::invite::{self, InviteError};
+///
+/// let permissions = Permissions::CREATE_INVITE | Permissions::VIEW_CHANNEL;
+///
+/// assert!(invite::check(permissions, ChannelType::GuildVoice).is_ok());
+/// assert_eq!(
+///     Err(InviteError::ChannelType {
+///         kind: ChannelType::GuildCategory,
+///     }),
+///     invite::check(permissions, ChannelType::GuildCategory),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [`InviteError::ChannelType`] if invites can't be created to
+/// channels of the type, such as categories.
+///
+/// Returns [`InviteError::MissingPermissions`] if the member is missing the
+/// Create Invite or View Channel permission.
+///
+/// [`InviteError::ChannelType`]: enum.InviteError.html#variant.ChannelType
+/// [`InviteError::MissingPermissions`]: enum.InviteError.html#variant.MissingPermissions
+pub fn check(permissions: Permissions, channel_type: ChannelType) -> Result<(), InviteError> {
+    let required = Action::CreateInvite
+        .required(channel_type)
+        .ok_or(InviteError::ChannelType { kind: channel_type })?;
+    let missing = required - permissions;
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(InviteError::MissingPermissions { missing })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InviteError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{channel::ChannelType, guild::Permissions};
+
+    assert_fields!(InviteError::Calculating: source);
+    assert_fields!(InviteError::ChannelType: kind);
+    assert_fields!(InviteError::MissingPermissions: missing);
+    assert_impl_all!(
+        InviteError: Clone,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_check() {
+        let permissions = Permissions::CREATE_INVITE | Permissions::VIEW_CHANNEL;
+
+        assert!(super::check(permissions, ChannelType::GuildText).is_ok());
+        assert!(super::check(permissions, ChannelType::GuildStageVoice).is_ok());
+        assert_eq!(
+            Err(InviteError::MissingPermissions {
+                missing: Permissions::CREATE_INVITE,
+            }),
+            super::check(Permissions::VIEW_CHANNEL, ChannelType::GuildNews),
+        );
+        assert_eq!(
+            Err(InviteError::ChannelType {
+                kind: ChannelType::GuildCategory,
+            }),
+            super::check(Permissions::all(), ChannelType::GuildCategory),
+        );
+    }
+}
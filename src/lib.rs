@@ -26,6 +26,45 @@
 //! twilight-permission-calculator = { branch = "trunk", default-features = false, git = "https://github.com/twilight-rs/permission-calculator" }
 //! ```
 //!
+//! The core calculator is always available. Larger subsystems are behind
+//! their own features so that they're only compiled when they're used:
+//!
+//! - `async`: calculating permissions with data fetched asynchronously from a
+//!   backend such as Redis or an HTTP API, via the `async_source` module
+//! - `audit`: scanning a guild for risky permission configurations via the
+//!   `audit` module
+//! - `batch`: calculating the permissions of members received in gateway
+//!   member chunks via the `member_chunk` module, of every member in every
+//!   channel via the `matrix` module, and storing the results compactly via the
+//!   `compact` module; combine with `rayon` to calculate in parallel
+//! - `cache`: keeping the permission state of guilds up to date from gateway
+//!   events and querying members' permissions via the `cache` module, indexing
+//!   a guild's roles and overwrites via the `index` module, and caching
+//!   calculated permissions via the `lru` module
+//! - `explain`: finding where a member's permissions come from and why they're
+//!   missing any via `Calculator::explain`, `Calculator::trace`,
+//!   `Calculator::why_denied`, and `Calculator::report`
+//! - `ext`: extension traits on `twilight-model` types, such as calculating a
+//!   member's permissions in a channel via `MemberExt::permissions_in`
+//! - `export`: exporting audits as CSV or TSV tables, via the `export` module
+//! - `hierarchy`: checking what members can do to other members and roles
+//!   based on the positions of their roles, via the `hierarchy` module
+//! - `rayon`: calculating many members or channels in parallel on rayon's
+//!   global thread pool, via `Calculator::in_channels`,
+//!   `ChannelCalculator::members`, and the `matrix` module
+//! - `serde`: serializing and deserializing explanations, traces, denials, and
+//!   reports, such as to send permission breakdowns to a web dashboard
+//! - `simulation`: previewing changes before they're made, such as syncing a
+//!   channel with its category via the `category_sync` module or deleting a
+//!   channel via the `soft_delete` module
+//!
+//! The `full` feature enables all of the above along with `tracing`:
+//!
+//! ```toml
+//! [dependencies]
+//! twilight-permission-calculator = { branch = "trunk", features = ["full"], git = "https://github.com/twilight-rs/permission-calculator" }
+//! ```
+//!
 //! # Examples
 //!
 //! ## Calculating member permissions in a channel
@@ -130,20 +169,121 @@
     warnings
 )]
 
+pub mod action;
+#[cfg(feature = "async")]
+pub mod async_source;
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod blueprint;
+pub mod boost;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod calculated;
+pub mod capability;
+#[cfg(feature = "simulation")]
+pub mod category_sync;
+pub mod channel;
+pub mod command;
+#[cfg(feature = "batch")]
+pub mod compact;
+pub mod compat;
+pub mod context;
+#[cfg(feature = "explain")]
+pub mod denial;
+pub mod dependency;
+pub mod diff;
+#[cfg(feature = "explain")]
+pub mod explain;
+#[cfg(feature = "export")]
+pub mod export;
+#[cfg(feature = "ext")]
+pub mod ext;
+#[cfg(feature = "hierarchy")]
+pub mod hierarchy;
+#[cfg(feature = "cache")]
+pub mod index;
+pub mod interaction;
+pub mod invite;
+#[cfg(feature = "cache")]
+pub mod lru;
+pub mod mask;
+#[cfg(feature = "batch")]
+pub mod matrix;
+#[cfg(feature = "batch")]
+pub mod member_chunk;
+pub mod mention;
+pub mod mention_everyone;
+pub mod model;
+pub mod muted;
+pub mod names;
+pub mod overwrite;
+pub mod owned;
 pub mod prelude;
+pub mod reaction;
+#[cfg(feature = "explain")]
+pub mod report;
+pub mod require;
+pub mod requirement;
+pub mod risk;
+pub mod role;
+pub mod send;
+pub mod snapshot;
+#[cfg(feature = "simulation")]
+pub mod soft_delete;
+pub mod source;
+pub mod stage;
+pub mod summary;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "explain")]
+pub mod trace;
+#[cfg(not(feature = "explain"))]
+mod trace;
+pub mod version;
+pub mod voice;
+pub mod webhook;
 
+use action::Action;
+use calculated::{CalculatedPermissions, ChannelPermissions};
+use capability::{Capability, Gates};
+use context::Context;
+#[cfg(feature = "explain")]
+use denial::{Culprit, Denial};
+use dependency::Dependencies;
+#[cfg(feature = "explain")]
+use explain::{Explanation, Source};
+use invite::InviteError;
+use mask::Masked;
+use model::GuildMember;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use reaction::EmojiSource;
+#[cfg(feature = "explain")]
+use report::Report;
+use require::{Deficiency, MissingInChannel, RequireError, SelfAuditReport};
+use role::RoleCalculator;
+use send::SendCapability;
 use std::{
+    borrow::Cow,
+    collections::HashMap,
     error::Error,
     fmt::{Display, Formatter, Result as FmtResult},
+    time::{SystemTime, UNIX_EPOCH},
 };
+use summary::PermissionSummary;
+use trace::Step;
+#[cfg(feature = "explain")]
+use trace::{Trace, TraceStep};
 use twilight_model::{
     channel::{
         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
-        ChannelType,
+        ChannelType, GuildChannel,
     },
-    guild::Permissions,
-    id::{GuildId, RoleId, UserId},
+    guild::{Permissions, Role},
+    id::{ChannelId, GuildId, RoleId, UserId},
 };
+use version::{DataVersion, Versioned};
+use voice::StreamError;
 
 /// Permissions associated with sending messages in a guild text channel.
 const PERMISSIONS_MESSAGING: Permissions = Permissions::from_bits_truncate(
@@ -167,6 +307,24 @@ const PERMISSIONS_ROOT: Permissions = Permissions::from_bits_truncate(
         | Permissions::VIEW_GUILD_INSIGHTS.bits(),
 );
 
+/// Permissions associated with stage channels.
+///
+/// Members in the audience of a stage can't speak until they're invited to,
+/// so out of the voice channel permissions only those used to join and
+/// moderate a stage apply to it. Request To Speak only applies to stage
+/// channels.
+const PERMISSIONS_STAGE: Permissions = Permissions::from_bits_truncate(
+    Permissions::CONNECT.bits()
+        | Permissions::MOVE_MEMBERS.bits()
+        | Permissions::MUTE_MEMBERS.bits()
+        | Permissions::REQUEST_TO_SPEAK.bits(),
+);
+
+/// Permissions that members keep while they're timed out.
+const PERMISSIONS_TIMED_OUT: Permissions = Permissions::from_bits_truncate(
+    Permissions::READ_MESSAGE_HISTORY.bits() | Permissions::VIEW_CHANNEL.bits(),
+);
+
 /// Permissions associated with only guild text channels.
 const PERMISSIONS_TEXT: Permissions = Permissions::from_bits_truncate(
     Permissions::ADD_REACTIONS.bits()
@@ -177,7 +335,8 @@ const PERMISSIONS_TEXT: Permissions = Permissions::from_bits_truncate(
         | Permissions::READ_MESSAGE_HISTORY.bits()
         | Permissions::SEND_MESSAGES.bits()
         | Permissions::SEND_TTS_MESSAGES.bits()
-        | Permissions::USE_EXTERNAL_EMOJIS.bits(),
+        | Permissions::USE_EXTERNAL_EMOJIS.bits()
+        | Permissions::USE_SLASH_COMMANDS.bits(),
 );
 
 /// Permissions associated with only voice channels.
@@ -192,13 +351,23 @@ const PERMISSIONS_VOICE: Permissions = Permissions::from_bits_truncate(
         | Permissions::USE_VAD.bits(),
 );
 
+/// Unix timestamp of the first second of 2015 in milliseconds, which is the
+/// epoch of timestamps in Discord snowflakes.
+const DISCORD_EPOCH: u64 = 1_420_070_400_000;
+
+/// How far in milliseconds the timestamp of a snowflake may be ahead of the
+/// system clock, which may be slightly behind Discord's.
+const SNOWFLAKE_CLOCK_SKEW: u64 = 5 * 60 * 1000;
+
 /// Error type for all calculator errors.
 ///
-/// This will only return if [`Calculator::continue_on_missing_items`] wasn't
-/// enabled.
+/// Missing items will only return an error if
+/// [`Calculator::continue_on_missing_items`] wasn't enabled, and invalid IDs
+/// will only return an error if [`Calculator::validate_ids`] was enabled.
 ///
 /// [`Calculator::continue_on_missing_items`]: struct.Calculator.html#method.continue_on_missing_items
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// [`Calculator::validate_ids`]: struct.Calculator.html#method.validate_ids
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[non_exhaustive]
 pub enum CalculatorError {
     /// `@everyone` role is missing from the guild's role list.
@@ -206,6 +375,22 @@ pub enum CalculatorError {
         /// ID of the guild and role.
         guild_id: GuildId,
     },
+    /// ID isn't a plausible snowflake.
+    ///
+    /// This is the case when the ID is zero, its timestamp is the Discord
+    /// epoch - as is the case with placeholders such as `UserId(1)` - or its
+    /// timestamp is more than a few minutes in the future.
+    InvalidId {
+        /// Raw value of the ID.
+        id: u64,
+        /// Type of the ID.
+        kind: IdKind,
+    },
+    /// Role is missing from the role list.
+    RoleMissing {
+        /// ID of the role.
+        role_id: RoleId,
+    },
 }
 
 impl Display for CalculatorError {
@@ -215,46 +400,312 @@ impl Display for CalculatorError {
                 "@everyone role is missing for guild {}",
                 guild_id
             )),
+            Self::InvalidId { id, kind } => {
+                f.write_fmt(format_args!("{} id {} is not a valid snowflake", kind, id))
+            }
+            Self::RoleMissing { role_id } => {
+                f.write_fmt(format_args!("role {} is missing", role_id))
+            }
         }
     }
 }
 
 impl Error for CalculatorError {}
 
+/// Whether denying a permission in a channel implicitly denies the
+/// permissions that depend on it.
+///
+/// Discord's clients treat a member who can't view a channel as having no
+/// permissions in it, and a member who can't send messages in a channel as
+/// unable to attach files, embed links, mention everyone, or send TTS
+/// messages in it. The permissions that Discord returns, such as for members
+/// in interactions, don't take this into account.
+///
+/// Each mode is a preset of [`Dependencies`], returned by [`dependencies`].
+/// Configuring a calculator's cascade mode configures its dependencies.
+///
+/// Defaults to [`Practical`].
+///
+/// [`Dependencies`]: dependency/struct.Dependencies.html
+/// [`Practical`]: #variant.Practical
+/// [`dependencies`]: #method.dependencies
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum CascadeMode {
+    /// Calculate permissions like Discord does, without implicitly denying
+    /// any permissions.
+    ///
+    /// Uses [`Dependencies::NONE`].
+    ///
+    /// [`Dependencies::NONE`]: dependency/struct.Dependencies.html#associatedconstant.NONE
+    DiscordParity,
+    /// Implicitly deny permissions when the permissions that they depend on
+    /// are denied, like Discord's clients do.
+    ///
+    /// Uses [`Dependencies::PRACTICAL`].
+    ///
+    /// [`Dependencies::PRACTICAL`]: dependency/struct.Dependencies.html#associatedconstant.PRACTICAL
+    Practical,
+}
+
+impl CascadeMode {
+    /// Dependencies that the mode applies.
+    pub fn dependencies(self) -> Dependencies {
+        match self {
+            Self::DiscordParity => Dependencies::NONE,
+            Self::Practical => Dependencies::PRACTICAL,
+        }
+    }
+}
+
+impl Default for CascadeMode {
+    fn default() -> Self {
+        Self::Practical
+    }
+}
+
+/// Type of an ID given to a calculator.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum IdKind {
+    /// ID of the guild.
+    Guild,
+    /// ID of the guild owner.
+    Owner,
+    /// ID of one of the member's roles.
+    Role,
+    /// ID of the member.
+    User,
+}
+
+impl Display for IdKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::Guild => "guild",
+            Self::Owner => "owner",
+            Self::Role => "role",
+            Self::User => "user",
+        })
+    }
+}
+
+/// Whether an ID is plausibly a snowflake created by Discord.
+///
+/// The ID's timestamp must be after the Discord epoch and not be in the
+/// future, allowing for the system clock being a few minutes behind.
+fn is_plausible_snowflake(id: u64) -> bool {
+    let timestamp = id >> 22;
+
+    if timestamp == 0 {
+        return false;
+    }
+
+    // If the system clock is broken then the timestamp can't be checked, so
+    // let it through.
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(u64::MAX, |duration| duration.as_millis() as u64);
+
+    timestamp.saturating_add(DISCORD_EPOCH) <= now.saturating_add(SNOWFLAKE_CLOCK_SKEW)
+}
+
 /// Calculate the permissions of a member.
 ///
 /// Using the member calculator, you can calculate the member's permissions in
 /// the [root-level][`root`] of the guild or [in a given channel][`in_channel`].
 ///
+/// Equality and hashing take into account every input of the calculator,
+/// including the owner ID and policies such as [`validate_ids`], so two
+/// calculators are only equal if they calculate the same permissions at the
+/// same time. This allows a calculator - along with a channel's type and
+/// overwrites - to be used as the key of a cache of calculated permissions.
+/// The one input that isn't part of the calculator is the system clock: a
+/// calculator with a [timeout] calculates different permissions once the
+/// timeout expires, so cached permissions of timed out members must not
+/// outlive their timeout.
+///
+/// Calculated permissions don't depend on the order of the member's roles or
+/// of a channel's overwrites, so roles and overwrites may be passed in the
+/// order they're received from Discord. This is checked by
+/// [`verify_order_independence`].
+///
 /// [`Calculator::member`]: struct.Calculator.html#method.member
 /// [`in_channel`]: #method.in_channel
 /// [`root`]: #method.root
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// [`validate_ids`]: #method.validate_ids
+/// [`verify_order_independence`]: #method.verify_order_independence
+/// [timeout]: #method.communication_disabled_until
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[must_use = "the member calculator isn't useful if you don't calculate permissions"]
 pub struct Calculator<'a> {
+    administrator_bypass: bool,
+    communication_disabled_until: Option<SystemTime>,
     continue_on_missing_items: bool,
+    data_version: DataVersion,
+    dependencies: Dependencies,
     guild_id: GuildId,
-    member_roles: &'a [(RoleId, Permissions)],
+    member_roles: Cow<'a, [(RoleId, Permissions)]>,
     owner_id: Option<UserId>,
+    retain_guild_permissions: bool,
     user_id: UserId,
+    validate_ids: bool,
 }
 
 impl<'a> Calculator<'a> {
     /// Create a calculator to calculate the permissions of a member.
+    ///
+    /// Use [`from_owned`] to hand over the member's roles instead of
+    /// borrowing them.
+    ///
+    /// [`from_owned`]: #method.from_owned
     pub fn new(
         guild_id: GuildId,
         user_id: UserId,
         member_roles: &'a [(RoleId, Permissions)],
+    ) -> Self {
+        Self::with_member_roles(guild_id, user_id, Cow::Borrowed(member_roles))
+    }
+
+    /// Create a calculator to calculate the permissions of a member that owns
+    /// the member's roles.
+    ///
+    /// This is useful for a [`Vec`] of freshly deserialized roles, since the
+    /// calculator isn't tied to their lifetime. Cloning the calculator clones
+    /// the roles; use an [`OwnedCalculator`] to share them instead.
+    ///
+    /// [`OwnedCalculator`]: owned/struct.OwnedCalculator.html
+    /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    pub fn from_owned(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: Vec<(RoleId, Permissions)>,
+    ) -> Self {
+        Self::with_member_roles(guild_id, user_id, Cow::Owned(member_roles))
+    }
+
+    /// Create a calculator with the defaults and the member's roles, borrowed
+    /// or owned.
+    fn with_member_roles(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: Cow<'a, [(RoleId, Permissions)]>,
     ) -> Self {
         Self {
+            administrator_bypass: true,
+            communication_disabled_until: None,
             continue_on_missing_items: false,
+            data_version: DataVersion::default(),
+            dependencies: Dependencies::default(),
             guild_id,
             owner_id: None,
             member_roles,
+            retain_guild_permissions: false,
             user_id,
+            validate_ids: false,
         }
     }
 
+    /// Create a calculator configured with the information in a context.
+    pub fn from_context(context: &Context<'a>) -> Self {
+        context.calculator()
+    }
+
+    /// Create a calculator for a member picked out of a guild.
+    ///
+    /// The calculator is configured with the member's roles and the owner of
+    /// the guild. Refer to [`GuildMember`] for more information.
+    ///
+    /// [`GuildMember`]: model/struct.GuildMember.html
+    pub fn from_guild(guild_member: &'a GuildMember) -> Self {
+        guild_member.calculator()
+    }
+
+    /// Configure whether administrators have every permission in every
+    /// channel.
+    ///
+    /// Discord ignores permission overwrites and the type of the channel for
+    /// members with the Administrator permission, which is the default. When
+    /// disabled, [`in_channel`] applies overwrites and removes permissions
+    /// that don't apply to the channel for administrators like it does for
+    /// other members, which is useful for inspecting how overwrites affect a
+    /// role regardless of whether it's an administrator. The owner of the guild
+    /// always has every permission.
+    ///
+    /// [`in_channel`]: #method.in_channel
+    pub fn administrator_bypass(mut self, administrator_bypass: bool) -> Self {
+        self.administrator_bypass = administrator_bypass;
+
+        self
+    }
+
+    /// Configure whether denying a permission in a channel implicitly denies
+    /// the permissions that depend on it.
+    ///
+    /// Use [`CascadeMode::DiscordParity`] to calculate the same permissions
+    /// as Discord, such as to compare with the permissions of members in
+    /// interactions.
+    ///
+    /// This configures the [dependencies] to the mode's preset, replacing
+    /// those configured before.
+    ///
+    /// Defaults to [`CascadeMode::Practical`].
+    ///
+    /// [`CascadeMode::DiscordParity`]: enum.CascadeMode.html#variant.DiscordParity
+    /// [`CascadeMode::Practical`]: enum.CascadeMode.html#variant.Practical
+    /// [dependencies]: #method.dependencies
+    pub fn cascade_mode(self, cascade_mode: CascadeMode) -> Self {
+        self.dependencies(cascade_mode.dependencies())
+    }
+
+    /// Configure which permissions are implicitly denied in a channel when the
+    /// permissions they depend on are denied.
+    ///
+    /// Use [`Dependencies::NONE`] to calculate the same permissions as
+    /// Discord.
+    ///
+    /// Defaults to [`Dependencies::PRACTICAL`].
+    ///
+    /// [`Dependencies::NONE`]: dependency/struct.Dependencies.html#associatedconstant.NONE
+    /// [`Dependencies::PRACTICAL`]: dependency/struct.Dependencies.html#associatedconstant.PRACTICAL
+    pub fn dependencies(mut self, dependencies: Dependencies) -> Self {
+        self.dependencies = dependencies;
+
+        self
+    }
+
+    /// Configure when the member's timeout expires.
+    ///
+    /// While the member is timed out, they only have the Read Message History
+    /// and View Channel permissions, both at the guild level and in channels,
+    /// unless they're the owner of the guild or an administrator.
+    ///
+    /// Whether the timeout has expired is checked against the system clock
+    /// whenever permissions are calculated, so permissions calculated with the
+    /// same calculator change once the timeout expires.
+    pub fn communication_disabled_until(
+        mut self,
+        communication_disabled_until: SystemTime,
+    ) -> Self {
+        self.communication_disabled_until
+            .replace(communication_disabled_until);
+
+        self
+    }
+
+    /// Configure the version of the data that permissions are calculated
+    /// from.
+    ///
+    /// The version is carried by the results of methods such as
+    /// [`in_channel_versioned`], so that external caches can tell when they're
+    /// stale. Defaults to the initial version.
+    ///
+    /// [`in_channel_versioned`]: #method.in_channel_versioned
+    pub fn data_version(mut self, data_version: DataVersion) -> Self {
+        self.data_version = data_version;
+
+        self
+    }
+
     /// Configure the ID of the owner of the guild.
     ///
     /// This should be used if you don't want to manually take the user ID and
@@ -271,6 +722,42 @@ impl<'a> Calculator<'a> {
         self
     }
 
+    /// Configure whether to keep guild-level permissions, such as View Audit
+    /// Log and Administrator, in the permissions calculated in channels.
+    ///
+    /// [`in_channel`] removes guild-level permissions by default since they
+    /// can't be used in a channel. Discord keeps them, such as in the
+    /// permissions of members in interactions, so retaining them is useful for
+    /// comparing with those. Permissions that don't apply to the type of
+    /// channel are still removed.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [`in_channel`]: #method.in_channel
+    pub fn retain_guild_permissions(mut self, retain_guild_permissions: bool) -> Self {
+        self.retain_guild_permissions = retain_guild_permissions;
+
+        self
+    }
+
+    /// Configure whether to validate that the IDs given to the calculator are
+    /// plausible snowflakes.
+    ///
+    /// This catches placeholder IDs, such as `UserId(0)`, that would otherwise
+    /// silently produce wrong results. The guild ID, user ID, owner ID, and the
+    /// IDs of the member's roles are validated.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// Refer to [`CalculatorError::InvalidId`] for what makes an ID invalid.
+    ///
+    /// [`CalculatorError::InvalidId`]: enum.CalculatorError.html#variant.InvalidId
+    pub fn validate_ids(mut self, validate_ids: bool) -> Self {
+        self.validate_ids = validate_ids;
+
+        self
+    }
+
     /// Calculate the guild-level permissions of a member.
     ///
     /// # Errors
@@ -279,12 +766,33 @@ impl<'a> Calculator<'a> {
     /// returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role with the
     /// same ID as the guild wasn't found in the given guild roles map.
     ///
+    /// If [`validate_ids`] was enabled, then this returns
+    /// [`CalculatorError::InvalidId`] if any of the IDs aren't plausible
+    /// snowflakes.
+    ///
     /// [`Calculator::continue_on_missing_items`]: struct.Calculator.html#method.continue_on_missing_items
     /// [`CalculatorError::EveryoneRoleMissing`]: enum.CalculatorError.html#method.EveryoneRoleMissing
+    /// [`CalculatorError::InvalidId`]: enum.CalculatorError.html#variant.InvalidId
+    /// [`validate_ids`]: #method.validate_ids
     pub fn root(&self) -> Result<Permissions, CalculatorError> {
+        self.root_traced(&mut |_, _, _| {})
+    }
+
+    /// Calculate the guild-level permissions of a member, recording each step
+    /// taken.
+    fn root_traced<F: FnMut(Step, Permissions, Permissions)>(
+        &self,
+        record: &mut F,
+    ) -> Result<Permissions, CalculatorError> {
+        if self.validate_ids {
+            self.check_ids()?;
+        }
+
         // If the user is the owner, then we can just return all of the
         // permissions.
         if matches!(self.owner_id, Some(id) if id == self.user_id) {
+            record(Step::Owner, Permissions::empty(), Permissions::all());
+
             return Ok(Permissions::all());
         }
 
@@ -313,18 +821,143 @@ impl<'a> Calculator<'a> {
             }
         };
 
+        record(Step::Everyone, Permissions::empty(), permissions);
+        let before = permissions;
+
         // Permissions on a user's roles are simply additive.
         for (_, role_permissions) in self.member_roles.iter() {
-            if permissions.contains(Permissions::ADMINISTRATOR) {
-                return Ok(Permissions::all());
-            }
-
             permissions.insert(*role_permissions);
         }
 
+        record(Step::Roles, before, permissions);
+
+        // Check for the administrator privilege only once all roles have been
+        // applied, so that the result doesn't depend on the order of roles.
+        if permissions.contains(Permissions::ADMINISTRATOR) {
+            record(Step::Administrator, permissions, Permissions::all());
+
+            return Ok(Permissions::all());
+        }
+
+        if self.is_timed_out() {
+            record(
+                Step::TimedOut,
+                permissions,
+                permissions & PERMISSIONS_TIMED_OUT,
+            );
+            permissions &= PERMISSIONS_TIMED_OUT;
+        }
+
         Ok(permissions)
     }
 
+    /// Calculate the guild-level permissions of a member, stamped with the
+    /// configured [data version].
+    ///
+    /// Refer to [`root`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`root`].
+    ///
+    /// [data version]: #method.data_version
+    /// [`root`]: #method.root
+    pub fn root_versioned(&self) -> Result<Versioned<Permissions>, CalculatorError> {
+        self.root()
+            .map(|permissions| Versioned::new(permissions, self.data_version))
+    }
+
+    /// Create a calculator for the permissions of a role of the guild, as if
+    /// a member only had the `@everyone` role and that role.
+    ///
+    /// Guild roles are given as tuples of the role's ID and permissions, and
+    /// must include the `@everyone` role, which has the same ID as the guild.
+    /// The role doesn't need to be one of the member's roles.
+    ///
+    /// The role calculator is configured like this calculator, such as with
+    /// its [cascade mode] and [administrator bypass], so that the role's
+    /// permissions are calculated the same way as the member's. The member's
+    /// timeout isn't carried over, since it doesn't affect the role.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let guild_roles = &[
+    ///     (RoleId(1), Permissions::VIEW_CHANNEL),
+    ///     (RoleId(2), Permissions::SEND_MESSAGES),
+    ///     (RoleId(3), Permissions::ATTACH_FILES),
+    /// ];
+    /// let overwrites = &[PermissionOverwrite {
+    ///     allow: Permissions::EMBED_LINKS,
+    ///     deny: Permissions::empty(),
+    ///     kind: PermissionOverwriteType::Role(RoleId(2)),
+    /// }];
+    ///
+    /// // The member doesn't have role 2.
+    /// let member_roles = &[guild_roles[0], guild_roles[2]];
+    /// let calculator = Calculator::new(GuildId(1), UserId(4), member_roles);
+    /// let role = calculator.role(guild_roles, RoleId(2))?;
+    ///
+    /// assert_eq!(
+    ///     Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+    ///     role.in_channel(ChannelType::GuildText, overwrites),
+    /// );
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If [`Calculator::continue_on_missing_items`] wasn't enabled, then this
+    /// returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone`
+    /// role isn't in the guild's roles, and [`CalculatorError::RoleMissing`]
+    /// if the role isn't.
+    ///
+    /// [administrator bypass]: #method.administrator_bypass
+    /// [cascade mode]: #method.cascade_mode
+    /// [`Calculator::continue_on_missing_items`]: struct.Calculator.html#method.continue_on_missing_items
+    /// [`CalculatorError::EveryoneRoleMissing`]: enum.CalculatorError.html#variant.EveryoneRoleMissing
+    /// [`CalculatorError::RoleMissing`]: enum.CalculatorError.html#variant.RoleMissing
+    pub fn role(
+        &self,
+        guild_roles: &[(RoleId, Permissions)],
+        role_id: RoleId,
+    ) -> Result<RoleCalculator, CalculatorError> {
+        let find = |id: RoleId, error: CalculatorError| match guild_roles
+            .iter()
+            .find(|(role_id, _)| *role_id == id)
+        {
+            Some((_, permissions)) => Ok(*permissions),
+            None if self.continue_on_missing_items => Ok(Permissions::empty()),
+            None => Err(error),
+        };
+
+        let everyone = find(
+            RoleId(self.guild_id.0),
+            CalculatorError::EveryoneRoleMissing {
+                guild_id: self.guild_id,
+            },
+        )?;
+        let permissions = find(role_id, CalculatorError::RoleMissing { role_id })?;
+
+        Ok(
+            RoleCalculator::new(self.guild_id, everyone, (role_id, permissions))
+                .administrator_bypass(self.administrator_bypass)
+                .dependencies(self.dependencies.clone())
+                .retain_guild_permissions(self.retain_guild_permissions),
+        )
+    }
+
     /// Calculate the permissions of the member in a channel, taking into
     /// account a combination of the guild-level permissions and channel-level
     /// permissions.
@@ -338,8 +971,8 @@ impl<'a> Calculator<'a> {
     /// "Mention Everyone", and "Send TTS Messages" permissions will not be
     /// present in the returned permission set.
     ///
-    /// When the given channel type is not a guild text channel, then the
-    /// following text permissions will not be present, even if enabled on the
+    /// When the given channel type is not a guild text or news channel, then
+    /// the following text permissions will not be present, even if enabled on the
     /// guild role level:
     ///
     /// - Add Reactions
@@ -351,6 +984,7 @@ impl<'a> Calculator<'a> {
     /// - Send Messages
     /// - Send TTS Messages
     /// - Use External Emojis
+    /// - Use Slash Commands
     ///
     /// When the given channel type is not a guild voice channel, then the
     /// following voice permissions will not be present, even if enabled on the
@@ -364,6 +998,12 @@ impl<'a> Calculator<'a> {
     /// - Stream
     /// - Use VAD
     ///
+    /// Members in the audience of a stage channel can't speak until they're
+    /// invited to, so in stage channels only the Move Members and Mute Members
+    /// voice permissions are present, along with Connect and Request To Speak.
+    /// For members without the Administrator permission, Request To Speak is
+    /// never present in other channels.
+    ///
     /// The following guild level permissions will always be removed:
     ///
     /// - Administrator
@@ -379,6 +1019,10 @@ impl<'a> Calculator<'a> {
     /// If you need to know a member's guild-level permissions (such as whether
     /// they have the "View Audit Log" permission), use [`permissions`].
     ///
+    /// The owner of the guild and administrators have every permission in
+    /// every channel, including guild-level permissions, unless
+    /// [`administrator_bypass`] was disabled.
+    ///
     /// # Examples
     ///
     /// See the crate-level documentation for an example.
@@ -391,216 +1035,3889 @@ impl<'a> Calculator<'a> {
     ///
     /// [`Calculator::continue_on_missing_items`]: struct.Calculator.html#method.continue_on_missing_items
     /// [`Error::EveryoneRoleMissing`]: enum.Error.html#method.EveryoneRoleMissing
+    /// [`administrator_bypass`]: #method.administrator_bypass
     /// [`permissions`]: #method.permissions
     pub fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
         self,
         channel_type: ChannelType,
         channel_overwrites: U,
     ) -> Result<Permissions, CalculatorError> {
-        let mut permissions = self.root()?;
+        let root = self.root()?;
 
-        // If the user contains the administrator privilege from the calculated
-        // root permissions, then we do not need to do any more work.
-        if permissions.contains(Permissions::ADMINISTRATOR) {
-            return Ok(permissions);
-        }
-
-        // Hierarchy documentation:
-        // <https://discord.com/developers/docs/topics/permissions#permission-overwrites>
-        let mut member_allow = Permissions::empty();
-        let mut member_deny = Permissions::empty();
-        let mut roles_allow = Permissions::empty();
-        let mut roles_deny = Permissions::empty();
-
-        for overwrite in channel_overwrites.clone() {
-            match overwrite.kind {
-                PermissionOverwriteType::Role(role) => {
-                    // We need to process the @everyone role first, so apply it
-                    // straight to the permissions. The other roles' permissions
-                    // will be applied later.
-                    if role.0 == self.guild_id.0 {
-                        permissions.remove(overwrite.deny);
-                        permissions.insert(overwrite.allow);
-
-                        continue;
-                    }
-
-                    if !self.member_roles.iter().any(|(id, _)| *id == role) {
-                        continue;
-                    }
+        Ok(self.in_channel_with_root(root, channel_type, channel_overwrites))
+    }
 
-                    roles_allow.insert(overwrite.allow);
-                    roles_deny.insert(overwrite.deny);
-                }
-                PermissionOverwriteType::Member(user_id) if user_id == self.user_id => {
-                    member_allow.insert(overwrite.allow);
-                    member_deny.insert(overwrite.deny);
-                }
-                PermissionOverwriteType::Member(_) => {}
-            }
-        }
+    /// Calculate the permissions of the member in a guild channel, taking the
+    /// type and permission overwrites from the channel.
+    ///
+    /// Refer to [`in_channel`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    pub fn in_guild_channel(self, channel: &GuildChannel) -> Result<Permissions, CalculatorError> {
+        let (_, _, channel_type, channel_overwrites) = model::channel_parts(channel);
 
-        let role_view_channel_denied = roles_deny.contains(Permissions::VIEW_CHANNEL)
-            && !roles_allow.contains(Permissions::VIEW_CHANNEL)
-            && !roles_allow.contains(Permissions::VIEW_CHANNEL);
+        self.in_channel(channel_type, channel_overwrites)
+    }
 
-        let member_view_channel_denied = member_deny.contains(Permissions::VIEW_CHANNEL)
-            && !member_allow.contains(Permissions::VIEW_CHANNEL);
+    /// Calculate the permissions of the member in each of a set of channels.
+    ///
+    /// The member's guild-level permissions are only calculated once and then
+    /// reused for every channel, which is cheaper than calling [`in_channel`]
+    /// for each channel. Permissions in each channel are otherwise calculated
+    /// like [`in_channel`].
+    ///
+    /// If a channel is given more than once, then the last entry for it is
+    /// used.
+    ///
+    /// With the `rayon` feature enabled, channels are calculated in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{ChannelId, GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(
+    ///     RoleId(1),
+    ///     Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+    /// )];
+    /// let announcements = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::SEND_MESSAGES,
+    ///     kind: PermissionOverwriteType::Role(RoleId(1)),
+    /// }];
+    /// let channels = vec![
+    ///     (ChannelId(2), ChannelType::GuildText, &[][..]),
+    ///     (ChannelId(3), ChannelType::GuildText, &announcements[..]),
+    /// ];
+    ///
+    /// let permissions = Calculator::new(GuildId(1), UserId(4), member_roles)
+    ///     .in_channels(channels)?;
+    ///
+    /// assert!(permissions[&ChannelId(2)].contains(Permissions::SEND_MESSAGES));
+    /// assert_eq!(Permissions::VIEW_CHANNEL, permissions[&ChannelId(3)]);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`root`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`root`]: #method.root
+    pub fn in_channels<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+    ) -> Result<HashMap<ChannelId, Permissions>, CalculatorError> {
+        let root = self.root()?;
+        let calculate = |(channel_id, channel_type, channel_overwrites)| {
+            (
+                channel_id,
+                self.in_channel_with_root(root, channel_type, channel_overwrites),
+            )
+        };
 
-        if member_view_channel_denied || role_view_channel_denied {
-            return Ok(Permissions::empty());
-        }
+        #[cfg(feature = "rayon")]
+        let permissions = channels
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(calculate)
+            .collect();
 
-        // If the member or any of their roles denies the Send Messages
-        // permission, then the rest of the messaging-related permissions can be
-        // removed.
-        let role_send_messages_denied = roles_deny.contains(Permissions::SEND_MESSAGES)
-            && !roles_allow.contains(Permissions::SEND_MESSAGES)
-            && !roles_allow.contains(Permissions::SEND_MESSAGES);
+        #[cfg(not(feature = "rayon"))]
+        let permissions = channels.into_iter().map(calculate).collect();
 
-        let member_send_messages_denied = member_deny.contains(Permissions::SEND_MESSAGES)
-            && !member_allow.contains(Permissions::SEND_MESSAGES);
+        Ok(permissions)
+    }
 
-        if member_send_messages_denied || role_send_messages_denied {
-            member_allow.remove(PERMISSIONS_MESSAGING);
-            roles_allow.remove(PERMISSIONS_MESSAGING);
-            permissions.remove(PERMISSIONS_MESSAGING);
-        }
+    /// Find the channels of a set in which the member has permissions.
+    ///
+    /// Channels are given as tuples of the channel's ID, type, and permission
+    /// overwrites, and their IDs are returned in the order they were given.
+    /// Like [`require_in_all`], required guild-level permissions are checked
+    /// against the member's guild-level permissions.
+    ///
+    /// # Examples
+    ///
+    /// Find every channel in which the member can manage messages:
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{ChannelId, GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+    /// let moderated = &[PermissionOverwrite {
+    ///     allow: Permissions::MANAGE_MESSAGES,
+    ///     deny: Permissions::empty(),
+    ///     kind: PermissionOverwriteType::Member(UserId(2)),
+    /// }];
+    /// let channels = vec![
+    ///     (ChannelId(3), ChannelType::GuildText, &[][..]),
+    ///     (ChannelId(4), ChannelType::GuildText, &moderated[..]),
+    /// ];
+    ///
+    /// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+    ///
+    /// assert_eq!(
+    ///     vec![ChannelId(4)],
+    ///     calculator.channels_with(channels, Permissions::MANAGE_MESSAGES)?,
+    /// );
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`root`].
+    ///
+    /// [`require_in_all`]: #method.require_in_all
+    /// [`root`]: #method.root
+    pub fn channels_with<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+        permissions: Permissions,
+    ) -> Result<Vec<ChannelId>, CalculatorError> {
+        let root = self.root()?;
 
-        permissions.remove(roles_deny);
-        permissions.insert(roles_allow);
-        permissions.remove(member_deny);
-        permissions.insert(member_allow);
+        Ok(channels
+            .into_iter()
+            .filter(|(channel_id, channel_type, channel_overwrites)| {
+                let calculated =
+                    self.in_channel_with_root(root, *channel_type, *channel_overwrites);
 
-        // Remove permissions that can't be used in a channel, i.e. are relevant
-        // to guild-level permission calculating.
-        permissions.remove(PERMISSIONS_ROOT);
+                MissingInChannel::new(*channel_id, root, calculated, permissions).is_none()
+            })
+            .map(|(channel_id, _, _)| channel_id)
+            .collect())
+    }
 
-        // Now remove permissions that can't be used in text or voice channels
-        // based on this channel's type. This handles category channels by
-        // removing all text and voice permissions.
-        if channel_type != ChannelType::GuildText {
-            permissions.remove(PERMISSIONS_TEXT);
-        }
+    /// Calculate the permissions of the member in a channel along with where
+    /// each of them comes from.
+    ///
+    /// Permissions are calculated like [`in_channel`]. Refer to the [`explain`]
+    /// module for how they're attributed to roles and overwrites.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{explain::Source, Calculator};
+    ///
+    /// let member_roles = &[
+    ///     (RoleId(1), Permissions::VIEW_CHANNEL),
+    ///     (RoleId(3), Permissions::empty()),
+    /// ];
+    /// let overwrites = &[PermissionOverwrite {
+    ///     allow: Permissions::SEND_MESSAGES,
+    ///     deny: Permissions::empty(),
+    ///     kind: PermissionOverwriteType::Role(RoleId(3)),
+    /// }];
+    ///
+    /// let explanation = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .explain(ChannelType::GuildText, overwrites)?;
+    ///
+    /// assert_eq!(
+    ///     Some(Source::RoleOverwrite { role_id: RoleId(3) }),
+    ///     explanation.source(Permissions::SEND_MESSAGES),
+    /// );
+    /// assert_eq!(Some(Source::Everyone), explanation.source(Permissions::VIEW_CHANNEL));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`explain`]: explain/index.html
+    /// [`in_channel`]: #method.in_channel
+    #[cfg(feature = "explain")]
+    pub fn explain<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Explanation, CalculatorError> {
+        let root = self.root()?;
+        let permissions = self.in_channel_with_root(root, channel_type, channel_overwrites.clone());
 
-        if channel_type != ChannelType::GuildVoice {
-            permissions.remove(PERMISSIONS_VOICE);
-        }
+        let bypass = if matches!(self.owner_id, Some(id) if id == self.user_id) {
+            Some(Source::Owner)
+        } else if self.administrator_bypass && root.contains(Permissions::ADMINISTRATOR) {
+            Some(Source::Administrator)
+        } else {
+            None
+        };
 
-        Ok(permissions)
+        Ok(Explanation::new(
+            self.guild_id,
+            self.user_id,
+            &self.member_roles,
+            channel_overwrites,
+            permissions,
+            bypass,
+        ))
     }
-}
 
-/// Dangerous infallible calculator to calculate the permissions of a member.
-///
-/// **Note that using this is dangerous, as it may allow your application to
-/// think a member has a permission when in reality they don't, or vice versa.**
-///
-/// This is a variant of the [`Calculator`] which will ignore when expected
-/// items are missing, such as the `@everyone` role information missing.
-///
+    /// Calculate the permissions of the member in a channel, recording every
+    /// step taken along with the permissions before and after it.
+    ///
+    /// Permissions are calculated like [`in_channel`]. Refer to the [`trace`]
+    /// module for more information.
+    ///
+    /// # Examples
+    ///
+    /// Find the step that removed the Send Messages permission:
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{trace::Step, Calculator};
+    ///
+    /// let member_roles = &[(
+    ///     RoleId(1),
+    ///     Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+    /// )];
+    /// let overwrites = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::SEND_MESSAGES,
+    ///     kind: PermissionOverwriteType::Member(UserId(2)),
+    /// }];
+    ///
+    /// let trace = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .trace(ChannelType::GuildText, overwrites)?;
+    ///
+    /// let step = trace
+    ///     .steps
+    ///     .iter()
+    ///     .find(|step| step.removed().contains(Permissions::SEND_MESSAGES))
+    ///     .map(|step| step.step);
+    ///
+    /// assert_eq!(Some(Step::MemberOverwrite), step);
+    /// assert_eq!(Permissions::VIEW_CHANNEL, trace.permissions);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`trace`]: trace/index.html
+    #[cfg(feature = "explain")]
+    pub fn trace<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Trace, CalculatorError> {
+        let mut steps = Vec::new();
+        let mut record = |step: Step, before: Permissions, after: Permissions| {
+            steps.push(TraceStep {
+                after,
+                before,
+                step,
+            });
+        };
+
+        let root = self.root_traced(&mut record)?;
+        let permissions =
+            self.in_channel_with_root_traced(root, channel_type, channel_overwrites, &mut record);
+
+        Ok(Trace { permissions, steps })
+    }
+
+    /// Find what's responsible for the member missing a permission in a
+    /// channel.
+    ///
+    /// Returns `None` if the member has the permission. If several
+    /// permissions are given, then only the first missing one in order of bit
+    /// position is considered. Refer to the [`denial`] module for more
+    /// information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{denial::Culprit, Calculator};
+    ///
+    /// let member_roles = &[
+    ///     (
+    ///         RoleId(1),
+    ///         Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+    ///     ),
+    ///     (RoleId(3), Permissions::empty()),
+    /// ];
+    /// let overwrites = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::SEND_MESSAGES,
+    ///     kind: PermissionOverwriteType::Role(RoleId(3)),
+    /// }];
+    ///
+    /// let denial = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .why_denied(ChannelType::GuildText, overwrites, Permissions::SEND_MESSAGES)?;
+    ///
+    /// assert_eq!(
+    ///     Some(Culprit::RoleOverwrite { role_id: RoleId(3) }),
+    ///     denial.map(|denial| denial.culprit),
+    /// );
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`denial`]: denial/index.html
+    /// [`in_channel`]: #method.in_channel
+    #[cfg(feature = "explain")]
+    pub fn why_denied<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        permission: Permissions,
+    ) -> Result<Option<Denial>, CalculatorError> {
+        let trace = self
+            .clone()
+            .trace(channel_type, channel_overwrites.clone())?;
+
+        Ok(denial::find(
+            self.guild_id,
+            self.user_id,
+            &self.member_roles,
+            channel_type,
+            channel_overwrites,
+            &trace,
+            permission,
+        ))
+    }
+
+    /// Create a human-readable report of the guild-level permissions of the
+    /// member.
+    ///
+    /// The report lists every permission the member has and which role it
+    /// comes from, as well as every permission they're missing. Refer to the
+    /// [`report`] module for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`root`].
+    ///
+    /// [`report`]: report/index.html
+    /// [`root`]: #method.root
+    #[cfg(feature = "explain")]
+    pub fn report_root(&self) -> Result<Report, CalculatorError> {
+        let mut timed_out = Permissions::empty();
+        let permissions = self.root_traced(&mut |step, before, after| {
+            if step == Step::TimedOut {
+                timed_out = before - after;
+            }
+        })?;
+
+        let bypass = if matches!(self.owner_id, Some(id) if id == self.user_id) {
+            Some(Source::Owner)
+        } else if permissions.contains(Permissions::ADMINISTRATOR) {
+            Some(Source::Administrator)
+        } else {
+            None
+        };
+
+        let explanation = Explanation::new(
+            self.guild_id,
+            self.user_id,
+            &self.member_roles,
+            &[],
+            permissions,
+            bypass,
+        );
+
+        let denied = names::iter(Permissions::all() - permissions)
+            .map(|(permission, _)| Denial {
+                culprit: if timed_out.contains(permission) {
+                    Culprit::TimedOut
+                } else {
+                    Culprit::NotGranted
+                },
+                denied: permission,
+                permission,
+            })
+            .collect();
+
+        Ok(Report {
+            denied,
+            granted: explanation.sources,
+            permissions,
+        })
+    }
+
+    /// Create a human-readable report of the permissions of the member in a
+    /// channel.
+    ///
+    /// The report lists every permission the member has and where it comes
+    /// from, like [`explain`], as well as every permission that can be used in
+    /// the channel but that they're missing and what's responsible, like
+    /// [`why_denied`]. Refer to the [`report`] module for more information.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(
+    ///     RoleId(1),
+    ///     Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+    /// )];
+    /// let overwrites = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::SEND_MESSAGES,
+    ///     kind: PermissionOverwriteType::Member(UserId(2)),
+    /// }];
+    ///
+    /// let report = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .report(ChannelType::GuildText, overwrites)?;
+    ///
+    /// assert!(report
+    ///     .to_string()
+    ///     .contains("- Send Messages: denied by the overwrite of the member\n"));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`explain`]: #method.explain
+    /// [`in_channel`]: #method.in_channel
+    /// [`report`]: report/index.html
+    /// [`why_denied`]: #method.why_denied
+    #[cfg(feature = "explain")]
+    pub fn report<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Report, CalculatorError> {
+        let explanation = self
+            .clone()
+            .explain(channel_type, channel_overwrites.clone())?;
+        let trace = self
+            .clone()
+            .trace(channel_type, channel_overwrites.clone())?;
+
+        let applicable = channel_permissions(
+            self.guild_id,
+            None,
+            &[],
+            Permissions::all() - Permissions::ADMINISTRATOR,
+            channel_type,
+            &[],
+        );
+
+        let denied = names::iter(applicable - trace.permissions)
+            .filter_map(|(permission, _)| {
+                denial::find(
+                    self.guild_id,
+                    self.user_id,
+                    &self.member_roles,
+                    channel_type,
+                    channel_overwrites.clone(),
+                    &trace,
+                    permission,
+                )
+            })
+            .collect();
+
+        Ok(Report {
+            denied,
+            granted: explanation.sources,
+            permissions: trace.permissions,
+        })
+    }
+
+    /// Preview the permissions of the member in a channel as if it were synced
+    /// with its category's permission overwrites.
+    ///
+    /// Discord never applies a category's overwrites to its channels when
+    /// calculating permissions: they're only copied to a channel when it's
+    /// created in or synced with the category. Syncing replaces all of the
+    /// channel's own overwrites with the category's, so the permissions are
+    /// calculated like [`in_channel`] with only the category's overwrites.
+    ///
+    /// Use [`in_channel`] with the channel's own overwrites for the
+    /// permissions the member actually has. To preview a sync for every role
+    /// of the guild, refer to [`CategorySync`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let category = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::SEND_MESSAGES,
+    ///     kind: PermissionOverwriteType::Role(RoleId(1)),
+    /// }];
+    ///
+    /// let permissions = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .in_channel_with_parent(ChannelType::GuildText, category)?;
+    ///
+    /// assert_eq!(Permissions::VIEW_CHANNEL, permissions);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`CategorySync`]: category_sync/struct.CategorySync.html
+    /// [`in_channel`]: #method.in_channel
+    pub fn in_channel_with_parent<'b, P: IntoIterator<Item = &'b PermissionOverwrite>>(
+        self,
+        channel_type: ChannelType,
+        parent_overwrites: P,
+    ) -> Result<Permissions, CalculatorError> {
+        let overwrites = parent_overwrites.into_iter().collect::<Vec<_>>();
+
+        self.in_channel(channel_type, overwrites)
+    }
+
+    /// Calculate the permissions of the member in a channel, wrapped with
+    /// helpers for common checks.
+    ///
+    /// Refer to [`in_channel`] for more information, and to
+    /// [`CalculatedPermissions`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`CalculatedPermissions`]: calculated/struct.CalculatedPermissions.html
+    /// [`in_channel`]: #method.in_channel
+    pub fn in_channel_calculated<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<CalculatedPermissions, CalculatorError> {
+        let root = self.root()?;
+
+        Ok(CalculatedPermissions::new(self.in_channel_with_root(
+            root,
+            channel_type,
+            channel_overwrites,
+        )))
+    }
+
+    /// Calculate the permissions of the member in a channel, depending on
+    /// whether the channel is visible to them.
+    ///
+    /// The channel is hidden exactly when the permissions calculated by
+    /// [`in_channel`] don't include View Channel. When an overwrite denies
+    /// View Channel, [`in_channel`] also denies every other permission, so
+    /// the two agree on which permissions the member has.
+    ///
+    /// Refer to [`in_channel`] for more information, and to
+    /// [`ChannelPermissions`] for an example.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`ChannelPermissions`]: calculated/enum.ChannelPermissions.html
+    /// [`in_channel`]: #method.in_channel
+    pub fn in_channel_visibility<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<ChannelPermissions, CalculatorError> {
+        self.in_channel_calculated(channel_type, channel_overwrites)
+            .map(CalculatedPermissions::visibility)
+    }
+
+    /// Calculate the permissions of the member in a channel along with the
+    /// permissions that were removed because they don't apply to the channel.
+    ///
+    /// [`in_channel`] removes guild-level permissions and permissions for
+    /// other types of channels from the result. These are returned as
+    /// [`Masked::stripped`], so that they can be shown as not applicable
+    /// rather than as denied. Members with the Administrator permission keep
+    /// every permission, so nothing is stripped from them.
+    ///
+    /// The permissions from before they were removed are returned as
+    /// [`Masked::raw`], which can be compared with the permissions that
+    /// Discord calculates. Discord doesn't implicitly deny permissions that
+    /// depend on a denied permission, so the raw permissions are calculated
+    /// without the configured [dependencies], regardless of the
+    /// [cascade mode]. They may include permissions that were removed from
+    /// [`Masked::permissions`] because of a dependency.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(
+    ///     RoleId(1),
+    ///     Permissions::CONNECT | Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES,
+    /// )];
+    ///
+    /// let masked = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .in_channel_masked(ChannelType::GuildText, &[])?;
+    ///
+    /// assert_eq!(Permissions::SEND_MESSAGES, masked.permissions);
+    /// assert_eq!(
+    ///     Permissions::CONNECT | Permissions::KICK_MEMBERS,
+    ///     masked.stripped,
+    /// );
+    /// assert_eq!(
+    ///     Permissions::CONNECT | Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES,
+    ///     masked.raw,
+    /// );
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`Masked::permissions`]: mask/struct.Masked.html#structfield.permissions
+    /// [`Masked::raw`]: mask/struct.Masked.html#structfield.raw
+    /// [`Masked::stripped`]: mask/struct.Masked.html#structfield.stripped
+    /// [cascade mode]: #method.cascade_mode
+    /// [dependencies]: #method.dependencies
+    /// [`in_channel`]: #method.in_channel
+    pub fn in_channel_masked<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Masked, CalculatorError> {
+        let root = self.root()?;
+        let overwrites = channel_overwrites.into_iter().collect::<Vec<_>>();
+        let mut stripped = Permissions::empty();
+
+        let permissions = self.in_channel_with_root_traced(
+            root,
+            channel_type,
+            overwrites.iter().copied(),
+            &mut |step, before, after| {
+                if matches!(step, Step::ChannelType { .. } | Step::GuildPermissions) {
+                    stripped.insert(before - after);
+                }
+            },
+        );
+
+        // Discord doesn't deny the permissions that depend on a denied
+        // permission, so the raw permissions are calculated without the
+        // configured dependencies.
+        let mut raw = None;
+        let unmasked = self
+            .dependencies(Dependencies::NONE)
+            .in_channel_with_root_traced(root, channel_type, overwrites, &mut |step, before, _| {
+                match step {
+                    Step::ChannelType { .. } | Step::GuildPermissions => {
+                        raw.get_or_insert(before);
+                    }
+                    // Discord also removes permissions from timed out members.
+                    Step::TimedOut => {
+                        if let Some(raw) = raw.as_mut() {
+                            raw.remove(Permissions::all() - PERMISSIONS_TIMED_OUT);
+                        }
+                    }
+                    _ => {}
+                }
+            });
+
+        // Nothing was masked if the calculation ended early, such as for
+        // administrators.
+        Ok(Masked::new(permissions, raw.unwrap_or(unmasked), stripped))
+    }
+
+    /// Calculate the permissions of the member in a channel, stamped with the
+    /// configured [data version].
+    ///
+    /// Refer to [`in_channel`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [data version]: #method.data_version
+    /// [`in_channel`]: #method.in_channel
+    pub fn in_channel_versioned<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Versioned<Permissions>, CalculatorError> {
+        let data_version = self.data_version;
+
+        self.in_channel(channel_type, channel_overwrites)
+            .map(|permissions| Versioned::new(permissions, data_version))
+    }
+
+    /// Whether the member can react to a message in a channel with an emoji.
+    ///
+    /// `existing` is whether the message already has a reaction with the
+    /// emoji. Refer to the [`reaction`] module for the permissions that are
+    /// required.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`reaction`]: reaction/index.html
+    pub fn can_react<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        emoji: EmojiSource,
+        existing: bool,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(reaction::can_react(permissions, emoji, existing))
+    }
+
+    /// Check whether the member can create an invite to a channel.
+    ///
+    /// Refer to [`invite::check`] for the rules that are applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{invite::InviteError, Calculator};
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+    /// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+    ///
+    /// assert_eq!(
+    ///     Err(InviteError::MissingPermissions {
+    ///         missing: Permissions::CREATE_INVITE,
+    ///     }),
+    ///     calculator.can_create_invite(ChannelType::GuildText, &[]),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InviteError::ChannelType`] or
+    /// [`InviteError::MissingPermissions`] if the member can't create an
+    /// invite to the channel.
+    ///
+    /// Returns [`InviteError::Calculating`] if the permissions couldn't be
+    /// calculated; refer to [`in_channel`] for when that happens.
+    ///
+    /// [`InviteError::Calculating`]: invite/enum.InviteError.html#variant.Calculating
+    /// [`InviteError::ChannelType`]: invite/enum.InviteError.html#variant.ChannelType
+    /// [`InviteError::MissingPermissions`]: invite/enum.InviteError.html#variant.MissingPermissions
+    /// [`in_channel`]: #method.in_channel
+    /// [`invite::check`]: invite/fn.check.html
+    pub fn can_create_invite<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<(), InviteError> {
+        let permissions = self
+            .in_channel(channel_type, channel_overwrites)
+            .map_err(|source| InviteError::Calculating { source })?;
+
+        invite::check(permissions, channel_type)
+    }
+
+    /// Whether the member can join a voice or stage channel.
+    ///
+    /// `user_limit` is the channel's user limit and `current_members` is how
+    /// many members are already connected to it. Refer to
+    /// [`voice::can_join_voice`] for the rules that are applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`voice::can_join_voice`]: voice/fn.can_join_voice.html
+    pub fn can_join_voice<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        user_limit: Option<u64>,
+        current_members: u64,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(voice::can_join_voice(
+            permissions,
+            user_limit,
+            current_members,
+        ))
+    }
+
+    /// Check whether the member can stream in a voice or stage channel, also
+    /// known as going live.
+    ///
+    /// `suppressed` is whether the member is in the audience of a stage
+    /// channel. Refer to [`voice::check_stream`] for the rules that are
+    /// applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{voice::StreamError, Calculator};
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::CONNECT | Permissions::VIEW_CHANNEL)];
+    /// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+    ///
+    /// assert_eq!(
+    ///     Err(StreamError::MissingPermissions {
+    ///         missing: Permissions::STREAM,
+    ///     }),
+    ///     calculator.can_stream(ChannelType::GuildVoice, &[], false),
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StreamError::ChannelType`], [`StreamError::MissingPermissions`],
+    /// or [`StreamError::Suppressed`] if the member can't stream in the
+    /// channel.
+    ///
+    /// Returns [`StreamError::Calculating`] if the permissions couldn't be
+    /// calculated; refer to [`in_channel`] for when that happens.
+    ///
+    /// [`StreamError::Calculating`]: voice/enum.StreamError.html#variant.Calculating
+    /// [`StreamError::ChannelType`]: voice/enum.StreamError.html#variant.ChannelType
+    /// [`StreamError::MissingPermissions`]: voice/enum.StreamError.html#variant.MissingPermissions
+    /// [`StreamError::Suppressed`]: voice/enum.StreamError.html#variant.Suppressed
+    /// [`in_channel`]: #method.in_channel
+    /// [`voice::check_stream`]: voice/fn.check_stream.html
+    pub fn can_stream<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        suppressed: bool,
+    ) -> Result<(), StreamError> {
+        let permissions = self
+            .in_channel(channel_type, channel_overwrites)
+            .map_err(|source| StreamError::Calculating { source })?;
+
+        voice::check_stream(permissions, channel_type, suppressed)
+    }
+
+    /// Whether the member can mention a role in a channel, notifying the
+    /// role's members.
+    ///
+    /// Refer to [`mention::can_mention_role`] for the rules that are applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`mention::can_mention_role`]: mention/fn.can_mention_role.html
+    pub fn can_mention_role<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        role: &Role,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(mention::can_mention_role(permissions, role))
+    }
+
+    /// Whether the member can send messages in a channel, and with which
+    /// limits.
+    ///
+    /// `slowmode` is whether the channel has a slowmode enabled. Refer to the
+    /// [`send`] module for the rules that are applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{
+    ///     send::{SendCapability, SendLimit},
+    ///     Calculator,
+    /// };
+    ///
+    /// let member_roles = &[(
+    ///     RoleId(1),
+    ///     Permissions::ATTACH_FILES
+    ///         | Permissions::READ_MESSAGE_HISTORY
+    ///         | Permissions::SEND_MESSAGES
+    ///         | Permissions::VIEW_CHANNEL,
+    /// )];
+    ///
+    /// let capability = Calculator::new(GuildId(1), UserId(2), member_roles)
+    ///     .can_send_in(ChannelType::GuildText, &[], true)?;
+    ///
+    /// assert_eq!(SendCapability::Limited(vec![SendLimit::Slowmode]), capability);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`send`]: send/index.html
+    pub fn can_send_in<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        slowmode: bool,
+    ) -> Result<SendCapability, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(send::check(permissions, channel_type, slowmode))
+    }
+
+    /// Whether the member is exempt from a channel's slowmode.
+    ///
+    /// `bot` is whether the member is a bot, since bots are always exempt.
+    /// Refer to [`send::is_slowmode_exempt`] for the permissions that exempt
+    /// other members.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`send::is_slowmode_exempt`]: send/fn.is_slowmode_exempt.html
+    pub fn is_slowmode_exempt<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        bot: bool,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(send::is_slowmode_exempt(permissions, bot))
+    }
+
+    /// Whether the member can use application commands, such as slash
+    /// commands, in a channel.
+    ///
+    /// Application commands can only be used in text and news channels, and
+    /// require the Use Slash Commands and View Channel permissions.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    pub fn can_use_commands_in<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(permissions.contains(Permissions::USE_SLASH_COMMANDS | Permissions::VIEW_CHANNEL))
+    }
+
+    /// Whether the member can take an action in a channel.
+    ///
+    /// Refer to [`Action::required`] for the permissions that each action
+    /// requires.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{action::Action, Calculator};
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+    ///
+    /// assert!(calculator
+    ///     .clone()
+    ///     .can_perform(Action::SendMessage, ChannelType::GuildText, &[])?);
+    /// assert!(!calculator.can_perform(Action::DeleteOthersMessage, ChannelType::GuildText, &[])?);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`Action::required`]: action/enum.Action.html#method.required
+    /// [`in_channel`]: #method.in_channel
+    pub fn can_perform<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        action: Action,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(channel_type, channel_overwrites)?;
+
+        Ok(action::can_perform(permissions, action, channel_type))
+    }
+
+    /// Whether the member can take an action in a channel right now, taking
+    /// restrictions that aren't permissions into account.
+    ///
+    /// The member's timeout is taken from the calculator, and the
+    /// verification level is ignored if the member has a role other than the
+    /// `@everyone` role or owns the guild. Refer to the [`capability`] module
+    /// for the restrictions that are applied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::{Permissions, VerificationLevel},
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{
+    ///     action::Action,
+    ///     capability::{Capability, Gates, Restriction},
+    ///     Calculator,
+    /// };
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let gates = Gates::new().unverified(VerificationLevel::Medium);
+    ///
+    /// let capability = Calculator::new(GuildId(1), UserId(2), member_roles).capability(
+    ///     Action::SendMessage,
+    ///     ChannelType::GuildText,
+    ///     &[],
+    ///     gates,
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     Capability::No(Restriction::VerificationLevel {
+    ///         level: VerificationLevel::Medium,
+    ///     }),
+    ///     capability,
+    /// );
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`capability`]: capability/index.html
+    /// [`in_channel`]: #method.in_channel
+    pub fn capability<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        action: Action,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        gates: Gates,
+    ) -> Result<Capability, CalculatorError> {
+        let root = self.root()?;
+        let permissions = self.in_channel_with_root(root, channel_type, channel_overwrites);
+
+        let timed_out = !root.contains(Permissions::ADMINISTRATOR) && self.is_timed_out();
+        let verification_exempt = self.owner_id == Some(self.user_id)
+            || self
+                .member_roles
+                .iter()
+                .any(|(role_id, _)| role_id.0 != self.guild_id.0);
+
+        Ok(capability::check(
+            permissions,
+            action,
+            channel_type,
+            gates.member(timed_out, verification_exempt),
+        ))
+    }
+
+    /// Whether the member is a moderator of a stage channel.
+    ///
+    /// Refer to [`stage::is_moderator`] for the permissions that are required.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`stage::is_moderator`]: stage/fn.is_moderator.html
+    pub fn is_stage_moderator<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        self,
+        channel_overwrites: U,
+    ) -> Result<bool, CalculatorError> {
+        let permissions = self.in_channel(ChannelType::GuildStageVoice, channel_overwrites)?;
+
+        Ok(stage::is_moderator(permissions))
+    }
+
+    /// Require that the member has a set of permissions in every one of the
+    /// given channels.
+    ///
+    /// Channels are given as tuples of the channel's ID, type, and permission
+    /// overwrites. The guild-level permissions of the member are only
+    /// calculated once.
+    ///
+    /// Permissions in each channel are calculated like [`in_channel`]. Required
+    /// guild-level permissions, such as Ban Members, are checked against the
+    /// member's guild-level permissions since they're never present in a
+    /// channel.
+    ///
+    /// # Examples
+    ///
+    /// Check that a bot can post in every log channel:
+    ///
+    /// ```rust
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{ChannelId, GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{require::RequireError, Calculator};
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let locked = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::SEND_MESSAGES,
+    ///     kind: PermissionOverwriteType::Role(RoleId(1)),
+    /// }];
+    /// let channels = vec![
+    ///     (ChannelId(2), ChannelType::GuildText, &[][..]),
+    ///     (ChannelId(3), ChannelType::GuildText, &locked[..]),
+    /// ];
+    ///
+    /// let calculator = Calculator::new(GuildId(1), UserId(4), member_roles);
+    ///
+    /// match calculator.require_in_all(channels, Permissions::SEND_MESSAGES) {
+    ///     Err(RequireError::MissingPermissions { channels }) => {
+    ///         assert_eq!(1, channels.len());
+    ///         assert_eq!(ChannelId(3), channels[0].channel_id);
+    ///         assert_eq!(Permissions::SEND_MESSAGES, channels[0].missing);
+    ///     }
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequireError::MissingPermissions`] listing every channel in
+    /// which the member is missing some of the required permissions.
+    ///
+    /// Returns [`RequireError::Calculating`] if the permissions couldn't be
+    /// calculated; refer to [`root`] for when that happens.
+    ///
+    /// [`RequireError::Calculating`]: require/enum.RequireError.html#variant.Calculating
+    /// [`RequireError::MissingPermissions`]: require/enum.RequireError.html#variant.MissingPermissions
+    /// [`in_channel`]: #method.in_channel
+    /// [`root`]: #method.root
+    pub fn require_in_all<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+        required: Permissions,
+    ) -> Result<(), RequireError> {
+        let root = self
+            .root()
+            .map_err(|source| RequireError::Calculating { source })?;
+
+        let missing = channels
+            .into_iter()
+            .filter_map(|(channel_id, channel_type, channel_overwrites)| {
+                let permissions = self.in_channel_with_root(root, channel_type, channel_overwrites);
+
+                MissingInChannel::new(channel_id, root, permissions, required)
+            })
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(RequireError::MissingPermissions { channels: missing })
+        }
+    }
+
+    /// Require that the member has a set of permissions in a channel.
+    ///
+    /// The channel is given as a tuple of its ID, type, and permission
+    /// overwrites. Required guild-level permissions are checked against the
+    /// member's guild-level permissions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{ChannelId, GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{require::RequireError, Calculator};
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+    /// let channel = (ChannelId(3), ChannelType::GuildText, &[][..]);
+    /// let required = Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES;
+    ///
+    /// match calculator.require_in_channel(channel, required) {
+    ///     Err(RequireError::MissingInChannel { missing, .. }) => {
+    ///         assert_eq!(Permissions::EMBED_LINKS, missing);
+    ///     }
+    ///     other => panic!("unexpected result: {:?}", other),
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequireError::MissingInChannel`] with exactly the required
+    /// permissions that the member doesn't have in the channel.
+    ///
+    /// Returns [`RequireError::Calculating`] if the permissions couldn't be
+    /// calculated; refer to [`root`] for when that happens.
+    ///
+    /// [`RequireError::Calculating`]: require/enum.RequireError.html#variant.Calculating
+    /// [`RequireError::MissingInChannel`]: require/enum.RequireError.html#variant.MissingInChannel
+    /// [`root`]: #method.root
+    pub fn require_in_channel(
+        &self,
+        channel: (ChannelId, ChannelType, &[PermissionOverwrite]),
+        required: Permissions,
+    ) -> Result<(), RequireError> {
+        let (channel_id, channel_type, channel_overwrites) = channel;
+        let root = self
+            .root()
+            .map_err(|source| RequireError::Calculating { source })?;
+        let permissions = self.in_channel_with_root(root, channel_type, channel_overwrites);
+
+        match MissingInChannel::new(channel_id, root, permissions, required) {
+            Some(MissingInChannel {
+                channel_id,
+                missing,
+            }) => Err(RequireError::MissingInChannel {
+                channel_id,
+                missing,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Require that the member has a set of permissions in at least one of the
+    /// given channels, returning the ID of the first channel that meets the
+    /// requirement.
+    ///
+    /// Channels are given and calculated like [`require_in_all`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RequireError::MissingPermissions`] listing every channel and
+    /// what the member is missing in it if none of the channels meet the
+    /// requirement, including when no channels were given.
+    ///
+    /// Returns [`RequireError::Calculating`] if the permissions couldn't be
+    /// calculated; refer to [`root`] for when that happens.
+    ///
+    /// [`RequireError::Calculating`]: require/enum.RequireError.html#variant.Calculating
+    /// [`RequireError::MissingPermissions`]: require/enum.RequireError.html#variant.MissingPermissions
+    /// [`require_in_all`]: #method.require_in_all
+    /// [`root`]: #method.root
+    pub fn require_in_any<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+        required: Permissions,
+    ) -> Result<ChannelId, RequireError> {
+        let root = self
+            .root()
+            .map_err(|source| RequireError::Calculating { source })?;
+
+        let mut missing = Vec::new();
+
+        for (channel_id, channel_type, channel_overwrites) in channels {
+            let permissions = self.in_channel_with_root(root, channel_type, channel_overwrites);
+
+            match MissingInChannel::new(channel_id, root, permissions, required) {
+                Some(channel) => missing.push(channel),
+                None => return Ok(channel_id),
+            }
+        }
+
+        Err(RequireError::MissingPermissions { channels: missing })
+    }
+
+    /// Audit whether the member has the permissions it needs in each channel
+    /// it uses, such as a bot checking its setup on startup.
+    ///
+    /// Requirements are a list of channel purposes - such as logging,
+    /// moderation, or music - and the permissions required for each purpose.
+    /// Channels are given as tuples of the channel's purpose, ID, type, and
+    /// permission overwrites. A channel whose purpose has no requirement
+    /// requires no permissions, and a purpose listed more than once requires
+    /// the permissions of every entry.
+    ///
+    /// Permissions are checked like [`require_in_all`], and the guild-level
+    /// permissions of the member are only calculated once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::ChannelType,
+    ///     guild::Permissions,
+    ///     id::{ChannelId, GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let requirements = &[
+    ///     ("logging", Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+    ///     ("music", Permissions::CONNECT | Permissions::SPEAK),
+    /// ];
+    /// let channels = vec![
+    ///     ("logging", ChannelId(2), ChannelType::GuildText, &[][..]),
+    ///     ("music", ChannelId(3), ChannelType::GuildVoice, &[][..]),
+    /// ];
+    ///
+    /// let report = Calculator::new(GuildId(1), UserId(4), member_roles)
+    ///     .self_audit(requirements, channels)?;
+    ///
+    /// assert_eq!(1, report.deficiencies.len());
+    /// assert_eq!("music", report.deficiencies[0].purpose);
+    /// assert_eq!(ChannelId(3), report.deficiencies[0].channel_id);
+    /// assert_eq!(Permissions::CONNECT | Permissions::SPEAK, report.deficiencies[0].missing);
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the permissions couldn't be calculated; refer to
+    /// [`root`] for when that happens.
+    ///
+    /// [`require_in_all`]: #method.require_in_all
+    /// [`root`]: #method.root
+    pub fn self_audit<
+        'b,
+        P: PartialEq,
+        U: IntoIterator<Item = (P, ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        requirements: &[(P, Permissions)],
+        channels: U,
+    ) -> Result<SelfAuditReport<P>, CalculatorError> {
+        let root = self.root()?;
+
+        let deficiencies = channels
+            .into_iter()
+            .filter_map(|(purpose, channel_id, channel_type, channel_overwrites)| {
+                let required = requirements
+                    .iter()
+                    .filter(|(requirement_purpose, _)| *requirement_purpose == purpose)
+                    .fold(Permissions::empty(), |required, (_, permissions)| {
+                        required | *permissions
+                    });
+                let permissions = self.in_channel_with_root(root, channel_type, channel_overwrites);
+
+                MissingInChannel::new(channel_id, root, permissions, required).map(|missing| {
+                    Deficiency {
+                        channel_id,
+                        missing: missing.missing,
+                        purpose,
+                    }
+                })
+            })
+            .collect();
+
+        Ok(SelfAuditReport { deficiencies })
+    }
+
+    /// Count the channels in which the member has each permission.
+    ///
+    /// Channels are given as tuples of the channel's ID, type, and permission
+    /// overwrites. The guild-level permissions of the member are only
+    /// calculated once, and permissions in each channel are calculated like
+    /// [`in_channel`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     channel::{
+    ///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+    ///         ChannelType,
+    ///     },
+    ///     guild::Permissions,
+    ///     id::{ChannelId, GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::Calculator;
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL)];
+    /// let rules = &[PermissionOverwrite {
+    ///     allow: Permissions::empty(),
+    ///     deny: Permissions::MANAGE_MESSAGES,
+    ///     kind: PermissionOverwriteType::Role(RoleId(1)),
+    /// }];
+    /// let channels = vec![
+    ///     (ChannelId(2), ChannelType::GuildText, &[][..]),
+    ///     (ChannelId(3), ChannelType::GuildText, &rules[..]),
+    /// ];
+    ///
+    /// let summary = Calculator::new(GuildId(1), UserId(4), member_roles).summarize(channels)?;
+    ///
+    /// assert_eq!(2, summary.channels);
+    /// assert_eq!(1, summary.count(Permissions::MANAGE_MESSAGES));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the permissions couldn't be calculated; refer to
+    /// [`root`] for when that happens.
+    ///
+    /// [`in_channel`]: #method.in_channel
+    /// [`root`]: #method.root
+    pub fn summarize<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+    ) -> Result<PermissionSummary, CalculatorError> {
+        let root = self.root()?;
+
+        Ok(PermissionSummary::new(channels.into_iter().map(
+            |(_, channel_type, channel_overwrites)| {
+                self.in_channel_with_root(root, channel_type, channel_overwrites)
+            },
+        )))
+    }
+
+    /// Verify that the member's permissions in a channel don't depend on the
+    /// order of the member's roles or of the channel's overwrites.
+    ///
+    /// Permissions are calculated with every rotation of the roles and the
+    /// overwrites, both as given and reversed, and compared to the result of
+    /// [`in_channel`]. Returns `false` if any ordering gives a different
+    /// result.
+    ///
+    /// This is a debugging utility for ruling out input order as the cause of
+    /// unexpected permissions, and is much slower than [`in_channel`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`in_channel`].
+    ///
+    /// [`in_channel`]: #method.in_channel
+    pub fn verify_order_independence(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: &[PermissionOverwrite],
+    ) -> Result<bool, CalculatorError> {
+        let expected = self.clone().in_channel(channel_type, channel_overwrites)?;
+        let overwrite_orderings = orderings(channel_overwrites);
+
+        for member_roles in orderings(&self.member_roles) {
+            let calculator = Calculator {
+                member_roles: Cow::Owned(member_roles),
+                ..self.clone()
+            };
+
+            for overwrites in &overwrite_orderings {
+                if calculator.clone().in_channel(channel_type, overwrites)? != expected {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Check that all of the given IDs are plausible snowflakes.
+    fn check_ids(&self) -> Result<(), CalculatorError> {
+        let ids = [
+            (self.guild_id.0, IdKind::Guild),
+            (self.user_id.0, IdKind::User),
+        ];
+        let owner = self.owner_id.map(|id| (id.0, IdKind::Owner));
+        let roles = self.member_roles.iter().map(|(id, _)| (id.0, IdKind::Role));
+
+        for (id, kind) in ids.iter().copied().chain(owner).chain(roles) {
+            if !is_plausible_snowflake(id) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(id, %kind, "ID is not a plausible snowflake");
+
+                return Err(CalculatorError::InvalidId { id, kind });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Calculate the permissions of the member in a channel from their
+    /// already calculated guild-level permissions.
+    fn in_channel_with_root<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        root: Permissions,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Permissions {
+        self.in_channel_with_root_traced(root, channel_type, channel_overwrites, &mut |_, _, _| {})
+    }
+
+    /// Calculate the permissions of the member in a channel from their
+    /// guild-level permissions, recording each step taken.
+    fn in_channel_with_root_traced<
+        'b,
+        U: IntoIterator<Item = &'b PermissionOverwrite>,
+        F: FnMut(Step, Permissions, Permissions),
+    >(
+        &self,
+        root: Permissions,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        record: &mut F,
+    ) -> Permissions {
+        channel_permissions_traced(
+            self.guild_id,
+            Some(self.user_id),
+            &self.member_roles,
+            root,
+            ChannelOptions {
+                administrator_bypass: self.administrator_bypass
+                    || matches!(self.owner_id, Some(id) if id == self.user_id),
+                channel_type,
+                dependencies: &self.dependencies,
+                retain_guild_permissions: self.retain_guild_permissions,
+                timed_out: self.is_timed_out(),
+            },
+            channel_overwrites,
+            record,
+        )
+    }
+
+    /// Whether the member's timeout hasn't expired yet.
+    fn is_timed_out(&self) -> bool {
+        matches!(self.communication_disabled_until, Some(until) if until > SystemTime::now())
+    }
+}
+
+/// Every rotation of a list of items, both as given and reversed.
+fn orderings<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut reversed = items.to_vec();
+    reversed.reverse();
+
+    let mut orderings = Vec::with_capacity(items.len().max(1) * 2);
+
+    for base in &[items.to_vec(), reversed] {
+        for mid in 0..items.len().max(1) {
+            let mut ordering = base.clone();
+            ordering.rotate_left(mid);
+            orderings.push(ordering);
+        }
+    }
+
+    orderings
+}
+
+/// Calculate permissions in a channel from already calculated guild-level
+/// permissions.
+///
+/// The user ID is used to match member overwrites, and may be `None` when
+/// calculating the permissions of roles rather than of a member.
+fn channel_permissions<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+    guild_id: GuildId,
+    user_id: Option<UserId>,
+    member_roles: &[(RoleId, Permissions)],
+    root: Permissions,
+    channel_type: ChannelType,
+    channel_overwrites: U,
+) -> Permissions {
+    channel_permissions_traced(
+        guild_id,
+        user_id,
+        member_roles,
+        root,
+        ChannelOptions::new(channel_type),
+        channel_overwrites,
+        &mut |_, _, _| {},
+    )
+}
+
+/// Dependencies applied when calculating permissions in channels without a
+/// calculator.
+static DEFAULT_DEPENDENCIES: Dependencies = Dependencies::PRACTICAL;
+
+/// Type of a channel and configuration of how permissions in it are
+/// calculated.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct ChannelOptions<'a> {
+    /// Whether administrators have every permission regardless of the
+    /// channel.
+    administrator_bypass: bool,
+    /// Type of the channel.
+    channel_type: ChannelType,
+    /// Permissions to implicitly deny when the permissions they depend on
+    /// are denied.
+    dependencies: &'a Dependencies,
+    /// Whether to keep guild-level permissions.
+    retain_guild_permissions: bool,
+    /// Whether the member is timed out.
+    timed_out: bool,
+}
+
+impl ChannelOptions<'static> {
+    /// Create the default configuration for a type of channel.
+    fn new(channel_type: ChannelType) -> Self {
+        Self {
+            administrator_bypass: true,
+            channel_type,
+            dependencies: &DEFAULT_DEPENDENCIES,
+            retain_guild_permissions: false,
+            timed_out: false,
+        }
+    }
+}
+
+/// Calculate permissions in a channel like [`channel_permissions`], passing
+/// each step taken to `record` along with the permissions before and after it.
+///
+/// `options` contains the type of the channel and configures how the
+/// calculation differs from the default.
+///
+/// [`channel_permissions`]: fn.channel_permissions.html
+fn channel_permissions_traced<
+    'b,
+    U: IntoIterator<Item = &'b PermissionOverwrite>,
+    F: FnMut(Step, Permissions, Permissions),
+>(
+    guild_id: GuildId,
+    user_id: Option<UserId>,
+    member_roles: &[(RoleId, Permissions)],
+    root: Permissions,
+    options: ChannelOptions<'_>,
+    channel_overwrites: U,
+    record: &mut F,
+) -> Permissions {
+    let administrator = root.contains(Permissions::ADMINISTRATOR);
+
+    // If the user contains the administrator privilege from the calculated
+    // root permissions, then we do not need to do any more work.
+    if administrator && options.administrator_bypass {
+        return root;
+    }
+
+    // Without the Administrator permission, the channel's overwrites and
+    // type apply to the member's other permissions. The guild-level
+    // permissions are already all of them at this point, so the ones the
+    // member's roles actually grant are combined again.
+    let base = if administrator {
+        member_roles
+            .iter()
+            .fold(Permissions::empty(), |acc, (_, permissions)| {
+                acc | *permissions
+            })
+            - Permissions::ADMINISTRATOR
+    } else {
+        root
+    };
+
+    let permissions = apply_overwrites_traced(
+        guild_id,
+        user_id,
+        member_roles,
+        base,
+        options,
+        channel_overwrites,
+        record,
+    );
+
+    // Overwrites can't give permissions back to a timed out member, but
+    // owners and administrators aren't affected by timeouts.
+    if !administrator && options.timed_out {
+        record(
+            Step::TimedOut,
+            permissions,
+            permissions & PERMISSIONS_TIMED_OUT,
+        );
+
+        permissions & PERMISSIONS_TIMED_OUT
+    } else {
+        permissions
+    }
+}
+
+/// Apply the overwrites of a channel and the permissions allowed in its type
+/// to a member's guild-level permissions, which don't contain the
+/// Administrator permission.
+fn apply_overwrites_traced<
+    'b,
+    U: IntoIterator<Item = &'b PermissionOverwrite>,
+    F: FnMut(Step, Permissions, Permissions),
+>(
+    guild_id: GuildId,
+    user_id: Option<UserId>,
+    member_roles: &[(RoleId, Permissions)],
+    root: Permissions,
+    options: ChannelOptions<'_>,
+    channel_overwrites: U,
+    record: &mut F,
+) -> Permissions {
+    let mut permissions = root;
+
+    // Hierarchy documentation:
+    // <https://discord.com/developers/docs/topics/permissions#permission-overwrites>
+    //
+    // Overwrites are collected before any of them are applied so that the
+    // result doesn't depend on the order of the overwrites.
+    let mut everyone_allow = Permissions::empty();
+    let mut everyone_deny = Permissions::empty();
+    let mut member_allow = Permissions::empty();
+    let mut member_deny = Permissions::empty();
+    let mut roles_allow = Permissions::empty();
+    let mut roles_deny = Permissions::empty();
+
+    for overwrite in channel_overwrites {
+        match overwrite.kind {
+            PermissionOverwriteType::Role(role) => {
+                // The @everyone role's overwrite is applied before the other
+                // roles' overwrites.
+                if role.0 == guild_id.0 {
+                    everyone_allow.insert(overwrite.allow);
+                    everyone_deny.insert(overwrite.deny);
+
+                    continue;
+                }
+
+                if !member_roles.iter().any(|(id, _)| *id == role) {
+                    continue;
+                }
+
+                roles_allow.insert(overwrite.allow);
+                roles_deny.insert(overwrite.deny);
+            }
+            PermissionOverwriteType::Member(id) if Some(id) == user_id => {
+                member_allow.insert(overwrite.allow);
+                member_deny.insert(overwrite.deny);
+            }
+            PermissionOverwriteType::Member(_) => {}
+        }
+    }
+
+    let mut before = permissions;
+    permissions.remove(everyone_deny);
+    permissions.insert(everyone_allow);
+    record(Step::EveryoneOverwrite, before, permissions);
+
+    // If an overwrite denies a permission and no overwrite applied after it
+    // allows it again, then the permissions that depend on it can be removed.
+    for dependency in options.dependencies.iter() {
+        let permission = dependency.permission;
+        let member_denied = member_deny.contains(permission) && !member_allow.contains(permission);
+        let role_denied = roles_deny.contains(permission)
+            && !roles_allow.contains(permission)
+            && !member_allow.contains(permission);
+        let everyone_denied = everyone_deny.contains(permission)
+            && !everyone_allow.contains(permission)
+            && !roles_allow.contains(permission)
+            && !member_allow.contains(permission);
+
+        if !member_denied && !role_denied && !everyone_denied {
+            continue;
+        }
+
+        let step = if permission == Permissions::VIEW_CHANNEL {
+            Step::ViewChannelDenied
+        } else if permission == Permissions::SEND_MESSAGES {
+            Step::SendMessagesDenied
+        } else {
+            Step::DependencyDenied { permission }
+        };
+
+        if dependency.dependents.is_all() {
+            record(step, permissions, Permissions::empty());
+
+            return Permissions::empty();
+        }
+
+        member_allow.remove(dependency.dependents);
+        roles_allow.remove(dependency.dependents);
+        before = permissions;
+        permissions.remove(dependency.dependents);
+        record(step, before, permissions);
+    }
+
+    before = permissions;
+    permissions.remove(roles_deny);
+    permissions.insert(roles_allow);
+    record(Step::RoleOverwrites, before, permissions);
+
+    before = permissions;
+    permissions.remove(member_deny);
+    permissions.insert(member_allow);
+    record(Step::MemberOverwrite, before, permissions);
+
+    // Remove permissions that can't be used in a channel, i.e. are relevant
+    // to guild-level permission calculating.
+    if !options.retain_guild_permissions {
+        before = permissions;
+        permissions.remove(PERMISSIONS_ROOT);
+        record(Step::GuildPermissions, before, permissions);
+    }
+
+    // Now remove permissions that can't be used in text, voice, or stage
+    // channels based on this channel's type. This handles category channels
+    // by removing all text, voice, and stage permissions. Guild-level
+    // permissions are left as they are.
+    before = permissions;
+    permissions &= channel::allowed_in(options.channel_type) | PERMISSIONS_ROOT;
+
+    record(
+        Step::ChannelType {
+            kind: options.channel_type,
+        },
+        before,
+        permissions,
+    );
+
+    permissions
+}
+
+/// Define methods of [`InfallibleCalculator`] that call the method of the same
+/// name on the inner [`Calculator`].
+///
+/// Each method is prefixed with how the inner method's result is handled:
+///
+/// - `configure` methods configure the inner calculator and return the
+///   infallible calculator;
+/// - `unwrap` methods unwrap the result of a calculation, which can't fail;
+/// - `forward` methods return the result as is, for methods whose errors
+///   aren't about the calculation itself.
+///
+/// Generic parameters are given in brackets after the name of the method.
+///
+/// [`Calculator`]: struct.Calculator.html
+/// [`InfallibleCalculator`]: struct.InfallibleCalculator.html
+macro_rules! infallible {
+    ($(
+        $(#[$meta:meta])*
+        $kind:ident fn $name:ident $([$($generics:tt)*])? ($($params:tt)*) -> $ret:ty;
+    )*) => {
+        $(
+            infallible!(
+                @method $kind $(#[$meta])* fn $name [$($($generics)*)?] ($($params)*) -> $ret
+            );
+        )*
+    };
+    (@method configure $(#[$meta:meta])* fn $name:ident [] (
+        mut self,
+        $arg:ident: $ty:ty $(,)?
+    ) -> $ret:ty) => {
+        $(#[$meta])*
+        pub fn $name(mut self, $arg: $ty) -> $ret {
+            self.0 = self.0.$name($arg);
+
+            self
+        }
+    };
+    (@method $kind:ident $(#[$meta:meta])* fn $name:ident [$($generics:tt)*] (
+        &self $(, $arg:ident: $ty:ty)* $(,)?
+    ) -> $ret:ty) => {
+        $(#[$meta])*
+        pub fn $name<$($generics)*>(&self $(, $arg: $ty)*) -> $ret {
+            infallible!(@call $kind self.0.$name($($arg),*))
+        }
+    };
+    (@method $kind:ident $(#[$meta:meta])* fn $name:ident [$($generics:tt)*] (
+        self $(, $arg:ident: $ty:ty)* $(,)?
+    ) -> $ret:ty) => {
+        $(#[$meta])*
+        pub fn $name<$($generics)*>(self $(, $arg: $ty)*) -> $ret {
+            infallible!(@call $kind self.0.$name($($arg),*))
+        }
+    };
+    (@call unwrap $call:expr) => {
+        $call.expect("inner fallible calculator is configured to ignore errors")
+    };
+    (@call forward $call:expr) => {
+        $call
+    };
+}
+
+/// Dangerous infallible calculator to calculate the permissions of a member.
+///
+/// **Note that using this is dangerous, as it may allow your application to
+/// think a member has a permission when in reality they don't, or vice versa.**
+///
+/// This is a variant of the [`Calculator`] which will ignore when expected
+/// items are missing, such as the `@everyone` role information missing.
+///
+/// Every method calls the method of the same name on an inner [`Calculator`]
+/// that continues on missing items and doesn't validate IDs, so it never
+/// returns a [`CalculatorError`]. Its results are unwrapped, other than those
+/// of methods whose errors aren't about calculating permissions, such as
+/// [`can_create_invite`], which are returned as is.
+///
 /// Refer to [`Calculator`] for additional information.
 ///
 /// [`Calculator`]: struct.Calculator.html
-#[derive(Clone, Debug, Eq, PartialEq)]
+/// [`CalculatorError`]: enum.CalculatorError.html
+/// [`can_create_invite`]: #method.can_create_invite
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[must_use = "the member calculator isn't useful if you don't calculate permissions"]
 pub struct InfallibleCalculator<'a>(Calculator<'a>);
 
-impl<'a> InfallibleCalculator<'a> {
-    /// Create an infallible calculator to calculate the permissions of a
-    /// member.
-    pub fn new(
-        guild_id: GuildId,
-        user_id: UserId,
-        member_roles: &'a [(RoleId, Permissions)],
-    ) -> Self {
-        let mut inner = Calculator::new(guild_id, user_id, member_roles);
-        inner.continue_on_missing_items = true;
+impl<'a> InfallibleCalculator<'a> {
+    /// Create an infallible calculator to calculate the permissions of a
+    /// member.
+    pub fn new(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: &'a [(RoleId, Permissions)],
+    ) -> Self {
+        Self::from_calculator(Calculator::new(guild_id, user_id, member_roles))
+    }
+
+    /// Create an infallible calculator that owns the member's roles.
+    ///
+    /// Refer to [`Calculator::from_owned`] for more information.
+    ///
+    /// [`Calculator::from_owned`]: struct.Calculator.html#method.from_owned
+    pub fn from_owned(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: Vec<(RoleId, Permissions)>,
+    ) -> Self {
+        Self::from_calculator(Calculator::from_owned(guild_id, user_id, member_roles))
+    }
+
+    /// Wrap a calculator, configuring it to continue on missing items.
+    fn from_calculator(mut inner: Calculator<'a>) -> Self {
+        inner.continue_on_missing_items = true;
+
+        Self(inner)
+    }
+
+    infallible! {
+        /// Configure the ID of the owner of the guild.
+        ///
+        /// Refer to the documentation for [`Calculator::owner_id`].
+        ///
+        /// [`Calculator::owner_id`]: struct.Calculator.html#method.owner_id
+        configure fn owner_id(mut self, owner_id: UserId) -> Self;
+
+        /// Configure whether administrators have every permission in every
+        /// channel.
+        ///
+        /// Refer to the documentation for [`Calculator::administrator_bypass`].
+        ///
+        /// [`Calculator::administrator_bypass`]: struct.Calculator.html#method.administrator_bypass
+        configure fn administrator_bypass(mut self, administrator_bypass: bool) -> Self;
+
+        /// Configure whether denying a permission in a channel implicitly denies
+        /// the permissions that depend on it.
+        ///
+        /// Refer to the documentation for [`Calculator::cascade_mode`].
+        ///
+        /// [`Calculator::cascade_mode`]: struct.Calculator.html#method.cascade_mode
+        configure fn cascade_mode(mut self, cascade_mode: CascadeMode) -> Self;
+
+        /// Configure when the member's timeout expires.
+        ///
+        /// Refer to the documentation for
+        /// [`Calculator::communication_disabled_until`].
+        ///
+        /// [`Calculator::communication_disabled_until`]: struct.Calculator.html#method.communication_disabled_until
+        configure fn communication_disabled_until(
+            mut self,
+            communication_disabled_until: SystemTime,
+        ) -> Self;
+
+        /// Configure the version of the data that permissions are calculated
+        /// from.
+        ///
+        /// Refer to the documentation for [`Calculator::data_version`].
+        ///
+        /// [`Calculator::data_version`]: struct.Calculator.html#method.data_version
+        configure fn data_version(mut self, data_version: DataVersion) -> Self;
+
+        /// Configure which permissions are implicitly denied in a channel when the
+        /// permissions they depend on are denied.
+        ///
+        /// Refer to the documentation for [`Calculator::dependencies`].
+        ///
+        /// [`Calculator::dependencies`]: struct.Calculator.html#method.dependencies
+        configure fn dependencies(mut self, dependencies: Dependencies) -> Self;
+
+        /// Configure whether to keep guild-level permissions in the permissions
+        /// calculated in channels.
+        ///
+        /// Refer to the documentation for
+        /// [`Calculator::retain_guild_permissions`].
+        ///
+        /// [`Calculator::retain_guild_permissions`]: struct.Calculator.html#method.retain_guild_permissions
+        configure fn retain_guild_permissions(mut self, retain_guild_permissions: bool) -> Self;
+
+        /// Calculate the guild-level permissions of a member without handling
+        /// errors.
+        ///
+        /// Refer to [`Calculator::root`] for more information.
+        ///
+        /// [`Calculator::root`]: struct.Calculator.html#method.root
+        unwrap fn root(&self) -> Permissions;
+
+        /// Calculate the permissions of the member in a channel without handling
+        /// errors, taking into account a combination of the guild-level permissions
+        /// and channel-level permissions.
+        ///
+        /// Refer to [`Calculator::in_channel`] for more information.
+        ///
+        /// [`Calculator::in_channel`]: struct.Calculator.html#method.in_channel
+        unwrap fn in_channel['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Permissions;
+
+        /// Calculate the permissions of the member in a guild channel without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::in_guild_channel`] for more information.
+        ///
+        /// [`Calculator::in_guild_channel`]: struct.Calculator.html#method.in_guild_channel
+        unwrap fn in_guild_channel(self, channel: &GuildChannel) -> Permissions;
+
+        /// Calculate the permissions of the member in each of a set of channels
+        /// without handling errors.
+        ///
+        /// Refer to [`Calculator::in_channels`] for more information.
+        ///
+        /// [`Calculator::in_channels`]: struct.Calculator.html#method.in_channels
+        unwrap fn in_channels[
+            'b,
+            U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        ](
+            &self,
+            channels: U,
+        ) -> HashMap<ChannelId, Permissions>;
+
+        /// Find the channels of a set in which the member has permissions without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::channels_with`] for more information.
+        ///
+        /// [`Calculator::channels_with`]: struct.Calculator.html#method.channels_with
+        unwrap fn channels_with[
+            'b,
+            U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        ](
+            &self,
+            channels: U,
+            permissions: Permissions,
+        ) -> Vec<ChannelId>;
+
+        /// Calculate the permissions of the member in a channel along with where
+        /// each of them comes from, without handling errors.
+        ///
+        /// Refer to [`Calculator::explain`] for more information.
+        ///
+        /// [`Calculator::explain`]: struct.Calculator.html#method.explain
+        #[cfg(feature = "explain")]
+        unwrap fn explain['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Explanation;
+
+        /// Calculate the permissions of the member in a channel along with the
+        /// steps taken, without handling errors.
+        ///
+        /// Refer to [`Calculator::trace`] for more information.
+        ///
+        /// [`Calculator::trace`]: struct.Calculator.html#method.trace
+        #[cfg(feature = "explain")]
+        unwrap fn trace['b, U: IntoIterator<Item = &'b PermissionOverwrite>](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Trace;
+
+        /// Find what's responsible for the member missing a permission in a
+        /// channel, without handling errors.
+        ///
+        /// Refer to [`Calculator::why_denied`] for more information.
+        ///
+        /// [`Calculator::why_denied`]: struct.Calculator.html#method.why_denied
+        #[cfg(feature = "explain")]
+        unwrap fn why_denied['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            permission: Permissions,
+        ) -> Option<Denial>;
+
+        /// Create a human-readable report of the guild-level permissions of the
+        /// member without handling errors.
+        ///
+        /// Refer to [`Calculator::report_root`] for more information.
+        ///
+        /// [`Calculator::report_root`]: struct.Calculator.html#method.report_root
+        #[cfg(feature = "explain")]
+        unwrap fn report_root(&self) -> Report;
+
+        /// Create a human-readable report of the permissions of the member in a
+        /// channel without handling errors.
+        ///
+        /// Refer to [`Calculator::report`] for more information.
+        ///
+        /// [`Calculator::report`]: struct.Calculator.html#method.report
+        #[cfg(feature = "explain")]
+        unwrap fn report['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Report;
+
+        /// Preview the permissions of the member in a channel as if it were
+        /// synced with its category without handling errors.
+        ///
+        /// Refer to [`Calculator::in_channel_with_parent`] for more information.
+        ///
+        /// [`Calculator::in_channel_with_parent`]: struct.Calculator.html#method.in_channel_with_parent
+        unwrap fn in_channel_with_parent[
+            'b,
+            P: IntoIterator<Item = &'b PermissionOverwrite>,
+        ](
+            self,
+            channel_type: ChannelType,
+            parent_overwrites: P,
+        ) -> Permissions;
+
+        /// Calculate the guild-level permissions of a member without handling
+        /// errors, stamped with the configured data version.
+        ///
+        /// Refer to [`Calculator::root_versioned`] for more information.
+        ///
+        /// [`Calculator::root_versioned`]: struct.Calculator.html#method.root_versioned
+        unwrap fn root_versioned(&self) -> Versioned<Permissions>;
+
+        /// Create a calculator for the permissions of a role of the guild
+        /// without handling errors.
+        ///
+        /// The role doesn't need to be one of the member's roles. Roles that
+        /// are missing from the guild's roles have no permissions. Refer to
+        /// [`Calculator::role`] for more information.
+        ///
+        /// [`Calculator::role`]: struct.Calculator.html#method.role
+        unwrap fn role(&self, guild_roles: &[(RoleId, Permissions)], role_id: RoleId) -> RoleCalculator;
+
+        /// Calculate the permissions of the member in a channel without handling
+        /// errors, wrapped with helpers for common checks.
+        ///
+        /// Refer to [`Calculator::in_channel_calculated`] for more information.
+        ///
+        /// [`Calculator::in_channel_calculated`]: struct.Calculator.html#method.in_channel_calculated
+        unwrap fn in_channel_calculated['b, U: IntoIterator<Item = &'b PermissionOverwrite>](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> CalculatedPermissions;
+
+        /// Calculate the permissions of the member in a channel without handling
+        /// errors, depending on whether the channel is visible to them.
+        ///
+        /// Refer to [`Calculator::in_channel_visibility`] for more information.
+        ///
+        /// [`Calculator::in_channel_visibility`]: struct.Calculator.html#method.in_channel_visibility
+        unwrap fn in_channel_visibility['b, U: IntoIterator<Item = &'b PermissionOverwrite>](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> ChannelPermissions;
+
+        /// Calculate the permissions of the member in a channel without handling
+        /// errors, along with the permissions that don't apply to the channel.
+        ///
+        /// Refer to [`Calculator::in_channel_masked`] for more information.
+        ///
+        /// [`Calculator::in_channel_masked`]: struct.Calculator.html#method.in_channel_masked
+        unwrap fn in_channel_masked['b, U: IntoIterator<Item = &'b PermissionOverwrite>](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Masked;
+
+        /// Calculate the permissions of the member in a channel without handling
+        /// errors, stamped with the configured data version.
+        ///
+        /// Refer to [`Calculator::in_channel_versioned`] for more information.
+        ///
+        /// [`Calculator::in_channel_versioned`]: struct.Calculator.html#method.in_channel_versioned
+        unwrap fn in_channel_versioned['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Versioned<Permissions>;
+
+        /// Whether the member can react to a message in a channel with an emoji,
+        /// without handling errors.
+        ///
+        /// Refer to [`Calculator::can_react`] for more information.
+        ///
+        /// [`Calculator::can_react`]: struct.Calculator.html#method.can_react
+        unwrap fn can_react['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            emoji: EmojiSource,
+            existing: bool,
+        ) -> bool;
+
+        /// Check whether the member can create an invite to a channel, without
+        /// handling calculation errors.
+        ///
+        /// Refer to [`Calculator::can_create_invite`] for more information.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`InviteError::ChannelType`] or
+        /// [`InviteError::MissingPermissions`] if the member can't create an
+        /// invite to the channel. [`InviteError::Calculating`] is never returned.
+        ///
+        /// [`Calculator::can_create_invite`]: struct.Calculator.html#method.can_create_invite
+        /// [`InviteError::Calculating`]: invite/enum.InviteError.html#variant.Calculating
+        /// [`InviteError::ChannelType`]: invite/enum.InviteError.html#variant.ChannelType
+        /// [`InviteError::MissingPermissions`]: invite/enum.InviteError.html#variant.MissingPermissions
+        forward fn can_create_invite['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> Result<(), InviteError>;
+
+        /// Whether the member can join a voice or stage channel, without handling
+        /// errors.
+        ///
+        /// Refer to [`Calculator::can_join_voice`] for more information.
+        ///
+        /// [`Calculator::can_join_voice`]: struct.Calculator.html#method.can_join_voice
+        unwrap fn can_join_voice['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            user_limit: Option<u64>,
+            current_members: u64,
+        ) -> bool;
+
+        /// Check whether the member can stream in a voice or stage channel,
+        /// without handling calculation errors.
+        ///
+        /// Refer to [`Calculator::can_stream`] for more information.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`StreamError::ChannelType`], [`StreamError::MissingPermissions`],
+        /// or [`StreamError::Suppressed`] if the member can't stream in the
+        /// channel. [`StreamError::Calculating`] is never returned.
+        ///
+        /// [`Calculator::can_stream`]: struct.Calculator.html#method.can_stream
+        /// [`StreamError::Calculating`]: voice/enum.StreamError.html#variant.Calculating
+        /// [`StreamError::ChannelType`]: voice/enum.StreamError.html#variant.ChannelType
+        /// [`StreamError::MissingPermissions`]: voice/enum.StreamError.html#variant.MissingPermissions
+        /// [`StreamError::Suppressed`]: voice/enum.StreamError.html#variant.Suppressed
+        forward fn can_stream['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            suppressed: bool,
+        ) -> Result<(), StreamError>;
+
+        /// Whether the member can mention a role in a channel, without handling
+        /// errors.
+        ///
+        /// Refer to [`Calculator::can_mention_role`] for more information.
+        ///
+        /// [`Calculator::can_mention_role`]: struct.Calculator.html#method.can_mention_role
+        unwrap fn can_mention_role['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            role: &Role,
+        ) -> bool;
+
+        /// Whether the member can send messages in a channel, and with which
+        /// limits, without handling errors.
+        ///
+        /// Refer to [`Calculator::can_send_in`] for more information.
+        ///
+        /// [`Calculator::can_send_in`]: struct.Calculator.html#method.can_send_in
+        unwrap fn can_send_in['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            slowmode: bool,
+        ) -> SendCapability;
+
+        /// Whether the member is exempt from a channel's slowmode, without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::is_slowmode_exempt`] for more information.
+        ///
+        /// [`Calculator::is_slowmode_exempt`]: struct.Calculator.html#method.is_slowmode_exempt
+        unwrap fn is_slowmode_exempt['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            bot: bool,
+        ) -> bool;
+
+        /// Whether the member can use application commands in a channel, without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::can_use_commands_in`] for more information.
+        ///
+        /// [`Calculator::can_use_commands_in`]: struct.Calculator.html#method.can_use_commands_in
+        unwrap fn can_use_commands_in['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> bool;
+
+        /// Whether the member can take an action in a channel, without handling
+        /// errors.
+        ///
+        /// Refer to [`Calculator::can_perform`] for more information.
+        ///
+        /// [`Calculator::can_perform`]: struct.Calculator.html#method.can_perform
+        unwrap fn can_perform['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            action: Action,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+        ) -> bool;
+
+        /// Whether the member can take an action in a channel right now, without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::capability`] for more information.
+        ///
+        /// [`Calculator::capability`]: struct.Calculator.html#method.capability
+        unwrap fn capability['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            action: Action,
+            channel_type: ChannelType,
+            channel_overwrites: U,
+            gates: Gates,
+        ) -> Capability;
+
+        /// Whether the member is a moderator of a stage channel, without handling
+        /// errors.
+        ///
+        /// Refer to [`Calculator::is_stage_moderator`] for more information.
+        ///
+        /// [`Calculator::is_stage_moderator`]: struct.Calculator.html#method.is_stage_moderator
+        unwrap fn is_stage_moderator['b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone](
+            self,
+            channel_overwrites: U,
+        ) -> bool;
+
+        /// Require that the member has a set of permissions in every one of the
+        /// given channels, without handling calculation errors.
+        ///
+        /// Refer to [`Calculator::require_in_all`] for more information.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`RequireError::MissingPermissions`] if the member is missing
+        /// any of the required permissions in any of the channels.
+        /// [`RequireError::Calculating`] is never returned.
+        ///
+        /// [`Calculator::require_in_all`]: struct.Calculator.html#method.require_in_all
+        /// [`RequireError::Calculating`]: require/enum.RequireError.html#variant.Calculating
+        /// [`RequireError::MissingPermissions`]: require/enum.RequireError.html#variant.MissingPermissions
+        forward fn require_in_all[
+            'b,
+            U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        ](
+            &self,
+            channels: U,
+            required: Permissions,
+        ) -> Result<(), RequireError>;
+
+        /// Require that the member has a set of permissions in a channel, without
+        /// handling calculation errors.
+        ///
+        /// Refer to [`Calculator::require_in_channel`] for more information.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`RequireError::MissingInChannel`] if the member is missing
+        /// any of the required permissions in the channel.
+        /// [`RequireError::Calculating`] is never returned.
+        ///
+        /// [`Calculator::require_in_channel`]: struct.Calculator.html#method.require_in_channel
+        /// [`RequireError::Calculating`]: require/enum.RequireError.html#variant.Calculating
+        /// [`RequireError::MissingInChannel`]: require/enum.RequireError.html#variant.MissingInChannel
+        forward fn require_in_channel(
+            &self,
+            channel: (ChannelId, ChannelType, &[PermissionOverwrite]),
+            required: Permissions,
+        ) -> Result<(), RequireError>;
+
+        /// Require that the member has a set of permissions in at least one of
+        /// the given channels, without handling calculation errors.
+        ///
+        /// Refer to [`Calculator::require_in_any`] for more information.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`RequireError::MissingPermissions`] if the member is missing
+        /// any of the required permissions in every channel.
+        /// [`RequireError::Calculating`] is never returned.
+        ///
+        /// [`Calculator::require_in_any`]: struct.Calculator.html#method.require_in_any
+        /// [`RequireError::Calculating`]: require/enum.RequireError.html#variant.Calculating
+        /// [`RequireError::MissingPermissions`]: require/enum.RequireError.html#variant.MissingPermissions
+        forward fn require_in_any[
+            'b,
+            U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        ](
+            &self,
+            channels: U,
+            required: Permissions,
+        ) -> Result<ChannelId, RequireError>;
+
+        /// Audit the member's permissions in channels against the permissions
+        /// required for each channel's purpose, without handling errors.
+        ///
+        /// Refer to [`Calculator::self_audit`] for more information.
+        ///
+        /// [`Calculator::self_audit`]: struct.Calculator.html#method.self_audit
+        unwrap fn self_audit[
+            'b,
+            P: PartialEq,
+            U: IntoIterator<Item = (P, ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        ](
+            &self,
+            requirements: &[(P, Permissions)],
+            channels: U,
+        ) -> SelfAuditReport<P>;
+
+        /// Count the channels in which the member has each permission, without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::summarize`] for more information.
+        ///
+        /// [`Calculator::summarize`]: struct.Calculator.html#method.summarize
+        unwrap fn summarize[
+            'b,
+            U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+        ](
+            &self,
+            channels: U,
+        ) -> PermissionSummary;
+
+        /// Verify that the member's permissions in a channel don't depend on the
+        /// order of the member's roles or of the channel's overwrites, without
+        /// handling errors.
+        ///
+        /// Refer to [`Calculator::verify_order_independence`] for more
+        /// information.
+        ///
+        /// [`Calculator::verify_order_independence`]: struct.Calculator.html#method.verify_order_independence
+        unwrap fn verify_order_independence(
+            &self,
+            channel_type: ChannelType,
+            channel_overwrites: &[PermissionOverwrite],
+        ) -> bool;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        action::Action,
+        capability::{Capability, Gates, Restriction},
+        dependency::Dependencies,
+        Calculator, CalculatorError, CascadeMode, DataVersion, EmojiSource, GuildId, IdKind,
+        InfallibleCalculator, Masked, RoleId, SendCapability, UserId, Versioned, DISCORD_EPOCH,
+    };
+    #[cfg(feature = "explain")]
+    use super::{denial::Culprit, Source, Step};
+    use proptest::{collection::vec, prelude::*};
+    use static_assertions::{assert_fields, assert_impl_all, assert_obj_safe};
+    use std::{
+        collections::HashSet,
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            CategoryChannel, ChannelType, GuildChannel,
+        },
+        guild::{Permissions, Role, VerificationLevel},
+        id::ChannelId,
+    };
+
+    assert_impl_all!(
+        CascadeMode: Clone,
+        Copy,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_fields!(CalculatorError::EveryoneRoleMissing: guild_id);
+    assert_fields!(CalculatorError::InvalidId: id, kind);
+    assert_fields!(CalculatorError::RoleMissing: role_id);
+    assert_impl_all!(
+        CalculatorError: Clone,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(Calculator<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(IdKind: Clone, Copy, Debug, Display, Eq, Hash, PartialEq, Send, Sync);
+    assert_obj_safe!(CalculatorError, Calculator<'_>);
+    assert_impl_all!(InfallibleCalculator<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_error_display() {
+        assert_eq!(
+            "@everyone role is missing for guild 123",
+            CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(123)
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "role id 0 is not a valid snowflake",
+            CalculatorError::InvalidId {
+                id: 0,
+                kind: IdKind::Role
+            }
+            .to_string(),
+        );
+        assert_eq!(
+            "role 2 is missing",
+            CalculatorError::RoleMissing { role_id: RoleId(2) }.to_string(),
+        );
+    }
+
+    #[test]
+    fn test_role() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::KICK_MEMBERS),
+            (
+                RoleId(4),
+                Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            ),
+        ];
+        let calculator = Calculator::new(GuildId(1), UserId(3), &guild_roles[..2]);
+
+        assert_eq!(
+            Permissions::KICK_MEMBERS | Permissions::VIEW_CHANNEL,
+            calculator.role(guild_roles, RoleId(2)).unwrap().root()
+        );
+        // Roles that the member doesn't have are found in the guild's roles.
+        assert_eq!(
+            Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            calculator.role(guild_roles, RoleId(4)).unwrap().root()
+        );
+        assert_eq!(
+            Err(CalculatorError::RoleMissing { role_id: RoleId(5) }),
+            calculator.role(guild_roles, RoleId(5))
+        );
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            calculator.role(&guild_roles[1..], RoleId(2))
+        );
+    }
+
+    #[test]
+    fn test_role_configured_like_calculator() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (
+                RoleId(2),
+                Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            ),
+            (RoleId(3), Permissions::ADMINISTRATOR),
+        ];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        }];
+
+        // The role is calculated with the same dependencies as the member.
+        let calculator = Calculator::new(GuildId(1), UserId(4), &guild_roles[..2])
+            .cascade_mode(CascadeMode::DiscordParity);
+        assert_eq!(
+            Permissions::EMBED_LINKS | Permissions::VIEW_CHANNEL,
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites)
+                .unwrap(),
+        );
+        assert_eq!(
+            Permissions::EMBED_LINKS | Permissions::VIEW_CHANNEL,
+            calculator
+                .role(guild_roles, RoleId(2))
+                .unwrap()
+                .in_channel(ChannelType::GuildText, overwrites),
+        );
+        assert_eq!(
+            Permissions::VIEW_CHANNEL,
+            Calculator::new(GuildId(1), UserId(4), &guild_roles[..2])
+                .role(guild_roles, RoleId(2))
+                .unwrap()
+                .in_channel(ChannelType::GuildText, overwrites),
+        );
+
+        let role = Calculator::new(GuildId(1), UserId(4), &guild_roles[..1])
+            .administrator_bypass(false)
+            .role(guild_roles, RoleId(3))
+            .unwrap();
+        assert_eq!(
+            Permissions::VIEW_CHANNEL,
+            role.in_channel(ChannelType::GuildText, &[]),
+        );
+
+        let role = Calculator::new(GuildId(1), UserId(4), &guild_roles[..1])
+            .retain_guild_permissions(true)
+            .role(&[(RoleId(1), Permissions::KICK_MEMBERS)], RoleId(1))
+            .unwrap();
+        assert_eq!(
+            Permissions::KICK_MEMBERS,
+            role.in_channel(ChannelType::GuildText, &[]),
+        );
+
+        // The member's timeout isn't a property of the role.
+        let role = Calculator::new(GuildId(1), UserId(4), &guild_roles[..2])
+            .communication_disabled_until(SystemTime::now() + Duration::from_secs(60))
+            .role(guild_roles, RoleId(2))
+            .unwrap();
+        assert_eq!(
+            Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            role.root(),
+        );
+        assert_eq!(
+            Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            role.in_channel(ChannelType::GuildText, &[]),
+        );
+    }
+
+    #[test]
+    fn test_validate_ids() {
+        // Real snowflakes from the Discord API documentation.
+        let guild_id = GuildId(197_038_439_483_310_086);
+        let user_id = UserId(80_351_110_224_678_912);
+        let member_roles = &[(RoleId(guild_id.0), Permissions::SEND_MESSAGES)];
+
+        let calculator = Calculator::new(guild_id, user_id, member_roles).validate_ids(true);
+        assert_eq!(Ok(Permissions::SEND_MESSAGES), calculator.root());
+
+        // Placeholders aren't validated by default.
+        let calculator = Calculator::new(guild_id, UserId(0), member_roles);
+        assert!(calculator.clone().root().is_ok());
+        assert_eq!(
+            Err(CalculatorError::InvalidId {
+                id: 0,
+                kind: IdKind::User
+            }),
+            calculator.validate_ids(true).root(),
+        );
+
+        let calculator = Calculator::new(guild_id, user_id, member_roles)
+            .owner_id(UserId(2))
+            .validate_ids(true);
+        assert_eq!(
+            Err(CalculatorError::InvalidId {
+                id: 2,
+                kind: IdKind::Owner
+            }),
+            calculator.root(),
+        );
+
+        // The timestamp of the maximum ID is far in the future.
+        let member_roles = &[(RoleId(u64::MAX), Permissions::empty())];
+        let calculator = Calculator::new(guild_id, user_id, member_roles).validate_ids(true);
+        assert_eq!(
+            Err(CalculatorError::InvalidId {
+                id: u64::MAX,
+                kind: IdKind::Role
+            }),
+            calculator.in_channel(ChannelType::GuildText, &[]),
+        );
+    }
+
+    /// Test that IDs created moments ago are valid even if the system clock
+    /// is slightly behind Discord's.
+    #[test]
+    fn test_plausible_snowflake_clock_skew() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system clock is after the unix epoch")
+            .as_millis() as u64;
+        let snowflake = |timestamp: u64| (timestamp - DISCORD_EPOCH) << 22;
+
+        assert!(super::is_plausible_snowflake(snowflake(now)));
+        assert!(super::is_plausible_snowflake(snowflake(now + 60 * 1000)));
+        assert!(!super::is_plausible_snowflake(snowflake(
+            now + 60 * 60 * 1000
+        )));
+    }
+
+    /// Test that calculators with different policies are distinct cache
+    /// keys.
+    #[test]
+    fn test_hash_includes_policies() {
+        let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES)];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        let mut lossy = calculator.clone();
+        lossy.continue_on_missing_items = true;
+
+        let keys = vec![
+            calculator.clone(),
+            calculator.clone().owner_id(UserId(2)),
+            calculator.clone().validate_ids(true),
+            calculator.clone().administrator_bypass(false),
+            calculator.clone().data_version(DataVersion::new(1)),
+            calculator
+                .clone()
+                .communication_disabled_until(SystemTime::UNIX_EPOCH),
+            lossy,
+        ];
+
+        assert_eq!(7, keys.into_iter().collect::<HashSet<_>>().len());
+    }
+
+    #[test]
+    fn test_timed_out() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::READ_MESSAGE_HISTORY
+                | Permissions::SEND_MESSAGES
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let until = SystemTime::now() + Duration::from_secs(3600);
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .communication_disabled_until(until);
+
+        assert_eq!(
+            Ok(Permissions::READ_MESSAGE_HISTORY | Permissions::VIEW_CHANNEL),
+            calculator.root(),
+        );
+        assert_eq!(
+            Ok(Permissions::READ_MESSAGE_HISTORY | Permissions::VIEW_CHANNEL),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites),
+        );
+        assert_eq!(
+            Ok(Permissions::all()),
+            calculator.clone().owner_id(UserId(2)).root()
+        );
+
+        // Expired timeouts don't affect permissions.
+        let expired = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .communication_disabled_until(SystemTime::UNIX_EPOCH);
+        assert!(expired
+            .in_channel(ChannelType::GuildText, overwrites)
+            .unwrap()
+            .contains(Permissions::ADD_REACTIONS | Permissions::SEND_MESSAGES));
+    }
+
+    #[test]
+    fn test_capability() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::READ_MESSAGE_HISTORY
+                | Permissions::SEND_MESSAGES
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let gates = Gates::new().unverified(VerificationLevel::Low);
+        let until = SystemTime::now() + Duration::from_secs(3600);
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        // Timed out members are told that they're timed out rather than that
+        // they're missing permissions.
+        assert_eq!(
+            Ok(Capability::No(Restriction::TimedOut)),
+            calculator
+                .clone()
+                .communication_disabled_until(until)
+                .capability(Action::SendMessage, ChannelType::GuildText, &[], gates),
+        );
+        assert_eq!(
+            Ok(Capability::No(Restriction::VerificationLevel {
+                level: VerificationLevel::Low
+            })),
+            calculator
+                .clone()
+                .capability(Action::SendMessage, ChannelType::GuildText, &[], gates),
+        );
+        assert_eq!(
+            Ok(Capability::Yes),
+            calculator.clone().owner_id(UserId(2)).capability(
+                Action::SendMessage,
+                ChannelType::GuildText,
+                &[],
+                gates
+            ),
+        );
+
+        // Members with roles aren't subject to the verification level.
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(3), Permissions::SEND_MESSAGES),
+        ];
+        assert_eq!(
+            Ok(Capability::Yes),
+            Calculator::new(GuildId(1), UserId(2), member_roles).capability(
+                Action::SendMessage,
+                ChannelType::GuildText,
+                &[],
+                gates
+            ),
+        );
+    }
+
+    /// Test that a role granting the administrator privilege grants all
+    /// permissions no matter where it is in the member's roles.
+    #[test]
+    fn test_administrator_role_order() {
+        let member_roles = &[
+            (RoleId(1), Permissions::SEND_MESSAGES),
+            (RoleId(3), Permissions::ADMINISTRATOR),
+        ];
+
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        assert_eq!(Permissions::all(), calculator.root().unwrap());
+        assert!(calculator
+            .verify_order_independence(ChannelType::GuildText, &[])
+            .unwrap());
+    }
+
+    /// Test that multiple overwrites on the `@everyone` role are combined
+    /// rather than applied in order.
+    #[test]
+    fn test_everyone_overwrites_order() {
+        let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+        let mut overwrites = vec![
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+        ];
+
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        let expected = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+        assert_eq!(
+            expected,
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, &overwrites)
+                .unwrap(),
+        );
+
+        overwrites.reverse();
+        assert_eq!(
+            expected,
+            calculator
+                .in_channel(ChannelType::GuildText, &overwrites)
+                .unwrap(),
+        );
+    }
+
+    fn role_id() -> impl Strategy<Value = RoleId> {
+        (1..6_u64).prop_map(RoleId)
+    }
+
+    fn permissions() -> impl Strategy<Value = Permissions> {
+        any::<u64>().prop_map(Permissions::from_bits_truncate)
+    }
+
+    fn overwrite() -> impl Strategy<Value = PermissionOverwrite> {
+        let kind = prop_oneof![
+            role_id().prop_map(PermissionOverwriteType::Role),
+            (1..4_u64).prop_map(|id| PermissionOverwriteType::Member(UserId(id))),
+        ];
+
+        (permissions(), permissions(), kind).prop_map(|(allow, deny, kind)| PermissionOverwrite {
+            allow,
+            deny,
+            kind,
+        })
+    }
+
+    proptest! {
+        /// Test that permissions don't depend on the order of roles or
+        /// overwrites, including duplicate roles and overwrites.
+        #[test]
+        fn test_order_independence(
+            everyone in permissions(),
+            roles in vec((role_id(), permissions()), 0..5),
+            overwrites in vec(overwrite(), 0..6),
+        ) {
+            let mut member_roles = vec![(RoleId(1), everyone)];
+            member_roles.extend(roles);
+
+            let calculator = Calculator::new(GuildId(1), UserId(2), &member_roles);
+
+            for channel_type in &[ChannelType::GuildText, ChannelType::GuildVoice] {
+                prop_assert!(calculator
+                    .verify_order_independence(*channel_type, &overwrites)
+                    .unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_in_channel_masked() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::BAN_MEMBERS
+                | Permissions::SEND_MESSAGES
+                | Permissions::SPEAK
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::CONNECT,
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+
+        let masked = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .in_channel_masked(ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert_eq!(Permissions::VIEW_CHANNEL, masked.permissions);
+        // Denied permissions aren't stripped.
+        assert_eq!(
+            Permissions::BAN_MEMBERS | Permissions::CONNECT | Permissions::SPEAK,
+            masked.stripped,
+        );
+        assert_eq!(masked.permissions | masked.stripped, masked.raw);
+        assert!(masked.is_stripped(Permissions::CONNECT));
+        assert!(!masked.is_stripped(Permissions::SEND_MESSAGES));
+
+        // Permissions removed because of a dependency are kept in the raw
+        // permissions, like Discord does.
+        let embed_links = &[(
+            RoleId(1),
+            Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+        )];
+        let masked = Calculator::new(GuildId(1), UserId(2), embed_links)
+            .in_channel_masked(ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert_eq!(Permissions::VIEW_CHANNEL, masked.permissions);
+        assert_eq!(Permissions::CONNECT, masked.stripped);
+        assert_eq!(
+            Permissions::CONNECT | Permissions::EMBED_LINKS | Permissions::VIEW_CHANNEL,
+            masked.raw,
+        );
+
+        // Timeouts apply to the raw permissions too.
+        let masked = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .communication_disabled_until(SystemTime::now() + Duration::from_secs(60))
+            .in_channel_masked(ChannelType::GuildText, &[])
+            .unwrap();
+        assert_eq!(Permissions::VIEW_CHANNEL, masked.permissions);
+        assert_eq!(Permissions::VIEW_CHANNEL, masked.raw);
+
+        let administrator = &[(RoleId(1), Permissions::ADMINISTRATOR)];
+        let masked = Calculator::new(GuildId(1), UserId(2), administrator)
+            .in_channel_masked(ChannelType::GuildText, &[])
+            .unwrap();
+        assert_eq!(Permissions::all(), masked.permissions);
+        assert_eq!(Permissions::all(), masked.raw);
+        assert!(masked.stripped.is_empty());
+    }
+
+    #[test]
+    fn test_cascade_mode() {
+        let member_roles = &[
+            (
+                RoleId(1),
+                Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            ),
+            (RoleId(3), Permissions::empty()),
+        ];
+        let muted = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(3)),
+        }];
+        let hidden = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        let parity = calculator.clone().cascade_mode(CascadeMode::DiscordParity);
+
+        assert_eq!(
+            Ok(Permissions::VIEW_CHANNEL),
+            calculator.clone().in_channel(ChannelType::GuildText, muted),
+        );
+        assert_eq!(
+            Ok(Permissions::EMBED_LINKS | Permissions::VIEW_CHANNEL),
+            parity.clone().in_channel(ChannelType::GuildText, muted),
+        );
+
+        assert_eq!(
+            Ok(Permissions::empty()),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, hidden),
+        );
+        assert_eq!(
+            Ok(Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES),
+            parity.clone().in_channel(ChannelType::GuildText, hidden),
+        );
+
+        // Cascade modes are presets of dependencies.
+        assert_eq!(
+            calculator.clone().dependencies(Dependencies::NONE),
+            parity.clone(),
+        );
+        assert_eq!(calculator, parity.cascade_mode(CascadeMode::Practical));
+        assert_eq!(
+            Dependencies::default(),
+            CascadeMode::default().dependencies()
+        );
+    }
+
+    /// Test that dependencies apply to the `@everyone` role's overwrite, and
+    /// that they don't apply when an overwrite applied later allows the
+    /// permission again.
+    #[test]
+    fn test_dependencies_everyone_overwrite() {
+        let member_roles = &[
+            (
+                RoleId(1),
+                Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            ),
+            (RoleId(3), Permissions::empty()),
+        ];
+        let everyone = |deny| PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        };
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        assert_eq!(
+            Ok(Permissions::VIEW_CHANNEL),
+            calculator.clone().in_channel(
+                ChannelType::GuildText,
+                &[everyone(Permissions::SEND_MESSAGES)]
+            ),
+        );
+        assert_eq!(
+            Ok(Permissions::empty()),
+            calculator.clone().in_channel(
+                ChannelType::GuildText,
+                &[everyone(Permissions::VIEW_CHANNEL)]
+            ),
+        );
+
+        let overwrites = &[
+            everyone(Permissions::VIEW_CHANNEL),
+            PermissionOverwrite {
+                allow: Permissions::VIEW_CHANNEL,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+        assert_eq!(
+            Ok(Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites),
+        );
+
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::VIEW_CHANNEL,
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::VIEW_CHANNEL,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(2)),
+            },
+        ];
+        assert_eq!(
+            Ok(Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            calculator.in_channel(ChannelType::GuildText, overwrites),
+        );
+    }
+
+    /// Test that a channel hidden by the `@everyone` role's overwrite is
+    /// reported as hidden with no permissions.
+    #[test]
+    fn test_in_channel_calculated_everyone_hidden() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+
+        let calculated = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .in_channel_calculated(ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert!(calculated.permissions().is_empty());
+        assert!(calculated.is_empty_due_to_hidden_channel());
+        assert!(calculated.visibility().is_hidden());
+    }
+
+    /// Test that a channel is hidden exactly when the calculated permissions
+    /// don't include View Channel, and that every permission is denied when
+    /// an overwrite hides it.
+    #[test]
+    fn test_visibility_matches_cascade() {
+        let member_roles = &[
+            (
+                RoleId(1),
+                Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            ),
+            (RoleId(3), Permissions::empty()),
+        ];
+        let overwrite = |kind, allow, deny| PermissionOverwrite { allow, deny, kind };
+        let everyone = PermissionOverwriteType::Role(RoleId(1));
+        let role = PermissionOverwriteType::Role(RoleId(3));
+        let member = PermissionOverwriteType::Member(UserId(2));
+        let (none, view) = (Permissions::empty(), Permissions::VIEW_CHANNEL);
+        let cases = vec![
+            (vec![], false),
+            (vec![overwrite(everyone, none, view)], true),
+            (vec![overwrite(role, none, view)], true),
+            (vec![overwrite(member, none, view)], true),
+            (
+                vec![overwrite(everyone, none, view), overwrite(role, view, none)],
+                false,
+            ),
+            (
+                vec![
+                    overwrite(everyone, none, view),
+                    overwrite(member, view, none),
+                ],
+                false,
+            ),
+            (
+                vec![overwrite(role, none, view), overwrite(member, view, none)],
+                false,
+            ),
+        ];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        for (overwrites, hidden) in cases {
+            let permissions = calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, &overwrites)
+                .unwrap();
+            let visibility = calculator
+                .clone()
+                .in_channel_visibility(ChannelType::GuildText, &overwrites)
+                .unwrap();
+
+            assert_eq!(hidden, visibility.is_hidden());
+            assert_eq!(hidden, permissions.is_empty());
+            assert_eq!(visibility.permissions(), permissions);
+        }
+    }
+
+    #[test]
+    fn test_dependencies() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::CONNECT | Permissions::SPEAK | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::CONNECT | Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        assert_eq!(
+            Ok(Permissions::empty()),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildVoice, overwrites),
+        );
+        assert_eq!(
+            Ok(Permissions::SPEAK),
+            calculator
+                .clone()
+                .dependencies(Dependencies::NONE)
+                .in_channel(ChannelType::GuildVoice, overwrites),
+        );
+
+        let connect = Dependencies::NONE.add(Permissions::CONNECT, Permissions::SPEAK);
+        let calculator = calculator.dependencies(connect);
+        assert_eq!(
+            Ok(Permissions::empty()),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildVoice, overwrites),
+        );
+
+        #[cfg(feature = "explain")]
+        {
+            let denial = calculator
+                .why_denied(ChannelType::GuildVoice, overwrites, Permissions::SPEAK)
+                .unwrap()
+                .unwrap();
+            assert_eq!(Culprit::MemberOverwrite, denial.culprit);
+            assert_eq!(Permissions::CONNECT, denial.denied);
+        }
+    }
+
+    #[test]
+    fn test_retain_guild_permissions() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::CONNECT
+                | Permissions::SEND_MESSAGES
+                | Permissions::VIEW_AUDIT_LOG
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_AUDIT_LOG,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        assert_eq!(
+            Ok(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            calculator.clone().in_channel(ChannelType::GuildText, &[]),
+        );
+
+        let retaining = calculator.retain_guild_permissions(true);
+        assert_eq!(
+            Ok(Permissions::SEND_MESSAGES
+                | Permissions::VIEW_AUDIT_LOG
+                | Permissions::VIEW_CHANNEL),
+            retaining.clone().in_channel(ChannelType::GuildText, &[]),
+        );
+        // Overwrites still apply to guild-level permissions.
+        assert_eq!(
+            Ok(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            retaining
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites),
+        );
+
+        let masked = retaining
+            .in_channel_masked(ChannelType::GuildText, &[])
+            .unwrap();
+        assert_eq!(Permissions::CONNECT, masked.stripped);
+        assert_eq!(masked.permissions | Permissions::CONNECT, masked.raw);
+    }
+
+    #[test]
+    fn test_in_channel_versioned() {
+        let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES)];
+        let version = DataVersion::new(3);
+
+        let versioned = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .data_version(version)
+            .in_channel_versioned(ChannelType::GuildText, &[])
+            .unwrap();
+
+        assert_eq!(
+            Versioned::new(Permissions::SEND_MESSAGES, version),
+            versioned
+        );
+        assert!(versioned.is_stale(version.next()));
+    }
+
+    #[test]
+    fn test_can_react() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::ADD_REACTIONS
+                | Permissions::READ_MESSAGE_HISTORY
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        assert!(calculator
+            .clone()
+            .can_react(ChannelType::GuildText, &[], EmojiSource::Guild, false)
+            .unwrap());
+        assert!(!calculator
+            .clone()
+            .can_react(ChannelType::GuildText, &[], EmojiSource::External, true)
+            .unwrap());
+        // Reactions are text permissions, which don't apply to voice channels.
+        assert!(!calculator
+            .can_react(ChannelType::GuildVoice, &[], EmojiSource::Unicode, true)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_in_channel_with_parent() {
+        let member_roles = &[
+            (
+                RoleId(1),
+                Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            ),
+            (RoleId(3), Permissions::empty()),
+        ];
+        let category = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(2)),
+            },
+        ];
+        // Syncing replaces the channel's own overwrites, so the channel's
+        // allow doesn't survive the category's deny.
+        let channel = &[PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(RoleId(3)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        assert_eq!(
+            Ok(Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, channel),
+        );
+        assert_eq!(
+            Ok(Permissions::ADD_REACTIONS | Permissions::VIEW_CHANNEL),
+            calculator
+                .clone()
+                .in_channel_with_parent(ChannelType::GuildText, category),
+        );
+        assert_eq!(
+            calculator.clone().in_channel(ChannelType::GuildText, &[]),
+            calculator.in_channel_with_parent(ChannelType::GuildText, &[]),
+        );
+    }
+
+    #[cfg(feature = "explain")]
+    #[test]
+    fn test_explain() {
+        let member_roles = &[(RoleId(1), Permissions::ADMINISTRATOR)];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        let explanation = calculator
+            .clone()
+            .explain(ChannelType::GuildText, &[])
+            .unwrap();
+        assert_eq!(Permissions::all(), explanation.permissions);
+        assert_eq!(
+            Some(Source::Administrator),
+            explanation.source(Permissions::KICK_MEMBERS)
+        );
+
+        let explanation = calculator
+            .clone()
+            .owner_id(UserId(2))
+            .explain(ChannelType::GuildText, &[])
+            .unwrap();
+        assert_eq!(
+            Some(Source::Owner),
+            explanation.source(Permissions::KICK_MEMBERS)
+        );
+
+        // Without the bypass, permissions come from the roles themselves.
+        let member_roles = &[
+            (RoleId(1), Permissions::SEND_MESSAGES),
+            (RoleId(3), Permissions::ADMINISTRATOR),
+        ];
+        let explanation = Calculator::new(GuildId(1), UserId(2), member_roles)
+            .administrator_bypass(false)
+            .explain(ChannelType::GuildText, &[])
+            .unwrap();
+        assert_eq!(Permissions::SEND_MESSAGES, explanation.permissions);
+        assert_eq!(
+            Some(Source::Everyone),
+            explanation.source(Permissions::SEND_MESSAGES)
+        );
+        assert!(explanation.source(Permissions::KICK_MEMBERS).is_none());
+    }
+
+    #[test]
+    fn test_in_channels() {
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(3), Permissions::CONNECT | Permissions::SEND_MESSAGES),
+        ];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let channels = vec![
+            (ChannelId(4), ChannelType::GuildText, &[][..]),
+            (ChannelId(5), ChannelType::GuildVoice, &[][..]),
+            (ChannelId(6), ChannelType::GuildText, &overwrites[..]),
+        ];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
 
-        Self(inner)
+        let permissions = calculator.in_channels(channels.clone()).unwrap();
+        assert_eq!(3, permissions.len());
+
+        for (channel_id, channel_type, channel_overwrites) in channels {
+            assert_eq!(
+                calculator
+                    .clone()
+                    .in_channel(channel_type, channel_overwrites)
+                    .unwrap(),
+                permissions[&channel_id],
+            );
+        }
+
+        assert!(matches!(
+            Calculator::new(GuildId(1), UserId(2), &[]).in_channels(Vec::new()),
+            Err(CalculatorError::EveryoneRoleMissing { .. })
+        ));
     }
 
-    /// Configure the ID of the owner of the guild.
-    ///
-    /// Refer to the documentation for [`Calculator::owner_id`].
-    ///
-    /// [`Calculator::owner_id`]: struct.Calculator.html#method.owner_id
-    pub fn owner_id(mut self, owner_id: UserId) -> Self {
-        self.0 = self.0.owner_id(owner_id);
+    #[cfg(feature = "explain")]
+    #[test]
+    fn test_trace() {
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (
+                RoleId(3),
+                Permissions::CONNECT | Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES,
+            ),
+        ];
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::ADD_REACTIONS,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
 
-        self
+        let trace = calculator
+            .clone()
+            .trace(ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert_eq!(
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites)
+                .unwrap(),
+            trace.permissions,
+        );
+        assert_eq!(
+            vec![
+                Step::Everyone,
+                Step::Roles,
+                Step::EveryoneOverwrite,
+                Step::SendMessagesDenied,
+                Step::RoleOverwrites,
+                Step::MemberOverwrite,
+                Step::GuildPermissions,
+                Step::ChannelType {
+                    kind: ChannelType::GuildText
+                },
+            ],
+            trace.steps.iter().map(|step| step.step).collect::<Vec<_>>(),
+        );
+
+        // Each step starts where the previous one left off.
+        for pair in trace.steps.windows(2) {
+            assert_eq!(pair[0].after, pair[1].before);
+        }
+
+        assert_eq!(Permissions::ADD_REACTIONS, trace.steps[2].granted());
+        assert_eq!(Permissions::KICK_MEMBERS, trace.steps[6].removed());
+        assert_eq!(Permissions::CONNECT, trace.steps[7].removed());
+
+        let trace = calculator
+            .owner_id(UserId(2))
+            .trace(ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert_eq!(Step::Owner, trace.steps[0].step);
+        assert_eq!(Permissions::all(), trace.permissions);
     }
 
-    /// Calculate the guild-level permissions of a member without handling
-    /// errors.
-    ///
-    /// Refer to [`Calculator::root`] for more information.
-    ///
-    /// [`Calculator::root`]: struct.Calculator.html#method.root
-    pub fn root(&self) -> Permissions {
-        self.0
-            .root()
-            .expect("inner fallible calculator is configured to ignore errors")
+    #[cfg(feature = "explain")]
+    #[test]
+    fn test_why_denied() {
+        let member_roles = &[
+            (
+                RoleId(1),
+                Permissions::ADD_REACTIONS
+                    | Permissions::ATTACH_FILES
+                    | Permissions::CONNECT
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::VIEW_CHANNEL,
+            ),
+            (RoleId(3), Permissions::empty()),
+        ];
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::ADD_REACTIONS,
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Member(UserId(2)),
+            },
+        ];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        let why = |kind, overwrites: &[PermissionOverwrite], permission| {
+            calculator
+                .clone()
+                .why_denied(kind, overwrites, permission)
+                .unwrap()
+                .map(|denial| (denial.culprit, denial.denied))
+        };
+
+        assert!(why(ChannelType::GuildText, &[], Permissions::SEND_MESSAGES).is_none());
+        assert_eq!(
+            Some((Culprit::EveryoneOverwrite, Permissions::ADD_REACTIONS)),
+            why(
+                ChannelType::GuildText,
+                overwrites,
+                Permissions::ADD_REACTIONS
+            ),
+        );
+        assert_eq!(
+            Some((Culprit::MemberOverwrite, Permissions::SEND_MESSAGES)),
+            why(
+                ChannelType::GuildText,
+                overwrites,
+                Permissions::ATTACH_FILES
+            ),
+        );
+        assert_eq!(
+            Some((
+                Culprit::ChannelType {
+                    kind: ChannelType::GuildText
+                },
+                Permissions::CONNECT
+            )),
+            why(ChannelType::GuildText, &[], Permissions::CONNECT),
+        );
+        assert_eq!(
+            Some((Culprit::GuildPermission, Permissions::KICK_MEMBERS)),
+            why(ChannelType::GuildText, &[], Permissions::KICK_MEMBERS),
+        );
+        assert_eq!(
+            Some((Culprit::NotGranted, Permissions::EMBED_LINKS)),
+            why(ChannelType::GuildText, &[], Permissions::EMBED_LINKS),
+        );
+
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(3)),
+        }];
+        assert_eq!(
+            Some((
+                Culprit::RoleOverwrite { role_id: RoleId(3) },
+                Permissions::VIEW_CHANNEL
+            )),
+            why(
+                ChannelType::GuildText,
+                overwrites,
+                Permissions::SEND_MESSAGES
+            ),
+        );
     }
 
-    /// Calculate the permissions of the member in a channel without handling
-    /// errors, taking into account a combination of the guild-level permissions
-    /// and channel-level permissions.
-    ///
-    /// Refer to [`Calculator::in_channel`] for more information.
-    ///
-    /// [`Calculator::in_channel`]: struct.Calculator.html#method.root
-    pub fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
-        self,
-        channel_type: ChannelType,
-        channel_overwrites: U,
-    ) -> Permissions {
-        self.0
-            .in_channel(channel_type, channel_overwrites)
-            .expect("inner fallible calculator is configured to ignore errors")
+    #[cfg(feature = "explain")]
+    #[test]
+    fn test_report() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        let report = calculator
+            .clone()
+            .report(ChannelType::GuildText, overwrites)
+            .unwrap();
+        assert_eq!(
+            Permissions::ADD_REACTIONS | Permissions::VIEW_CHANNEL,
+            report.permissions
+        );
+        assert_eq!(
+            vec![
+                (Permissions::ADD_REACTIONS, Source::MemberOverwrite),
+                (Permissions::VIEW_CHANNEL, Source::Everyone),
+            ],
+            report.granted,
+        );
+        // Guild-level and voice permissions aren't reported as denied.
+        assert!(report.denied.iter().all(|denial| !matches!(
+            denial.culprit,
+            Culprit::ChannelType { .. } | Culprit::GuildPermission
+        )));
+        assert!(report.denied.iter().any(|denial| {
+            denial.permission == Permissions::SEND_MESSAGES
+                && denial.culprit == Culprit::MemberOverwrite
+        }));
+
+        let report = calculator
+            .communication_disabled_until(SystemTime::now() + Duration::from_secs(60))
+            .report_root()
+            .unwrap();
+        assert_eq!(Permissions::VIEW_CHANNEL, report.permissions);
+        let kick = report
+            .denied
+            .iter()
+            .find(|denial| denial.permission == Permissions::KICK_MEMBERS)
+            .unwrap();
+        assert_eq!(Culprit::TimedOut, kick.culprit);
+        let ban = report
+            .denied
+            .iter()
+            .find(|denial| denial.permission == Permissions::BAN_MEMBERS)
+            .unwrap();
+        assert_eq!(Culprit::NotGranted, ban.culprit);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::{Calculator, CalculatorError, GuildId, InfallibleCalculator, RoleId, UserId};
-    use static_assertions::{assert_fields, assert_impl_all, assert_obj_safe};
-    use std::{
-        error::Error,
-        fmt::{Debug, Display},
-    };
-    use twilight_model::{
-        channel::{
-            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
-            ChannelType,
-        },
-        guild::Permissions,
-    };
+    #[test]
+    fn test_can_use_commands_in() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::USE_SLASH_COMMANDS | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::USE_SLASH_COMMANDS,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
 
-    assert_fields!(CalculatorError::EveryoneRoleMissing: guild_id);
-    assert_impl_all!(
-        CalculatorError: Clone,
-        Debug,
-        Display,
-        Error,
-        Eq,
-        PartialEq,
-        Send,
-        Sync
-    );
-    assert_impl_all!(Calculator<'_>: Clone, Debug, Eq, PartialEq, Send, Sync);
-    assert_obj_safe!(CalculatorError, Calculator<'_>);
-    assert_impl_all!(InfallibleCalculator<'_>: Clone, Debug, Eq, PartialEq, Send, Sync);
+        assert_eq!(
+            Ok(true),
+            calculator
+                .clone()
+                .can_use_commands_in(ChannelType::GuildNews, &[]),
+        );
+        assert_eq!(
+            Ok(false),
+            calculator
+                .clone()
+                .can_use_commands_in(ChannelType::GuildText, overwrites),
+        );
+        assert_eq!(
+            Ok(false),
+            calculator.can_use_commands_in(ChannelType::GuildVoice, &[]),
+        );
+    }
 
     #[test]
-    fn test_error_display() {
+    fn test_stage_channel() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::CONNECT
+                | Permissions::MOVE_MEMBERS
+                | Permissions::REQUEST_TO_SPEAK
+                | Permissions::SPEAK
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let moderators = &[PermissionOverwrite {
+            allow: Permissions::MANAGE_CHANNELS | Permissions::MUTE_MEMBERS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        // Audience members can't speak, and can only request to in stages.
         assert_eq!(
-            "@everyone role is missing for guild 123",
-            CalculatorError::EveryoneRoleMissing {
-                guild_id: GuildId(123)
-            }
-            .to_string(),
+            Ok(Permissions::CONNECT
+                | Permissions::MOVE_MEMBERS
+                | Permissions::REQUEST_TO_SPEAK
+                | Permissions::VIEW_CHANNEL),
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildStageVoice, &[]),
+        );
+        assert_eq!(
+            Ok(Permissions::CONNECT
+                | Permissions::MOVE_MEMBERS
+                | Permissions::SPEAK
+                | Permissions::VIEW_CHANNEL),
+            calculator.clone().in_channel(ChannelType::GuildVoice, &[]),
+        );
+        assert!(!calculator.clone().is_stage_moderator(&[]).unwrap());
+        assert!(calculator.is_stage_moderator(moderators).unwrap());
+    }
+
+    #[test]
+    fn test_can_send_in() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::ATTACH_FILES
+                | Permissions::MANAGE_MESSAGES
+                | Permissions::READ_MESSAGE_HISTORY
+                | Permissions::SEND_MESSAGES
+                | Permissions::VIEW_CHANNEL,
+        )];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        assert_eq!(
+            Ok(SendCapability::Yes),
+            calculator
+                .clone()
+                .can_send_in(ChannelType::GuildText, &[], true),
         );
+        assert!(!calculator
+            .can_send_in(ChannelType::GuildText, overwrites, false)
+            .unwrap()
+            .can_send());
     }
 
     #[test]
@@ -614,6 +4931,109 @@ mod tests {
         assert_eq!(Permissions::all(), calculator.root().unwrap());
     }
 
+    /// Test which channel types each known permission is present in.
+    #[test]
+    fn test_channel_type_masks() {
+        use ChannelType::{
+            GuildCategory as C, GuildNews as N, GuildStageVoice as S, GuildStore as St,
+            GuildText as T, GuildVoice as V,
+        };
+
+        const ALL: &[ChannelType] = &[C, N, S, St, T, V];
+        const NONE: &[ChannelType] = &[];
+        const STAGE: &[ChannelType] = &[S];
+        const STAGE_AND_VOICE: &[ChannelType] = &[S, V];
+        const TEXT: &[ChannelType] = &[N, T];
+        const VOICE: &[ChannelType] = &[V];
+
+        let table: &[(Permissions, &[ChannelType])] = &[
+            (Permissions::CREATE_INVITE, ALL),
+            (Permissions::KICK_MEMBERS, NONE),
+            (Permissions::BAN_MEMBERS, NONE),
+            (Permissions::ADMINISTRATOR, NONE),
+            (Permissions::MANAGE_CHANNELS, ALL),
+            (Permissions::MANAGE_GUILD, NONE),
+            (Permissions::ADD_REACTIONS, TEXT),
+            (Permissions::VIEW_AUDIT_LOG, NONE),
+            (Permissions::PRIORITY_SPEAKER, VOICE),
+            (Permissions::STREAM, VOICE),
+            (Permissions::VIEW_CHANNEL, ALL),
+            (Permissions::SEND_MESSAGES, TEXT),
+            (Permissions::SEND_TTS_MESSAGES, TEXT),
+            (Permissions::MANAGE_MESSAGES, TEXT),
+            (Permissions::EMBED_LINKS, TEXT),
+            (Permissions::ATTACH_FILES, TEXT),
+            (Permissions::READ_MESSAGE_HISTORY, TEXT),
+            (Permissions::MENTION_EVERYONE, TEXT),
+            (Permissions::USE_EXTERNAL_EMOJIS, TEXT),
+            (Permissions::VIEW_GUILD_INSIGHTS, NONE),
+            (Permissions::CONNECT, STAGE_AND_VOICE),
+            (Permissions::SPEAK, VOICE),
+            (Permissions::MUTE_MEMBERS, STAGE_AND_VOICE),
+            (Permissions::DEAFEN_MEMBERS, VOICE),
+            (Permissions::MOVE_MEMBERS, STAGE_AND_VOICE),
+            (Permissions::USE_VAD, VOICE),
+            (Permissions::CHANGE_NICKNAME, NONE),
+            (Permissions::MANAGE_NICKNAMES, NONE),
+            (Permissions::MANAGE_ROLES, ALL),
+            (Permissions::MANAGE_WEBHOOKS, ALL),
+            (Permissions::MANAGE_EMOJIS, NONE),
+            (Permissions::USE_SLASH_COMMANDS, TEXT),
+            (Permissions::REQUEST_TO_SPEAK, STAGE),
+        ];
+
+        let classified = table
+            .iter()
+            .fold(Permissions::empty(), |all, (permission, _)| {
+                all | *permission
+            });
+        assert_eq!(Permissions::all(), classified);
+
+        // Every permission other than Administrator, so that channel type
+        // masks are applied.
+        let member_roles = &[(RoleId(1), Permissions::all() - Permissions::ADMINISTRATOR)];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        for channel_type in ALL {
+            let permissions = calculator.clone().in_channel(*channel_type, &[]).unwrap();
+
+            for (permission, channel_types) in table {
+                assert_eq!(
+                    channel_types.contains(channel_type),
+                    permissions.contains(*permission),
+                    "{:?} in {:?}",
+                    permission,
+                    channel_type,
+                );
+            }
+        }
+    }
+
+    /// Test that the owner has every permission in channels, regardless of
+    /// overwrites and the type of the channel.
+    #[test]
+    fn test_owner_in_channel() {
+        let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES)];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles).owner_id(UserId(2));
+
+        for channel_type in &[
+            ChannelType::GuildCategory,
+            ChannelType::GuildStageVoice,
+            ChannelType::GuildText,
+            ChannelType::GuildVoice,
+        ] {
+            assert_eq!(
+                Ok(Permissions::all()),
+                calculator.clone().in_channel(*channel_type, overwrites),
+            );
+        }
+    }
+
     // Test that a permission overwrite denying the "View Channel" permission
     // implicitly denies all other permissions.
     #[test]
@@ -734,6 +5154,220 @@ mod tests {
         assert_eq!(Permissions::SEND_MESSAGES, perms);
     }
 
+    /// Test that a calculator owning the member's roles isn't tied to their
+    /// lifetime and calculates like one borrowing them.
+    #[test]
+    fn test_owned_member_roles() {
+        fn owned() -> Calculator<'static> {
+            let member_roles = vec![
+                (RoleId(1), Permissions::VIEW_CHANNEL),
+                (RoleId(2), Permissions::SEND_MESSAGES),
+            ];
+
+            Calculator::from_owned(GuildId(1), UserId(3), member_roles)
+        }
+
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES),
+        ];
+        let borrowed = Calculator::new(GuildId(1), UserId(3), member_roles);
+
+        assert_eq!(borrowed, owned());
+        assert_eq!(
+            borrowed.in_channel(ChannelType::GuildText, &[]),
+            owned().in_channel(ChannelType::GuildText, &[]),
+        );
+    }
+
+    /// Test that the infallible calculator's mirrors of the fallible
+    /// calculator's methods ignore the missing `@everyone` role.
+    #[test]
+    fn test_infallible_calculator_parity() {
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::READ_MESSAGE_HISTORY
+                | Permissions::SEND_MESSAGES
+                | Permissions::VIEW_CHANNEL,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let channels = vec![
+            (ChannelId(3), ChannelType::GuildText, &overwrites[..]),
+            (ChannelId(4), ChannelType::GuildText, &[][..]),
+        ];
+        let version = DataVersion::new(1);
+        let calc = InfallibleCalculator::new(GuildId(1), UserId(2), &[]).data_version(version);
+
+        assert_eq!(
+            Versioned::new(Permissions::empty(), version),
+            calc.root_versioned()
+        );
+        assert_eq!(
+            version,
+            calc.clone()
+                .in_channel_versioned(ChannelType::GuildText, overwrites)
+                .version,
+        );
+        assert!(calc
+            .clone()
+            .in_channel_calculated(ChannelType::GuildText, overwrites)
+            .can_send_messages());
+        assert!(!calc
+            .clone()
+            .in_channel_visibility(ChannelType::GuildText, overwrites)
+            .is_hidden());
+        assert_eq!(
+            Masked::new(
+                Permissions::READ_MESSAGE_HISTORY
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::VIEW_CHANNEL,
+                Permissions::READ_MESSAGE_HISTORY
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::VIEW_CHANNEL,
+                Permissions::empty(),
+            ),
+            calc.clone()
+                .in_channel_masked(ChannelType::GuildText, overwrites),
+        );
+        assert!(calc.clone().can_react(
+            ChannelType::GuildText,
+            overwrites,
+            EmojiSource::Unicode,
+            true
+        ));
+        assert!(calc
+            .clone()
+            .can_send_in(ChannelType::GuildText, overwrites, false)
+            .can_send());
+        assert!(!calc.clone().is_stage_moderator(overwrites));
+        assert!(!calc
+            .clone()
+            .can_use_commands_in(ChannelType::GuildText, overwrites));
+        assert_eq!(
+            Some(&calc.clone().in_channel(ChannelType::GuildText, overwrites)),
+            calc.in_channels(channels.clone()).get(&ChannelId(3)),
+        );
+        assert_eq!(
+            vec![ChannelId(3)],
+            calc.channels_with(channels.clone(), Permissions::VIEW_CHANNEL),
+        );
+        assert_eq!(
+            calc.clone()
+                .in_channel(ChannelType::GuildCategory, overwrites),
+            calc.clone()
+                .in_guild_channel(&GuildChannel::Category(CategoryChannel {
+                    guild_id: Some(GuildId(1)),
+                    id: ChannelId(3),
+                    kind: ChannelType::GuildCategory,
+                    name: "category".to_owned(),
+                    permission_overwrites: overwrites.to_vec(),
+                    position: 0,
+                })),
+        );
+        #[cfg(feature = "explain")]
+        {
+            assert_eq!(
+                Some(Source::MemberOverwrite),
+                calc.clone()
+                    .explain(ChannelType::GuildText, overwrites)
+                    .source(Permissions::SEND_MESSAGES),
+            );
+            assert!(calc
+                .clone()
+                .why_denied(
+                    ChannelType::GuildText,
+                    overwrites,
+                    Permissions::SEND_MESSAGES
+                )
+                .is_none());
+            assert_eq!(calc.root(), calc.report_root().permissions);
+            assert_eq!(
+                calc.clone().in_channel(ChannelType::GuildText, overwrites),
+                calc.clone()
+                    .report(ChannelType::GuildText, overwrites)
+                    .permissions
+            );
+            assert_eq!(
+                calc.clone().in_channel(ChannelType::GuildText, overwrites),
+                calc.clone()
+                    .trace(ChannelType::GuildText, overwrites)
+                    .permissions
+            );
+        }
+        assert_eq!(
+            Permissions::empty(),
+            calc.clone()
+                .in_channel_with_parent(ChannelType::GuildText, &[])
+        );
+        assert_eq!(
+            1,
+            calc.summarize(channels.clone())
+                .count(Permissions::SEND_MESSAGES)
+        );
+        assert!(calc
+            .require_in_all(channels.clone(), Permissions::SEND_MESSAGES)
+            .is_err());
+        assert!(calc
+            .require_in_channel(channels[0], Permissions::SEND_MESSAGES)
+            .is_ok());
+        assert!(calc
+            .clone()
+            .can_perform(Action::SendMessage, ChannelType::GuildText, overwrites));
+        assert!(!calc
+            .clone()
+            .is_slowmode_exempt(ChannelType::GuildText, overwrites, false));
+        assert!(calc
+            .clone()
+            .can_create_invite(ChannelType::GuildText, overwrites)
+            .is_err());
+        assert!(!calc
+            .clone()
+            .can_join_voice(ChannelType::GuildVoice, &[], None, 0));
+        assert!(calc.role(&[], RoleId(5)).root().is_empty());
+        assert!(calc
+            .clone()
+            .can_stream(ChannelType::GuildVoice, &[], false)
+            .is_err());
+        assert!(!calc.clone().can_mention_role(
+            ChannelType::GuildText,
+            overwrites,
+            &Role {
+                color: 0,
+                hoist: false,
+                id: RoleId(5),
+                managed: false,
+                mentionable: false,
+                name: "role".to_owned(),
+                permissions: Permissions::empty(),
+                position: 1,
+                tags: None,
+            }
+        ));
+        assert!(calc
+            .clone()
+            .capability(
+                Action::SendMessage,
+                ChannelType::GuildText,
+                overwrites,
+                Gates::new()
+            )
+            .is_yes());
+        assert_eq!(
+            ChannelId(3),
+            calc.require_in_any(channels.clone(), Permissions::SEND_MESSAGES)
+                .unwrap(),
+        );
+        let report = calc.self_audit(
+            &[((), Permissions::SEND_MESSAGES)],
+            channels
+                .into_iter()
+                .map(|(id, kind, overwrites)| ((), id, kind, overwrites)),
+        );
+        assert_eq!(1, report.deficiencies.len());
+        assert_eq!(ChannelId(4), report.deficiencies[0].channel_id);
+        assert!(calc.verify_order_independence(ChannelType::GuildText, overwrites));
+    }
+
     /// Test that a member that has a role with the "administrator" permission
     /// has all denying overwrites ignored.
     #[test]
@@ -757,4 +5391,47 @@ mod tests {
         );
         assert!(perms.is_all());
     }
+
+    #[test]
+    fn test_admin_bypass_disabled() {
+        let member_roles = &[
+            (
+                RoleId(1),
+                Permissions::READ_MESSAGE_HISTORY
+                    | Permissions::SEND_MESSAGES
+                    | Permissions::VIEW_CHANNEL,
+            ),
+            (
+                RoleId(3),
+                Permissions::ADD_REACTIONS | Permissions::ADMINISTRATOR,
+            ),
+        ];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let calc = InfallibleCalculator::new(GuildId(1), UserId(2), member_roles)
+            .administrator_bypass(false);
+
+        // Only the permissions granted by the member's roles remain, less
+        // those denied by the channel's overwrites.
+        assert_eq!(
+            Permissions::ADD_REACTIONS
+                | Permissions::READ_MESSAGE_HISTORY
+                | Permissions::VIEW_CHANNEL,
+            calc.clone().in_channel(ChannelType::GuildText, overwrites),
+        );
+        assert!(calc
+            .clone()
+            .administrator_bypass(true)
+            .in_channel(ChannelType::GuildText, overwrites)
+            .is_all());
+
+        // The owner isn't affected.
+        assert!(calc
+            .owner_id(UserId(2))
+            .in_channel(ChannelType::GuildText, overwrites)
+            .is_all());
+    }
 }
@@ -0,0 +1,303 @@
+//! Least recently used cache of calculated permissions.
+//!
+//! Calculating a member's permissions in a channel is cheap, but bots that
+//! check the same members in the same channels on every command can skip it
+//! entirely. [`LruCache`] stores calculated permissions keyed by guild,
+//! member, and channel, evicting the least recently used entry once it's
+//! full.
+//!
+//! The cache doesn't know when permissions change. Callers wire its
+//! invalidation methods to their event handling: a member's entries are
+//! invalidated when their roles change, a channel's when its overwrites
+//! change, and a guild's when its roles or owner change.
+//!
+//! [`LruCache`]: struct.LruCache.html
+
+use std::collections::{BTreeMap, HashMap};
+use twilight_model::{
+    guild::Permissions,
+    id::{ChannelId, GuildId, UserId},
+};
+
+/// Key of an entry: the guild, member, and channel.
+type Key = (GuildId, UserId, ChannelId);
+
+/// Least recently used cache of calculated permissions of members in
+/// channels.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::ChannelType,
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::{lru::LruCache, Calculator};
+///
+/// let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+/// let mut cache = LruCache::new(1000);
+///
+/// let permissions = cache.get_or_try_insert_with(GuildId(1), UserId(2), ChannelId(3), || {
+///     Calculator::new(GuildId(1), UserId(2), member_roles)
+///         .in_channel(ChannelType::GuildText, &[])
+/// })?;
+/// assert_eq!(Permissions::VIEW_CHANNEL, permissions);
+/// assert_eq!(
+///     Some(Permissions::VIEW_CHANNEL),
+///     cache.get(GuildId(1), UserId(2), ChannelId(3)),
+/// );
+///
+/// // The member's roles were updated.
+/// cache.invalidate_member(GuildId(1), UserId(2));
+/// assert!(cache.get(GuildId(1), UserId(2), ChannelId(3)).is_none());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LruCache {
+    capacity: usize,
+    entries: HashMap<Key, (Permissions, u64)>,
+    recency: BTreeMap<u64, Key>,
+    tick: u64,
+}
+
+impl LruCache {
+    /// Create a cache holding up to a number of entries.
+    ///
+    /// A cache with a capacity of zero stores nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Maximum number of entries in the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the cached permissions of a member in a channel, marking them as
+    /// the most recently used.
+    pub fn get(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+    ) -> Option<Permissions> {
+        let key = (guild_id, user_id, channel_id);
+        let tick = self.next_tick();
+        let (permissions, used) = self.entries.get_mut(&key)?;
+
+        self.recency.remove(used);
+        self.recency.insert(tick, key);
+        *used = tick;
+
+        Some(*permissions)
+    }
+
+    /// Insert the permissions of a member in a channel, evicting the least
+    /// recently used entry if the cache is full.
+    pub fn insert(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+        permissions: Permissions,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let key = (guild_id, user_id, channel_id);
+        let tick = self.next_tick();
+
+        if let Some((_, used)) = self.entries.insert(key, (permissions, tick)) {
+            self.recency.remove(&used);
+        } else if self.entries.len() > self.capacity {
+            if let Some((&oldest, _)) = self.recency.iter().next() {
+                if let Some(evicted) = self.recency.remove(&oldest) {
+                    self.entries.remove(&evicted);
+                }
+            }
+        }
+
+        self.recency.insert(tick, key);
+    }
+
+    /// Get the cached permissions of a member in a channel, or calculate and
+    /// insert them if they aren't cached.
+    pub fn get_or_insert_with<F: FnOnce() -> Permissions>(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+        f: F,
+    ) -> Permissions {
+        if let Some(permissions) = self.get(guild_id, user_id, channel_id) {
+            return permissions;
+        }
+
+        let permissions = f();
+        self.insert(guild_id, user_id, channel_id, permissions);
+
+        permissions
+    }
+
+    /// Get the cached permissions of a member in a channel, or calculate and
+    /// insert them if they aren't cached and the calculation succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the calculation, in which case nothing is
+    /// inserted.
+    pub fn get_or_try_insert_with<E, F: FnOnce() -> Result<Permissions, E>>(
+        &mut self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+        f: F,
+    ) -> Result<Permissions, E> {
+        if let Some(permissions) = self.get(guild_id, user_id, channel_id) {
+            return Ok(permissions);
+        }
+
+        let permissions = f()?;
+        self.insert(guild_id, user_id, channel_id, permissions);
+
+        Ok(permissions)
+    }
+
+    /// Remove the cached permissions of a member in every channel of a guild,
+    /// such as when their roles change.
+    pub fn invalidate_member(&mut self, guild_id: GuildId, user_id: UserId) {
+        self.retain(|(guild, user, _)| *guild != guild_id || *user != user_id);
+    }
+
+    /// Remove the cached permissions of every member in a channel of a guild,
+    /// such as when its overwrites change.
+    pub fn invalidate_channel(&mut self, guild_id: GuildId, channel_id: ChannelId) {
+        self.retain(|(guild, _, channel)| *guild != guild_id || *channel != channel_id);
+    }
+
+    /// Remove every cached permission of a guild, such as when its roles or
+    /// owner change.
+    pub fn invalidate_guild(&mut self, guild_id: GuildId) {
+        self.retain(|(guild, _, _)| *guild != guild_id);
+    }
+
+    /// Remove every entry.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    fn retain<F: FnMut(&Key) -> bool>(&mut self, mut f: F) {
+        self.entries.retain(|key, _| f(key));
+
+        let entries = &self.entries;
+        self.recency = std::mem::take(&mut self.recency)
+            .into_iter()
+            .filter(|(_, key)| entries.contains_key(key))
+            .collect();
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+
+        self.tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LruCache;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        guild::Permissions,
+        id::{ChannelId, GuildId, UserId},
+    };
+
+    assert_impl_all!(LruCache: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_eviction() {
+        let mut cache = LruCache::new(2);
+        cache.insert(GuildId(1), UserId(2), ChannelId(3), Permissions::CONNECT);
+        cache.insert(GuildId(1), UserId(4), ChannelId(3), Permissions::SPEAK);
+
+        // The first entry is now the most recently used.
+        assert_eq!(
+            Some(Permissions::CONNECT),
+            cache.get(GuildId(1), UserId(2), ChannelId(3)),
+        );
+
+        cache.insert(GuildId(1), UserId(5), ChannelId(3), Permissions::STREAM);
+        assert_eq!(2, cache.len());
+        assert!(cache.get(GuildId(1), UserId(4), ChannelId(3)).is_none());
+        assert!(cache.get(GuildId(1), UserId(2), ChannelId(3)).is_some());
+
+        // Replacing an entry doesn't evict anything.
+        cache.insert(GuildId(1), UserId(5), ChannelId(3), Permissions::empty());
+        assert_eq!(2, cache.len());
+        assert_eq!(
+            Some(Permissions::empty()),
+            cache.get(GuildId(1), UserId(5), ChannelId(3)),
+        );
+
+        let mut empty = LruCache::new(0);
+        empty.insert(GuildId(1), UserId(2), ChannelId(3), Permissions::CONNECT);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let mut cache = LruCache::new(10);
+
+        for (guild, user, channel) in &[(1, 2, 3), (1, 2, 4), (1, 5, 3), (6, 2, 3)] {
+            cache.insert(
+                GuildId(*guild),
+                UserId(*user),
+                ChannelId(*channel),
+                Permissions::empty(),
+            );
+        }
+
+        cache.invalidate_member(GuildId(1), UserId(2));
+        assert_eq!(2, cache.len());
+        assert!(cache.get(GuildId(6), UserId(2), ChannelId(3)).is_some());
+
+        cache.invalidate_channel(GuildId(1), ChannelId(3));
+        assert_eq!(1, cache.len());
+
+        cache.invalidate_guild(GuildId(6));
+        assert!(cache.is_empty());
+
+        let calculated = cache.get_or_insert_with(GuildId(1), UserId(2), ChannelId(3), || {
+            Permissions::VIEW_CHANNEL
+        });
+        assert_eq!(Permissions::VIEW_CHANNEL, calculated);
+        assert_eq!(
+            Err(()),
+            cache.get_or_try_insert_with(GuildId(1), UserId(2), ChannelId(4), || Err(())),
+        );
+        assert_eq!(1, cache.len());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,72 @@
+//! Permissions removed from a member because they don't apply to a channel.
+//!
+//! Calculating permissions in a channel ends by removing the permissions that
+//! can't be used there: guild-level permissions such as Ban Members, and
+//! permissions for other types of channels, such as Connect in a text
+//! channel. A member may have these permissions, so interfaces listing the
+//! permissions that a member lacks can use [`Masked::stripped`] to show them
+//! as not applicable rather than as denied.
+//!
+//! [`Masked::raw`] keeps the permissions from before they were removed, and
+//! doesn't implicitly deny permissions that depend on denied permissions.
+//! This is what Discord itself calculates, such as for the permissions of
+//! members in interactions, regardless of the calculator's cascade mode.
+//!
+//! [`Masked::raw`]: struct.Masked.html#structfield.raw
+//! [`Masked::stripped`]: struct.Masked.html#structfield.stripped
+
+use twilight_model::guild::Permissions;
+
+/// Permissions of a member in a channel both before and after the permissions
+/// that don't apply to the channel were removed.
+///
+/// Created via [`Calculator::in_channel_masked`].
+///
+/// [`Calculator::in_channel_masked`]: ../struct.Calculator.html#method.in_channel_masked
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Masked {
+    /// Permissions of the member in the channel.
+    pub permissions: Permissions,
+    /// Permissions of the member in the channel before guild-level
+    /// permissions and permissions that don't apply to the type of channel
+    /// were removed.
+    ///
+    /// These are calculated only from the member's roles and the channel's
+    /// overwrites, like Discord does: unlike [`permissions`], permissions
+    /// that depend on a denied permission aren't removed, whichever
+    /// dependencies the calculator was configured with.
+    ///
+    /// [`permissions`]: #structfield.permissions
+    pub raw: Permissions,
+    /// Permissions that the member would have had, but that were removed
+    /// because they're guild-level permissions or don't apply to the type of
+    /// channel.
+    pub stripped: Permissions,
+}
+
+impl Masked {
+    /// Create masked permissions from the permissions of a member after and
+    /// before masking, and the permissions that were stripped.
+    pub const fn new(permissions: Permissions, raw: Permissions, stripped: Permissions) -> Self {
+        Self {
+            permissions,
+            raw,
+            stripped,
+        }
+    }
+
+    /// Whether a permission was stripped rather than denied.
+    pub const fn is_stripped(&self, permission: Permissions) -> bool {
+        self.stripped.contains(permission)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Masked;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+
+    assert_fields!(Masked: permissions, raw, stripped);
+    assert_impl_all!(Masked: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+}
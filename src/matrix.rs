@@ -0,0 +1,405 @@
+//! Calculating the permissions of every member in every channel of a guild.
+//!
+//! Audit dashboards show the permissions of each member in each channel.
+//! Calling [`Calculator::in_channel`] for every pair recalculates each
+//! member's guild-level permissions and scans each channel's overwrites once
+//! per pair. [`MatrixCalculator`] calculates each member's guild-level
+//! permissions once and groups each channel's overwrites once, and then
+//! combines them into a [`PermissionMatrix`].
+//!
+//! [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+//! [`MatrixCalculator`]: struct.MatrixCalculator.html
+//! [`PermissionMatrix`]: struct.PermissionMatrix.html
+
+use super::{channel::ChannelCalculator, CalculatorError, InfallibleCalculator};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::{collections::HashMap, hash::Hash};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Calculate the permissions of every member of a guild in every channel.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::matrix::MatrixCalculator;
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+///     (RoleId(2), Permissions::empty()),
+/// ];
+/// let staff_only = &[
+///     PermissionOverwrite {
+///         allow: Permissions::empty(),
+///         deny: Permissions::VIEW_CHANNEL,
+///         kind: PermissionOverwriteType::Role(RoleId(1)),
+///     },
+///     PermissionOverwrite {
+///         allow: Permissions::VIEW_CHANNEL,
+///         deny: Permissions::empty(),
+///         kind: PermissionOverwriteType::Role(RoleId(2)),
+///     },
+/// ];
+/// let members = &[(UserId(3), &[][..]), (UserId(4), &[RoleId(2)][..])];
+/// let channels = vec![
+///     (ChannelId(5), ChannelType::GuildText, &[][..]),
+///     (ChannelId(6), ChannelType::GuildText, &staff_only[..]),
+/// ];
+///
+/// let matrix = MatrixCalculator::new(GuildId(1), guild_roles)?.calculate(members, channels);
+///
+/// assert!(!matrix
+///     .get(UserId(3), ChannelId(6))
+///     .unwrap()
+///     .contains(Permissions::VIEW_CHANNEL));
+/// assert!(matrix
+///     .get(UserId(4), ChannelId(6))
+///     .unwrap()
+///     .contains(Permissions::VIEW_CHANNEL));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the matrix calculator isn't useful if you don't calculate with it"]
+pub struct MatrixCalculator<'a> {
+    everyone: Permissions,
+    guild_id: GuildId,
+    guild_roles: &'a [(RoleId, Permissions)],
+    owner_id: Option<UserId>,
+}
+
+impl<'a> MatrixCalculator<'a> {
+    /// Create a calculator for the members and channels of a guild.
+    ///
+    /// The guild's roles must include the `@everyone` role, which has the same
+    /// ID as the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the guild's roles.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn new(
+        guild_id: GuildId,
+        guild_roles: &'a [(RoleId, Permissions)],
+    ) -> Result<Self, CalculatorError> {
+        let everyone = guild_roles
+            .iter()
+            .find(|(id, _)| id.0 == guild_id.0)
+            .map(|(_, permissions)| *permissions)
+            .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+
+        Ok(Self {
+            everyone,
+            guild_id,
+            guild_roles,
+            owner_id: None,
+        })
+    }
+
+    /// Configure the ID of the owner of the guild.
+    ///
+    /// Refer to the documentation for [`Calculator::owner_id`].
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id.replace(owner_id);
+
+        self
+    }
+
+    /// Calculate the permissions of each member in each channel.
+    ///
+    /// With the `rayon` feature enabled, members are calculated in parallel.
+    ///
+    /// Members are given as tuples of the member's ID and the IDs of their
+    /// roles, such as from [`Member::roles`]; the `@everyone` role doesn't
+    /// need to be included. Roles that aren't in the guild's roles are
+    /// ignored. Channels are given as tuples of the channel's ID, type, and
+    /// permission overwrites.
+    ///
+    /// [`Member::roles`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Member.html#structfield.roles
+    pub fn calculate<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        members: &[(UserId, &[RoleId])],
+        channels: U,
+    ) -> PermissionMatrix {
+        let channels = channels
+            .into_iter()
+            .map(|(channel_id, channel_type, channel_overwrites)| {
+                (
+                    channel_id,
+                    ChannelCalculator::new(self.guild_id, channel_type, channel_overwrites),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let row =
+            |(user_id, role_ids): &(UserId, &[RoleId])| self.row(*user_id, role_ids, &channels);
+
+        #[cfg(feature = "rayon")]
+        let permissions = members.par_iter().flat_map_iter(row).collect();
+
+        #[cfg(not(feature = "rayon"))]
+        let permissions = members.iter().flat_map(row).collect();
+
+        PermissionMatrix::new(
+            members.iter().map(|(user_id, _)| *user_id).collect(),
+            channels.iter().map(|(channel_id, _)| *channel_id).collect(),
+            permissions,
+        )
+    }
+
+    /// Calculate the permissions of a member in each channel.
+    pub(crate) fn row(
+        &self,
+        user_id: UserId,
+        role_ids: &[RoleId],
+        channels: &[(ChannelId, ChannelCalculator)],
+    ) -> Vec<Permissions> {
+        let mut member_roles = Vec::with_capacity(role_ids.len() + 1);
+        member_roles.push((RoleId(self.guild_id.0), self.everyone));
+        member_roles.extend(
+            self.guild_roles
+                .iter()
+                .filter(|(id, _)| id.0 != self.guild_id.0 && role_ids.contains(id)),
+        );
+
+        let mut calculator = InfallibleCalculator::new(self.guild_id, user_id, &member_roles);
+
+        if let Some(owner_id) = self.owner_id {
+            calculator = calculator.owner_id(owner_id);
+        }
+
+        let root = calculator.root();
+
+        channels
+            .iter()
+            .map(|(_, channel)| channel.in_channel_with_root(&calculator.0, root))
+            .collect()
+    }
+}
+
+/// Permissions of every member of a guild in every channel, created via
+/// [`MatrixCalculator::calculate`].
+///
+/// [`MatrixCalculator::calculate`]: struct.MatrixCalculator.html#method.calculate
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermissionMatrix {
+    channel_indices: HashMap<ChannelId, usize>,
+    channels: Vec<ChannelId>,
+    member_indices: HashMap<UserId, usize>,
+    members: Vec<UserId>,
+    /// Permissions of each member in each channel, in rows of members.
+    permissions: Vec<Permissions>,
+}
+
+impl PermissionMatrix {
+    /// Create a matrix from the permissions of each member in each channel,
+    /// in rows of members.
+    pub(crate) fn new(
+        members: Vec<UserId>,
+        channels: Vec<ChannelId>,
+        permissions: Vec<Permissions>,
+    ) -> Self {
+        debug_assert_eq!(members.len() * channels.len(), permissions.len());
+
+        Self {
+            channel_indices: index(&channels),
+            channels,
+            member_indices: index(&members),
+            members,
+            permissions,
+        }
+    }
+
+    /// IDs of the channels, in the order they were given.
+    pub fn channels(&self) -> &[ChannelId] {
+        &self.channels
+    }
+
+    /// IDs of the members, in the order they were given.
+    pub fn members(&self) -> &[UserId] {
+        &self.members
+    }
+
+    /// Permissions of a member in a channel.
+    ///
+    /// Returns `None` if the member or the channel isn't in the matrix. If a
+    /// member or channel was given more than once, then its last entry is
+    /// used.
+    pub fn get(&self, user_id: UserId, channel_id: ChannelId) -> Option<Permissions> {
+        let row = *self.member_indices.get(&user_id)?;
+        let column = *self.channel_indices.get(&channel_id)?;
+
+        Some(self.permissions[row * self.channels.len() + column])
+    }
+
+    /// Permissions of a member in each channel, in the order the channels were
+    /// given.
+    ///
+    /// Returns `None` if the member isn't in the matrix.
+    pub fn member(
+        &self,
+        user_id: UserId,
+    ) -> Option<impl Iterator<Item = (ChannelId, Permissions)> + '_> {
+        let row = *self.member_indices.get(&user_id)?;
+        let start = row * self.channels.len();
+
+        Some(
+            self.channels.iter().copied().zip(
+                self.permissions[start..start + self.channels.len()]
+                    .iter()
+                    .copied(),
+            ),
+        )
+    }
+
+    /// Permissions of each member in a channel, in the order the members were
+    /// given.
+    ///
+    /// Returns `None` if the channel isn't in the matrix.
+    pub fn channel(
+        &self,
+        channel_id: ChannelId,
+    ) -> Option<impl Iterator<Item = (UserId, Permissions)> + '_> {
+        let column = *self.channel_indices.get(&channel_id)?;
+
+        Some(self.members.iter().enumerate().map(move |(row, user_id)| {
+            (
+                *user_id,
+                self.permissions[row * self.channels.len() + column],
+            )
+        }))
+    }
+}
+
+/// Map each ID to its position, with later entries replacing earlier ones.
+fn index<T: Copy + Eq + Hash>(ids: &[T]) -> HashMap<T, usize> {
+    ids.iter()
+        .enumerate()
+        .map(|(position, id)| (*id, position))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatrixCalculator, PermissionMatrix};
+    use crate::{Calculator, CalculatorError};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(MatrixCalculator<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(PermissionMatrix: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_matrix() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::CONNECT | Permissions::SEND_MESSAGES),
+            (RoleId(3), Permissions::ADMINISTRATOR),
+        ];
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(UserId(5)),
+            },
+        ];
+        let members = &[
+            (UserId(4), &[RoleId(2)][..]),
+            (UserId(5), &[RoleId(2), RoleId(9)][..]),
+            (UserId(6), &[RoleId(3)][..]),
+            (UserId(7), &[][..]),
+        ];
+        let channels = vec![
+            (ChannelId(10), ChannelType::GuildText, &[][..]),
+            (ChannelId(11), ChannelType::GuildText, &overwrites[..]),
+            (ChannelId(12), ChannelType::GuildVoice, &overwrites[..]),
+        ];
+
+        let matrix = MatrixCalculator::new(GuildId(1), guild_roles)
+            .unwrap()
+            .owner_id(UserId(7))
+            .calculate(members, channels.clone());
+        assert_eq!(
+            &[UserId(4), UserId(5), UserId(6), UserId(7)],
+            matrix.members()
+        );
+        assert_eq!(
+            &[ChannelId(10), ChannelId(11), ChannelId(12)],
+            matrix.channels()
+        );
+
+        for (user_id, role_ids) in members {
+            let member_roles = guild_roles
+                .iter()
+                .copied()
+                .filter(|(id, _)| id.0 == 1 || role_ids.contains(id))
+                .collect::<Vec<_>>();
+            let calculator =
+                Calculator::new(GuildId(1), *user_id, &member_roles).owner_id(UserId(7));
+
+            for (channel_id, channel_type, channel_overwrites) in &channels {
+                assert_eq!(
+                    calculator
+                        .clone()
+                        .in_channel(*channel_type, *channel_overwrites)
+                        .ok(),
+                    matrix.get(*user_id, *channel_id),
+                );
+            }
+        }
+
+        assert!(!matrix
+            .get(UserId(4), ChannelId(11))
+            .unwrap()
+            .contains(Permissions::SEND_MESSAGES));
+        assert_eq!(3, matrix.member(UserId(5)).unwrap().count());
+        assert_eq!(
+            Some(Permissions::all()),
+            matrix
+                .channel(ChannelId(12))
+                .unwrap()
+                .find(|(user_id, _)| *user_id == UserId(6))
+                .map(|(_, permissions)| permissions),
+        );
+        assert!(matrix.get(UserId(8), ChannelId(10)).is_none());
+        assert!(matrix.member(UserId(8)).is_none());
+        assert!(matrix.channel(ChannelId(13)).is_none());
+
+        assert!(matches!(
+            MatrixCalculator::new(GuildId(1), &[]),
+            Err(CalculatorError::EveryoneRoleMissing { .. })
+        ));
+    }
+}
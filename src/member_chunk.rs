@@ -0,0 +1,269 @@
+//! Calculating the permissions of members as they're received in gateway
+//! member chunks.
+//!
+//! Requesting the members of a large guild results in many [`MemberChunk`]
+//! events. [`ChunkCalculator`] calculates the permissions of the members in
+//! each chunk as it's consumed, so that a guild can be audited without first
+//! collecting all of its members.
+//!
+//! [`ChunkCalculator`]: struct.ChunkCalculator.html
+//! [`MemberChunk`]: https://docs.rs/twilight-model/*/twilight_model/gateway/payload/struct.MemberChunk.html
+
+use super::{CalculatorError, InfallibleCalculator};
+use std::vec::IntoIter;
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    gateway::payload::MemberChunk,
+    guild::{Member, Permissions},
+    id::{GuildId, RoleId, UserId},
+};
+
+/// Calculate the permissions of members in a channel from member chunks.
+///
+/// # Examples
+///
+/// Stream the permissions of members as chunks are received:
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::ChannelType,
+///     gateway::payload::MemberChunk,
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::member_chunk::ChunkCalculator;
+///
+/// # let chunks: Vec<MemberChunk> = Vec::new();
+/// let guild_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+///
+/// let calculator = ChunkCalculator::new(GuildId(1), guild_roles, ChannelType::GuildText, &[])?;
+///
+/// for (user_id, permissions) in calculator.calculate(chunks) {
+///     if !permissions.contains(Permissions::SEND_MESSAGES) {
+///         println!("{} can't send messages", user_id);
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the chunk calculator isn't useful if you don't calculate with it"]
+pub struct ChunkCalculator<'a> {
+    channel_overwrites: &'a [PermissionOverwrite],
+    channel_type: ChannelType,
+    everyone: Permissions,
+    guild_id: GuildId,
+    guild_roles: &'a [(RoleId, Permissions)],
+    owner_id: Option<UserId>,
+}
+
+impl<'a> ChunkCalculator<'a> {
+    /// Create a calculator for the members of a guild in one of its channels.
+    ///
+    /// The guild's roles must include the `@everyone` role, which has the same
+    /// ID as the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the guild's roles.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn new(
+        guild_id: GuildId,
+        guild_roles: &'a [(RoleId, Permissions)],
+        channel_type: ChannelType,
+        channel_overwrites: &'a [PermissionOverwrite],
+    ) -> Result<Self, CalculatorError> {
+        let everyone = guild_roles
+            .iter()
+            .find(|(id, _)| id.0 == guild_id.0)
+            .map(|(_, permissions)| *permissions)
+            .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+
+        Ok(Self {
+            channel_overwrites,
+            channel_type,
+            everyone,
+            guild_id,
+            guild_roles,
+            owner_id: None,
+        })
+    }
+
+    /// Configure the ID of the owner of the guild.
+    ///
+    /// Refer to the documentation for [`Calculator::owner_id`].
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id.replace(owner_id);
+
+        self
+    }
+
+    /// Calculate the permissions of a member in the channel.
+    ///
+    /// Roles of the member that aren't in the guild's roles, such as roles
+    /// deleted since the member was received, are ignored.
+    pub fn member(&self, member: &Member) -> Permissions {
+        let mut member_roles = Vec::with_capacity(member.roles.len() + 1);
+        member_roles.push((RoleId(self.guild_id.0), self.everyone));
+        member_roles.extend(
+            self.guild_roles
+                .iter()
+                .filter(|(id, _)| member.roles.contains(id)),
+        );
+
+        let mut calculator =
+            InfallibleCalculator::new(self.guild_id, member.user.id, &member_roles);
+
+        if let Some(owner_id) = self.owner_id {
+            calculator = calculator.owner_id(owner_id);
+        }
+
+        calculator.in_channel(self.channel_type, self.channel_overwrites)
+    }
+
+    /// Lazily calculate the permissions of the members in member chunks.
+    ///
+    /// Only one chunk is held at a time; the next chunk is consumed once all
+    /// members of the current chunk have been yielded. Chunks of other guilds
+    /// are skipped.
+    pub fn calculate<I: IntoIterator<Item = MemberChunk>>(
+        self,
+        chunks: I,
+    ) -> ChunkPermissions<'a, I::IntoIter> {
+        ChunkPermissions {
+            calculator: self,
+            chunks: chunks.into_iter(),
+            members: Vec::new().into_iter(),
+        }
+    }
+}
+
+/// Iterator over the permissions of members in member chunks, created via
+/// [`ChunkCalculator::calculate`].
+///
+/// Yields the ID of each member along with their permissions in the channel,
+/// in the order the members were received.
+///
+/// [`ChunkCalculator::calculate`]: struct.ChunkCalculator.html#method.calculate
+#[derive(Debug)]
+pub struct ChunkPermissions<'a, I> {
+    calculator: ChunkCalculator<'a>,
+    chunks: I,
+    members: IntoIter<Member>,
+}
+
+impl<I: Iterator<Item = MemberChunk>> Iterator for ChunkPermissions<'_, I> {
+    type Item = (UserId, Permissions);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(member) = self.members.next() {
+                return Some((member.user.id, self.calculator.member(&member)));
+            }
+
+            let chunk = self.chunks.next()?;
+
+            if chunk.guild_id == self.calculator.guild_id {
+                self.members = chunk.members.into_iter();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkCalculator, ChunkPermissions};
+    use crate::CalculatorError;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash, vec::IntoIter};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        gateway::payload::MemberChunk,
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(ChunkCalculator<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(ChunkPermissions<'_, IntoIter<MemberChunk>>: Debug, Iterator, Send, Sync);
+
+    /// Create a member chunk as it's received over the gateway.
+    fn chunk(guild_id: u64, members: &[(u64, &[u64])]) -> MemberChunk {
+        let members = members
+            .iter()
+            .map(|(user_id, roles)| {
+                let roles = roles
+                    .iter()
+                    .map(|id| format!("\"{}\"", id))
+                    .collect::<Vec<_>>()
+                    .join(",");
+
+                format!(
+                    r#"{{"deaf":false,"joined_at":"2021-01-01T00:00:00.000000+00:00","mute":false,"roles":[{}],"user":{{"avatar":null,"discriminator":"0001","id":"{}","username":"user"}}}}"#,
+                    roles, user_id,
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        serde_json::from_str(&format!(
+            r#"{{"chunk_count":1,"chunk_index":0,"guild_id":"{}","members":[{}],"not_found":[]}}"#,
+            guild_id, members,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_calculate() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES),
+        ];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(4)),
+        }];
+        let chunks = vec![
+            chunk(1, &[(3, &[2]), (4, &[])]),
+            chunk(10, &[(11, &[])]),
+            chunk(1, &[(5, &[2, 9])]),
+        ];
+
+        let calculator =
+            ChunkCalculator::new(GuildId(1), guild_roles, ChannelType::GuildText, overwrites)
+                .unwrap()
+                .owner_id(UserId(5));
+
+        assert_eq!(
+            vec![
+                (
+                    UserId(3),
+                    Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+                ),
+                (
+                    UserId(4),
+                    Permissions::VIEW_CHANNEL | Permissions::ADD_REACTIONS
+                ),
+                (UserId(5), Permissions::all()),
+            ],
+            calculator.calculate(chunks).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_everyone_role_missing() {
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            ChunkCalculator::new(GuildId(1), &[], ChannelType::GuildText, &[]),
+        );
+    }
+}
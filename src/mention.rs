@@ -0,0 +1,85 @@
+//! Checking whether a member can mention a role.
+//!
+//! Whether mentioning a role notifies its members depends on both the role and
+//! the member sending the message: anyone who can send messages can mention a
+//! role that's marked as mentionable, while roles that aren't can only be
+//! mentioned by members with the Mention Everyone permission.
+//!
+//! Mentioning `@everyone` and `@here` always requires Mention Everyone; refer
+//! to the [`mention_everyone`] module for where that's possible.
+//!
+//! [`mention_everyone`]: ../mention_everyone/index.html
+
+use twilight_model::guild::{Permissions, Role};
+
+/// Permissions needed to send a message mentioning anything.
+const SEND: Permissions = Permissions::from_bits_truncate(
+    Permissions::SEND_MESSAGES.bits() | Permissions::VIEW_CHANNEL.bits(),
+);
+
+/// Whether a member with permissions in a channel can mention a role in it,
+/// notifying the role's members.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     guild::{Permissions, Role},
+///     id::RoleId,
+/// };
+/// use twilight_permission_calculator::mention;
+///
+/// let mut role = Role {
+///     color: 0,
+///     hoist: false,
+///     id: RoleId(1),
+///     managed: false,
+///     mentionable: false,
+///     name: "moderators".to_owned(),
+///     permissions: Permissions::empty(),
+///     position: 1,
+///     tags: None,
+/// };
+/// let permissions = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+///
+/// assert!(!mention::can_mention_role(permissions, &role));
+/// assert!(mention::can_mention_role(permissions | Permissions::MENTION_EVERYONE, &role));
+///
+/// role.mentionable = true;
+/// assert!(mention::can_mention_role(permissions, &role));
+/// ```
+pub fn can_mention_role(permissions: Permissions, role: &Role) -> bool {
+    permissions.contains(SEND)
+        && (role.mentionable || permissions.contains(Permissions::MENTION_EVERYONE))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util;
+    use twilight_model::guild::{Permissions, Role};
+
+    fn role(mentionable: bool) -> Role {
+        Role {
+            mentionable,
+            ..test_util::role(1, Permissions::empty())
+        }
+    }
+
+    #[test]
+    fn test_can_mention_role() {
+        let send = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+
+        assert!(super::can_mention_role(send, &role(true)));
+        assert!(!super::can_mention_role(send, &role(false)));
+        assert!(super::can_mention_role(
+            send | Permissions::MENTION_EVERYONE,
+            &role(false)
+        ));
+
+        // Nothing can be mentioned without being able to send messages.
+        assert!(!super::can_mention_role(
+            Permissions::MENTION_EVERYONE | Permissions::VIEW_CHANNEL,
+            &role(true)
+        ));
+    }
+}
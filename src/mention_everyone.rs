@@ -0,0 +1,264 @@
+//! Finding everywhere that `@everyone` and `@here` can be mentioned.
+//!
+//! Mention Everyone is usually the first permission administrators want to
+//! lock down, but whether it can be used depends on every channel's
+//! overwrites. [`report`] calculates it for every role and member overwrite
+//! in every channel.
+//!
+//! Mention Everyone is only usable alongside the View Channel and Send
+//! Messages permissions, so only roles and members that have all three count.
+//! Only text and news channels are checked, since messages can't be sent in
+//! other channels.
+//!
+//! [`report`]: fn.report.html
+
+use super::{channel_permissions, role, CalculatorError};
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Permissions needed for Mention Everyone to be usable.
+const USABLE: Permissions = Permissions::from_bits_truncate(
+    Permissions::MENTION_EVERYONE.bits()
+        | Permissions::SEND_MESSAGES.bits()
+        | Permissions::VIEW_CHANNEL.bits(),
+);
+
+/// Everywhere that `@everyone` and `@here` can be mentioned, created via
+/// [`report`].
+///
+/// [`report`]: fn.report.html
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MentionEveryoneReport {
+    /// Channels in which at least one role or member overwrite can mention
+    /// everyone, in the order they were given.
+    pub channels: Vec<ChannelId>,
+    /// Member overwrites that allow a member to mention everyone in a channel,
+    /// along with the channel they're in.
+    ///
+    /// Members are checked as if they only had the `@everyone` role, so that
+    /// only overwrites which grant the permission by themselves are included.
+    pub members: Vec<(ChannelId, UserId)>,
+    /// Roles that can mention everyone, along with the channels they can
+    /// mention everyone in, in the order they were given.
+    ///
+    /// Roles that can't mention everyone in any channel aren't included.
+    pub roles: Vec<(RoleId, Vec<ChannelId>)>,
+}
+
+impl MentionEveryoneReport {
+    /// Whether nobody can mention everyone in any channel.
+    pub fn is_locked_down(&self) -> bool {
+        self.channels.is_empty()
+    }
+}
+
+/// Find every role, member overwrite, and channel where Mention Everyone is
+/// usable.
+///
+/// Guild roles are given as tuples of the role's ID and permissions, and must
+/// include the `@everyone` role, which has the same ID as the guild. Channels
+/// are given as tuples of the channel's ID, type, and permission overwrites.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::mention_everyone;
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+///     (RoleId(2), Permissions::MENTION_EVERYONE),
+/// ];
+/// let announcements = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+/// let channels = vec![
+///     (ChannelId(3), ChannelType::GuildText, &[][..]),
+///     (ChannelId(4), ChannelType::GuildText, &announcements[..]),
+/// ];
+///
+/// let report = mention_everyone::report(GuildId(1), guild_roles, channels)?;
+///
+/// assert_eq!(vec![ChannelId(3)], report.channels);
+/// assert_eq!(vec![(RoleId(2), vec![ChannelId(3)])], report.roles);
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+/// isn't in the guild's roles.
+///
+/// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+pub fn report<'b, U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>>(
+    guild_id: GuildId,
+    guild_roles: &[(RoleId, Permissions)],
+    channels: U,
+) -> Result<MentionEveryoneReport, CalculatorError> {
+    let everyone = guild_roles
+        .iter()
+        .find(|(id, _)| id.0 == guild_id.0)
+        .map(|(_, permissions)| *permissions)
+        .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+    let everyone_roles = &[(RoleId(guild_id.0), everyone)];
+    let everyone_root = role::root(everyone, Permissions::empty());
+
+    let mut report = MentionEveryoneReport::default();
+    let mut roles = guild_roles
+        .iter()
+        .map(|(id, _)| (*id, Vec::new()))
+        .collect::<Vec<_>>();
+
+    for (channel_id, channel_type, channel_overwrites) in channels {
+        // Administrators have every permission in every channel, but messages
+        // can only be sent in text channels.
+        if !matches!(
+            channel_type,
+            ChannelType::GuildNews | ChannelType::GuildText
+        ) {
+            continue;
+        }
+
+        let mut usable = false;
+
+        for (role, (_, channels)) in guild_roles.iter().zip(roles.iter_mut()) {
+            let permissions =
+                role::in_channel(guild_id, everyone, *role, channel_type, channel_overwrites);
+
+            if permissions.contains(USABLE) {
+                channels.push(channel_id);
+                usable = true;
+            }
+        }
+
+        for overwrite in channel_overwrites {
+            let user_id = match overwrite.kind {
+                PermissionOverwriteType::Member(user_id) => user_id,
+                PermissionOverwriteType::Role(_) => continue,
+            };
+
+            let permissions = channel_permissions(
+                guild_id,
+                Some(user_id),
+                everyone_roles,
+                everyone_root,
+                channel_type,
+                channel_overwrites,
+            );
+
+            if permissions.contains(USABLE) {
+                report.members.push((channel_id, user_id));
+                usable = true;
+            }
+        }
+
+        if usable {
+            report.channels.push(channel_id);
+        }
+    }
+
+    report.roles = roles
+        .into_iter()
+        .filter(|(_, channels)| !channels.is_empty())
+        .collect();
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MentionEveryoneReport;
+    use crate::CalculatorError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(MentionEveryoneReport: channels, members, roles);
+    assert_impl_all!(
+        MentionEveryoneReport: Clone,
+        Debug,
+        Default,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_report() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::ADMINISTRATOR),
+            (RoleId(3), Permissions::SEND_MESSAGES),
+        ];
+        let member = &[PermissionOverwrite {
+            allow: Permissions::MENTION_EVERYONE | Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(4)),
+        }];
+        let role = &[PermissionOverwrite {
+            allow: Permissions::MENTION_EVERYONE,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(RoleId(3)),
+        }];
+        let channels = vec![
+            (ChannelId(5), ChannelType::GuildText, &member[..]),
+            (ChannelId(6), ChannelType::GuildText, &role[..]),
+            (ChannelId(7), ChannelType::GuildVoice, &role[..]),
+        ];
+
+        let report = super::report(GuildId(1), guild_roles, channels).unwrap();
+
+        assert_eq!(
+            MentionEveryoneReport {
+                channels: vec![ChannelId(5), ChannelId(6)],
+                members: vec![(ChannelId(5), UserId(4))],
+                roles: vec![
+                    (RoleId(2), vec![ChannelId(5), ChannelId(6)]),
+                    (RoleId(3), vec![ChannelId(6)]),
+                ],
+            },
+            report,
+        );
+    }
+
+    #[test]
+    fn test_locked_down() {
+        let guild_roles = &[(RoleId(1), Permissions::MENTION_EVERYONE)];
+        let channels = vec![(ChannelId(2), ChannelType::GuildText, &[][..])];
+
+        let report = super::report(GuildId(1), guild_roles, channels).unwrap();
+        assert!(report.is_locked_down());
+
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(2)
+            }),
+            super::report(GuildId(2), guild_roles, Vec::new()),
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Calculating permissions from `twilight-model`'s guild, member, and channel
+//! types.
+//!
+//! Bots that already have a [`Guild`] and [`Member`], such as from a cache or
+//! a guild create event, would otherwise have to pick out the member's roles
+//! and the owner of the guild by hand. [`GuildMember`] does that once and
+//! creates calculators from it, and [`Calculator::in_guild_channel`] takes
+//! the type and overwrites of a [`GuildChannel`] directly.
+//!
+//! Calculators borrow the member's roles rather than owning them, so the
+//! roles picked out of the guild are stored in a [`GuildMember`] that the
+//! calculator borrows from.
+//!
+//! [`Calculator::in_guild_channel`]: ../struct.Calculator.html#method.in_guild_channel
+//! [`Guild`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Guild.html
+//! [`GuildChannel`]: https://docs.rs/twilight-model/*/twilight_model/channel/enum.GuildChannel.html
+//! [`GuildMember`]: struct.GuildMember.html
+//! [`Member`]: https://docs.rs/twilight-model/*/twilight_model/guild/struct.Member.html
+
+use super::{Calculator, CalculatorError};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType, GuildChannel},
+    guild::{Guild, Member, Permissions, Role},
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Member of a guild along with the permissions of their roles, picked out of
+/// the guild's roles.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::GuildChannel,
+///     guild::{Guild, Member, Permissions},
+/// };
+/// use twilight_permission_calculator::{model::GuildMember, Calculator};
+///
+/// # let (guild, member, channel): (Guild, Member, GuildChannel) = unimplemented!();
+/// let guild_member = GuildMember::new(&guild, &member);
+/// let permissions = Calculator::from_guild(&guild_member).in_guild_channel(&channel)?;
+///
+/// if permissions.contains(Permissions::SEND_MESSAGES) {
+///     println!("member can send messages in the channel");
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct GuildMember {
+    guild_id: GuildId,
+    member_roles: Vec<(RoleId, Permissions)>,
+    owner_id: UserId,
+    user_id: UserId,
+}
+
+impl GuildMember {
+    /// Pick out the roles of a member from the roles of their guild.
+    ///
+    /// The `@everyone` role is included along with the member's roles. Roles
+    /// of the member that aren't in the guild are ignored.
+    pub fn new(guild: &Guild, member: &Member) -> Self {
+        Self::from_roles(guild.id, guild.owner_id, &guild.roles, member)
+    }
+
+    /// Pick out the roles of a member from a guild's roles, such as when the
+    /// guild's roles and owner are cached separately from the guild.
+    ///
+    /// Refer to [`new`] for more information.
+    ///
+    /// [`new`]: #method.new
+    pub fn from_roles(
+        guild_id: GuildId,
+        owner_id: UserId,
+        guild_roles: &[Role],
+        member: &Member,
+    ) -> Self {
+        Self {
+            guild_id,
+            member_roles: member_roles(guild_id, guild_roles, member),
+            owner_id,
+            user_id: member.user.id,
+        }
+    }
+
+    /// ID of the guild.
+    pub fn guild_id(&self) -> GuildId {
+        self.guild_id
+    }
+
+    /// Roles of the member, including the `@everyone` role, along with their
+    /// permissions.
+    pub fn member_roles(&self) -> &[(RoleId, Permissions)] {
+        &self.member_roles
+    }
+
+    /// ID of the owner of the guild.
+    pub fn owner_id(&self) -> UserId {
+        self.owner_id
+    }
+
+    /// ID of the member.
+    pub fn user_id(&self) -> UserId {
+        self.user_id
+    }
+
+    /// Create a calculator for the member, configured with the owner of the
+    /// guild.
+    pub fn calculator(&self) -> Calculator<'_> {
+        Calculator::new(self.guild_id, self.user_id, &self.member_roles).owner_id(self.owner_id)
+    }
+
+    /// Calculate the guild-level permissions of the member.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::root`].
+    ///
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    pub fn root(&self) -> Result<Permissions, CalculatorError> {
+        self.calculator().root()
+    }
+
+    /// Calculate the permissions of the member in a channel of the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    pub fn in_channel(&self, channel: &GuildChannel) -> Result<Permissions, CalculatorError> {
+        self.calculator().in_guild_channel(channel)
+    }
+}
+
+/// Roles of a member, including the `@everyone` role, along with their
+/// permissions.
+pub(crate) fn member_roles(
+    guild_id: GuildId,
+    guild_roles: &[Role],
+    member: &Member,
+) -> Vec<(RoleId, Permissions)> {
+    guild_roles
+        .iter()
+        .filter(|role| role.id.0 == guild_id.0 || member.roles.contains(&role.id))
+        .map(|role| (role.id, role.permissions))
+        .collect()
+}
+
+/// ID, guild ID, type, and permission overwrites of a guild channel.
+pub(crate) fn channel_parts(
+    channel: &GuildChannel,
+) -> (
+    ChannelId,
+    Option<GuildId>,
+    ChannelType,
+    &[PermissionOverwrite],
+) {
+    match channel {
+        GuildChannel::Category(c) => (c.id, c.guild_id, c.kind, &c.permission_overwrites),
+        GuildChannel::Text(c) => (c.id, c.guild_id, c.kind, &c.permission_overwrites),
+        GuildChannel::Stage(c) | GuildChannel::Voice(c) => {
+            (c.id, c.guild_id, c.kind, &c.permission_overwrites)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GuildMember;
+    use crate::test_util::role;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        guild::{Member, Permissions},
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(GuildMember: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_from_roles() {
+        let guild_roles = &[
+            role(1, Permissions::VIEW_CHANNEL),
+            role(2, Permissions::SEND_MESSAGES),
+            role(3, Permissions::KICK_MEMBERS),
+        ];
+        let member: Member = serde_json::from_str(
+            r#"{"deaf":false,"guild_id":"1","joined_at":"2021-01-01T00:00:00.000000+00:00","mute":false,"roles":["2","4"],"user":{"avatar":null,"discriminator":"0001","id":"5","username":"user"}}"#,
+        )
+        .unwrap();
+
+        let guild_member = GuildMember::from_roles(GuildId(1), UserId(6), guild_roles, &member);
+        assert_eq!(
+            &[
+                (RoleId(1), Permissions::VIEW_CHANNEL),
+                (RoleId(2), Permissions::SEND_MESSAGES),
+            ],
+            guild_member.member_roles(),
+        );
+        assert_eq!(UserId(5), guild_member.user_id());
+        assert_eq!(
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            guild_member.root().unwrap(),
+        );
+
+        let owner = GuildMember::from_roles(GuildId(1), UserId(5), guild_roles, &member);
+        assert_eq!(Permissions::all(), owner.root().unwrap());
+    }
+}
@@ -0,0 +1,297 @@
+//! Verifying that a muted role is effective in every channel.
+//!
+//! Moderation bots mute members by giving them a role that is denied
+//! permissions, such as Send Messages and Speak, in every channel. The role is
+//! only effective if every channel has an overwrite denying the permissions,
+//! and if no other role or member overwrite allows them again. [`verify`]
+//! finds every channel where that isn't the case.
+//!
+//! [`verify`]: fn.verify.html
+
+use super::{overwrite, Calculator, CalculatorError};
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Place where a muted role isn't effective.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Gap {
+    /// Channel doesn't have an overwrite denying the muted role some of the
+    /// permissions.
+    ///
+    /// Only permissions that apply to the type of the channel are included.
+    OverwriteMissing {
+        /// ID of the channel.
+        channel_id: ChannelId,
+        /// Permissions that aren't denied.
+        permissions: Permissions,
+    },
+    /// Member with the muted role still has some of the permissions in a
+    /// channel, such as due to another role or a member overwrite allowing
+    /// them.
+    Overridden {
+        /// ID of the channel.
+        channel_id: ChannelId,
+        /// Permissions that the member still has.
+        permissions: Permissions,
+        /// ID of the member.
+        user_id: UserId,
+    },
+}
+
+/// Places where a muted role isn't effective, created via [`verify`].
+///
+/// [`verify`]: fn.verify.html
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MutedReport {
+    /// Gaps in the order of the channels they're in. Within a channel, a
+    /// missing overwrite is listed before the members that were given.
+    pub gaps: Vec<Gap>,
+}
+
+impl MutedReport {
+    /// Whether the muted role is effective in every channel.
+    pub fn is_effective(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
+/// Verify that a muted role is denied permissions in every channel, both by
+/// the channel's overwrites and for each of the given members.
+///
+/// Members are given as tuples of the member's ID and roles, and their roles
+/// are given like they are to [`Calculator::new`]. Members that don't have the
+/// muted role are skipped. Members that can't view a channel can't use any of
+/// the permissions in it, and so never have a gap in it. Channels are given as
+/// tuples of the channel's ID, type, and permission overwrites.
+///
+/// # Examples
+///
+/// Find the channel that a muted member can still send messages in:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::muted::{self, Gap};
+///
+/// let muted_role = RoleId(2);
+/// let member_roles = &[
+///     (RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL),
+///     (muted_role, Permissions::empty()),
+/// ];
+/// let members = &[(UserId(3), &member_roles[..])];
+///
+/// let muted = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(muted_role),
+/// }];
+/// let channels = vec![
+///     (ChannelId(4), ChannelType::GuildText, &muted[..]),
+///     (ChannelId(5), ChannelType::GuildText, &[][..]),
+/// ];
+///
+/// let report = muted::verify(GuildId(1), muted_role, Permissions::SEND_MESSAGES, members, channels)?;
+///
+/// assert_eq!(
+///     vec![
+///         Gap::OverwriteMissing {
+///             channel_id: ChannelId(5),
+///             permissions: Permissions::SEND_MESSAGES,
+///         },
+///         Gap::Overridden {
+///             channel_id: ChannelId(5),
+///             permissions: Permissions::SEND_MESSAGES,
+///             user_id: UserId(3),
+///         },
+///     ],
+///     report.gaps,
+/// );
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+/// isn't in the roles of a member.
+///
+/// [`Calculator::new`]: ../struct.Calculator.html#method.new
+/// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+pub fn verify<'b, U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>>(
+    guild_id: GuildId,
+    role_id: RoleId,
+    denied: Permissions,
+    members: &[(UserId, &[(RoleId, Permissions)])],
+    channels: U,
+) -> Result<MutedReport, CalculatorError> {
+    let members = members
+        .iter()
+        .filter(|(_, member_roles)| member_roles.iter().any(|(id, _)| *id == role_id))
+        .map(|(user_id, member_roles)| {
+            let calculator = Calculator::new(guild_id, *user_id, member_roles);
+            let root = calculator.root()?;
+
+            Ok((calculator, root))
+        })
+        .collect::<Result<Vec<_>, CalculatorError>>()?;
+
+    let mut report = MutedReport::default();
+
+    for (channel_id, channel_type, channel_overwrites) in channels {
+        let overwrite_denied = channel_overwrites
+            .iter()
+            .find(|overwrite| overwrite.kind == PermissionOverwriteType::Role(role_id))
+            .map_or(Permissions::empty(), |overwrite| overwrite.deny);
+        let not_denied = (denied & overwrite::applicable(channel_type)) - overwrite_denied;
+
+        if !not_denied.is_empty() {
+            report.gaps.push(Gap::OverwriteMissing {
+                channel_id,
+                permissions: not_denied,
+            });
+        }
+
+        for (calculator, root) in &members {
+            let permissions =
+                calculator.in_channel_with_root(*root, channel_type, channel_overwrites);
+
+            if !permissions.contains(Permissions::VIEW_CHANNEL) {
+                continue;
+            }
+
+            let remaining = denied & permissions;
+
+            if !remaining.is_empty() {
+                report.gaps.push(Gap::Overridden {
+                    channel_id,
+                    permissions: remaining,
+                    user_id: calculator.user_id,
+                });
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gap, MutedReport};
+    use crate::CalculatorError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Gap::OverwriteMissing: channel_id, permissions);
+    assert_fields!(Gap::Overridden: channel_id, permissions, user_id);
+    assert_fields!(MutedReport: gaps);
+    assert_impl_all!(Gap: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(MutedReport: Clone, Debug, Default, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_verify() {
+        let muted = RoleId(2);
+        let denied = Permissions::SEND_MESSAGES | Permissions::SPEAK;
+        let everyone = (
+            RoleId(1),
+            Permissions::SEND_MESSAGES | Permissions::SPEAK | Permissions::VIEW_CHANNEL,
+        );
+        let muted_roles = &[everyone, (muted, Permissions::empty())];
+        let helper_roles = &[
+            everyone,
+            (muted, Permissions::empty()),
+            (RoleId(3), Permissions::empty()),
+        ];
+        let unmuted_roles = &[everyone];
+        let members = &[
+            (UserId(4), &muted_roles[..]),
+            (UserId(5), &helper_roles[..]),
+            (UserId(6), &unmuted_roles[..]),
+        ];
+
+        let text = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(muted),
+            },
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+        let voice = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SPEAK,
+            kind: PermissionOverwriteType::Role(muted),
+        }];
+        let hidden = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(muted),
+        }];
+        let channels = vec![
+            (ChannelId(7), ChannelType::GuildText, &text[..]),
+            (ChannelId(8), ChannelType::GuildVoice, &voice[..]),
+            (ChannelId(9), ChannelType::GuildText, &hidden[..]),
+        ];
+
+        let report = super::verify(GuildId(1), muted, denied, members, channels).unwrap();
+
+        assert!(!report.is_effective());
+        assert_eq!(
+            vec![
+                Gap::Overridden {
+                    channel_id: ChannelId(7),
+                    permissions: Permissions::SEND_MESSAGES,
+                    user_id: UserId(5),
+                },
+                Gap::OverwriteMissing {
+                    channel_id: ChannelId(9),
+                    permissions: Permissions::SEND_MESSAGES,
+                },
+            ],
+            report.gaps,
+        );
+    }
+
+    #[test]
+    fn test_everyone_role_missing() {
+        let member_roles = &[(RoleId(2), Permissions::empty())];
+        let members = &[(UserId(3), &member_roles[..])];
+
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            super::verify(
+                GuildId(1),
+                RoleId(2),
+                Permissions::SEND_MESSAGES,
+                members,
+                Vec::new()
+            ),
+        );
+    }
+}
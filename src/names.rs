@@ -0,0 +1,174 @@
+//! Human-readable names of permissions.
+//!
+//! Names are written in title case and match the names used throughout the
+//! documentation of this crate, such as "View Channel" for
+//! `Permissions::VIEW_CHANNEL`. None of the names contain characters that are
+//! interpreted by Discord's markdown, so they can be placed in messages and
+//! embeds without escaping.
+
+use std::fmt::{Formatter, Result as FmtResult};
+use twilight_model::guild::Permissions;
+
+/// Names of every known permission, in order of their bit position.
+const NAMES: &[(Permissions, &str)] = &[
+    (Permissions::CREATE_INVITE, "Create Invite"),
+    (Permissions::KICK_MEMBERS, "Kick Members"),
+    (Permissions::BAN_MEMBERS, "Ban Members"),
+    (Permissions::ADMINISTRATOR, "Administrator"),
+    (Permissions::MANAGE_CHANNELS, "Manage Channels"),
+    (Permissions::MANAGE_GUILD, "Manage Guild"),
+    (Permissions::ADD_REACTIONS, "Add Reactions"),
+    (Permissions::VIEW_AUDIT_LOG, "View Audit Log"),
+    (Permissions::PRIORITY_SPEAKER, "Priority Speaker"),
+    (Permissions::STREAM, "Stream"),
+    (Permissions::VIEW_CHANNEL, "View Channel"),
+    (Permissions::SEND_MESSAGES, "Send Messages"),
+    (Permissions::SEND_TTS_MESSAGES, "Send TTS Messages"),
+    (Permissions::MANAGE_MESSAGES, "Manage Messages"),
+    (Permissions::EMBED_LINKS, "Embed Links"),
+    (Permissions::ATTACH_FILES, "Attach Files"),
+    (Permissions::READ_MESSAGE_HISTORY, "Read Message History"),
+    (Permissions::MENTION_EVERYONE, "Mention Everyone"),
+    (Permissions::USE_EXTERNAL_EMOJIS, "Use External Emojis"),
+    (Permissions::VIEW_GUILD_INSIGHTS, "View Guild Insights"),
+    (Permissions::CONNECT, "Connect"),
+    (Permissions::SPEAK, "Speak"),
+    (Permissions::MUTE_MEMBERS, "Mute Members"),
+    (Permissions::DEAFEN_MEMBERS, "Deafen Members"),
+    (Permissions::MOVE_MEMBERS, "Move Members"),
+    (Permissions::USE_VAD, "Use VAD"),
+    (Permissions::CHANGE_NICKNAME, "Change Nickname"),
+    (Permissions::MANAGE_NICKNAMES, "Manage Nicknames"),
+    (Permissions::MANAGE_ROLES, "Manage Roles"),
+    (Permissions::MANAGE_WEBHOOKS, "Manage Webhooks"),
+    (Permissions::MANAGE_EMOJIS, "Manage Emojis"),
+    (Permissions::USE_SLASH_COMMANDS, "Use Slash Commands"),
+    (Permissions::REQUEST_TO_SPEAK, "Request To Speak"),
+];
+
+/// Retrieve the name of a single permission.
+///
+/// Returns `None` if the given value isn't exactly one known permission.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::names;
+///
+/// assert_eq!(Some("Send Messages"), names::get(Permissions::SEND_MESSAGES));
+/// assert!(names::get(Permissions::SEND_MESSAGES | Permissions::SPEAK).is_none());
+/// ```
+pub fn get(permission: Permissions) -> Option<&'static str> {
+    NAMES
+        .iter()
+        .find(|(known, _)| *known == permission)
+        .map(|(_, name)| *name)
+}
+
+/// Iterate over the permissions contained in a set along with their names.
+///
+/// Permissions are returned in order of their bit position.
+pub fn iter(permissions: Permissions) -> Names {
+    Names {
+        index: 0,
+        permissions,
+    }
+}
+
+/// Write the names of the permissions contained in a set separated by commas,
+/// such as "Embed Links, Attach Files".
+pub(crate) fn write_list(f: &mut Formatter<'_>, permissions: Permissions) -> FmtResult {
+    for (idx, (_, name)) in iter(permissions).enumerate() {
+        if idx > 0 {
+            f.write_str(", ")?;
+        }
+
+        f.write_str(name)?;
+    }
+
+    Ok(())
+}
+
+/// Iterator over the permissions in a set and their names.
+///
+/// Created via [`iter`].
+///
+/// [`iter`]: fn.iter.html
+#[derive(Clone, Debug)]
+pub struct Names {
+    index: usize,
+    permissions: Permissions,
+}
+
+impl Iterator for Names {
+    type Item = (Permissions, &'static str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((permission, name)) = NAMES.get(self.index) {
+            self.index += 1;
+
+            if self.permissions.contains(*permission) {
+                return Some((*permission, *name));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Names, NAMES};
+    use static_assertions::assert_impl_all;
+    use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+    use twilight_model::guild::Permissions;
+
+    assert_impl_all!(Names: Clone, Debug, Iterator, Send, Sync);
+
+    /// Test that every known permission has exactly one name.
+    #[test]
+    fn test_all_named() {
+        let mut named = Permissions::empty();
+
+        for (permission, _) in NAMES {
+            assert!(!named.intersects(*permission));
+            named.insert(*permission);
+        }
+
+        assert_eq!(Permissions::all(), named);
+    }
+
+    #[test]
+    fn test_markdown_safe() {
+        for (_, name) in NAMES {
+            assert!(!name.contains(|c| "*_~`|>\\:<@#[]()".contains(c)));
+        }
+    }
+
+    #[test]
+    fn test_iter() {
+        let permissions = Permissions::SPEAK | Permissions::ADD_REACTIONS;
+        let names = super::iter(permissions).map(|(_, name)| name);
+
+        assert_eq!(vec!["Add Reactions", "Speak"], names.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_write_list() {
+        struct List(Permissions);
+
+        impl Display for List {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                super::write_list(f, self.0)
+            }
+        }
+
+        assert_eq!("", List(Permissions::empty()).to_string());
+        assert_eq!("Speak", List(Permissions::SPEAK).to_string());
+        assert_eq!(
+            "Add Reactions, Speak",
+            List(Permissions::SPEAK | Permissions::ADD_REACTIONS).to_string(),
+        );
+    }
+}
@@ -0,0 +1,884 @@
+//! Building permission overwrites that are valid for a channel, and checking
+//! whether members may set them.
+
+use super::{
+    PERMISSIONS_MESSAGING, PERMISSIONS_ROOT, PERMISSIONS_STAGE, PERMISSIONS_TEXT, PERMISSIONS_VOICE,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    channel::{
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{GuildId, UserId},
+};
+
+/// Permissions that may be set in an overwrite for a type of channel.
+///
+/// Guild-level permissions never apply to channels. Category overwrites may
+/// contain both text and voice permissions since they're synced to the
+/// category's channels.
+pub(crate) fn applicable(channel_type: ChannelType) -> Permissions {
+    let channel = Permissions::all() - PERMISSIONS_ROOT;
+
+    match channel_type {
+        ChannelType::GuildCategory => channel,
+        ChannelType::GuildNews | ChannelType::GuildText => {
+            channel - PERMISSIONS_STAGE - PERMISSIONS_VOICE
+        }
+        ChannelType::GuildStageVoice => {
+            channel - PERMISSIONS_TEXT - (PERMISSIONS_VOICE - PERMISSIONS_STAGE)
+        }
+        ChannelType::GuildVoice => {
+            channel - PERMISSIONS_TEXT - (PERMISSIONS_STAGE - PERMISSIONS_VOICE)
+        }
+        ChannelType::GuildStore => {
+            channel - PERMISSIONS_STAGE - PERMISSIONS_TEXT - PERMISSIONS_VOICE
+        }
+        // Overwrites don't exist outside of guilds.
+        _ => Permissions::empty(),
+    }
+}
+
+/// Error returned when an overwrite can't be built.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OverwriteBuilderError {
+    /// Permissions were set that don't apply to the type of channel, such as
+    /// voice permissions in a text channel or guild-level permissions in any
+    /// channel.
+    NotApplicable {
+        /// Type of channel the overwrite is for.
+        channel_type: ChannelType,
+        /// Permissions that don't apply to the type of channel.
+        permissions: Permissions,
+    },
+}
+
+impl Display for OverwriteBuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::NotApplicable {
+                channel_type,
+                permissions,
+            } => f.write_fmt(format_args!(
+                "permissions {:?} don't apply to {:?} channels",
+                permissions, channel_type
+            )),
+        }
+    }
+}
+
+impl Error for OverwriteBuilderError {}
+
+/// Error returned when a member may not edit a channel's permission
+/// overwrites.
+///
+/// Returned by [`check_edit`].
+///
+/// [`check_edit`]: fn.check_edit.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OverwriteEditError {
+    /// Member doesn't have the Manage Roles permission in the channel, which
+    /// the Discord client calls Manage Permissions.
+    MissingPermission,
+    /// Member doesn't have permissions in the channel that the edit would
+    /// allow or deny.
+    NotHeld {
+        /// Permissions that would be rejected.
+        permissions: Permissions,
+    },
+}
+
+impl Display for OverwriteEditError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::MissingPermission => {
+                f.write_str("member doesn't have the manage roles permission in the channel")
+            }
+            Self::NotHeld { permissions } => f.write_fmt(format_args!(
+                "member doesn't have permissions {:?} in the channel",
+                permissions
+            )),
+        }
+    }
+}
+
+impl Error for OverwriteEditError {}
+
+/// Structural problem with a permission overwrite.
+///
+/// Returned by [`validate_overwrite`].
+///
+/// [`validate_overwrite`]: fn.validate_overwrite.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OverwriteViolation {
+    /// Permissions are both allowed and denied.
+    Conflicting {
+        /// Permissions that are both allowed and denied.
+        permissions: Permissions,
+    },
+    /// Member overwrite targets the ID of the guild, which is the ID of the
+    /// `@everyone` role rather than of a member.
+    MemberIsGuild {
+        /// ID of the targeted member.
+        user_id: UserId,
+    },
+    /// Permissions are allowed or denied that don't apply to the type of
+    /// channel, such as voice permissions in a text channel or guild-level
+    /// permissions in any channel.
+    NotApplicable {
+        /// Type of channel the overwrite is for.
+        channel_type: ChannelType,
+        /// Permissions that don't apply to the type of channel.
+        permissions: Permissions,
+    },
+}
+
+impl Display for OverwriteViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Conflicting { permissions } => f.write_fmt(format_args!(
+                "permissions {:?} are both allowed and denied",
+                permissions
+            )),
+            Self::MemberIsGuild { user_id } => f.write_fmt(format_args!(
+                "member overwrite targets {}, which is the guild's ID",
+                user_id
+            )),
+            Self::NotApplicable {
+                channel_type,
+                permissions,
+            } => f.write_fmt(format_args!(
+                "permissions {:?} don't apply to {:?} channels",
+                permissions, channel_type
+            )),
+        }
+    }
+}
+
+/// Reason that permissions allowed by an overwrite don't have an effect.
+///
+/// Returned by [`OverwriteBuilder::warnings`].
+///
+/// [`OverwriteBuilder::warnings`]: struct.OverwriteBuilder.html#method.warnings
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum OverwriteWarning {
+    /// Permissions are allowed while the View Channel permission is denied,
+    /// which implicitly denies all other permissions.
+    ViewChannelDenied {
+        /// Allowed permissions without an effect.
+        permissions: Permissions,
+    },
+    /// Messaging permissions - Attach Files, Embed Links, Mention Everyone,
+    /// and Send TTS Messages - are allowed while the Send Messages permission
+    /// is denied, which implicitly denies them.
+    SendMessagesDenied {
+        /// Allowed permissions without an effect.
+        permissions: Permissions,
+    },
+}
+
+/// Build a permission overwrite for a type of channel, rejecting permissions
+/// that the Discord client wouldn't allow in the overwrite.
+///
+/// Like toggling permissions in the client, allowing a permission removes it
+/// from the denied permissions and denying a permission removes it from the
+/// allowed permissions.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{permission_overwrite::PermissionOverwriteType, ChannelType},
+///     guild::Permissions,
+///     id::RoleId,
+/// };
+/// use twilight_permission_calculator::overwrite::{OverwriteBuilder, OverwriteWarning};
+///
+/// let builder = OverwriteBuilder::new(ChannelType::GuildText, PermissionOverwriteType::Role(RoleId(2)))
+///     .allow(Permissions::EMBED_LINKS | Permissions::READ_MESSAGE_HISTORY)
+///     .deny(Permissions::SEND_MESSAGES);
+///
+/// // Embed Links won't have an effect while Send Messages is denied.
+/// assert_eq!(
+///     vec![OverwriteWarning::SendMessagesDenied {
+///         permissions: Permissions::EMBED_LINKS,
+///     }],
+///     builder.warnings(),
+/// );
+///
+/// let overwrite = builder.build()?;
+/// assert_eq!(Permissions::SEND_MESSAGES, overwrite.deny);
+///
+/// // Voice permissions don't apply to text channels.
+/// assert!(OverwriteBuilder::new(ChannelType::GuildText, PermissionOverwriteType::Role(RoleId(2)))
+///     .allow(Permissions::SPEAK)
+///     .build()
+///     .is_err());
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the overwrite builder isn't useful if you don't build it"]
+pub struct OverwriteBuilder {
+    allow: Permissions,
+    channel_type: ChannelType,
+    deny: Permissions,
+    kind: PermissionOverwriteType,
+}
+
+impl OverwriteBuilder {
+    /// Create a builder for an overwrite on a role or member in a type of
+    /// channel.
+    pub fn new(channel_type: ChannelType, kind: PermissionOverwriteType) -> Self {
+        Self {
+            allow: Permissions::empty(),
+            channel_type,
+            deny: Permissions::empty(),
+            kind,
+        }
+    }
+
+    /// Allow permissions, removing them from the denied permissions.
+    pub fn allow(mut self, permissions: Permissions) -> Self {
+        self.allow.insert(permissions);
+        self.deny.remove(permissions);
+
+        self
+    }
+
+    /// Deny permissions, removing them from the allowed permissions.
+    pub fn deny(mut self, permissions: Permissions) -> Self {
+        self.deny.insert(permissions);
+        self.allow.remove(permissions);
+
+        self
+    }
+
+    /// Allowed permissions that won't have an effect due to other permissions
+    /// being denied.
+    ///
+    /// Warnings don't prevent the overwrite from being built.
+    pub fn warnings(&self) -> Vec<OverwriteWarning> {
+        let mut warnings = Vec::new();
+
+        if self.deny.contains(Permissions::VIEW_CHANNEL) && !self.allow.is_empty() {
+            warnings.push(OverwriteWarning::ViewChannelDenied {
+                permissions: self.allow,
+            });
+        }
+
+        let messaging = self.allow & PERMISSIONS_MESSAGING;
+
+        if self.deny.contains(Permissions::SEND_MESSAGES) && !messaging.is_empty() {
+            warnings.push(OverwriteWarning::SendMessagesDenied {
+                permissions: messaging,
+            });
+        }
+
+        warnings
+    }
+
+    /// Build the overwrite.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OverwriteBuilderError::NotApplicable`] if any allowed or
+    /// denied permissions don't apply to the type of channel.
+    ///
+    /// [`OverwriteBuilderError::NotApplicable`]: enum.OverwriteBuilderError.html#variant.NotApplicable
+    pub fn build(self) -> Result<PermissionOverwrite, OverwriteBuilderError> {
+        let not_applicable = (self.allow | self.deny) - applicable(self.channel_type);
+
+        if !not_applicable.is_empty() {
+            return Err(OverwriteBuilderError::NotApplicable {
+                channel_type: self.channel_type,
+                permissions: not_applicable,
+            });
+        }
+
+        Ok(PermissionOverwrite {
+            allow: self.allow,
+            deny: self.deny,
+            kind: self.kind,
+        })
+    }
+}
+
+/// Check whether a member may edit a permission overwrite of a channel.
+///
+/// `permissions` are the member's permissions in the channel, such as
+/// calculated via [`Calculator::in_channel`]. Members need the Manage Roles
+/// permission in the channel to edit its overwrites, and may only allow or
+/// deny permissions that they have in the channel themselves. Administrators
+/// have every permission, so they may set any permission.
+///
+/// Only the permissions that the edit changes are checked: `existing` is the
+/// overwrite as it is now, if there is one, and `requested` is the overwrite
+/// as it would be after the edit.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///     guild::Permissions,
+///     id::RoleId,
+/// };
+/// use twilight_permission_calculator::overwrite::{self, OverwriteEditError};
+///
+/// let permissions = Permissions::MANAGE_ROLES | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+/// let requested = PermissionOverwrite {
+///     allow: Permissions::SEND_MESSAGES,
+///     deny: Permissions::ATTACH_FILES,
+///     kind: PermissionOverwriteType::Role(RoleId(2)),
+/// };
+///
+/// assert_eq!(
+///     Err(OverwriteEditError::NotHeld {
+///         permissions: Permissions::ATTACH_FILES,
+///     }),
+///     overwrite::check_edit(permissions, None, &requested),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [`OverwriteEditError::MissingPermission`] if the member doesn't
+/// have the Manage Roles permission in the channel.
+///
+/// Returns [`OverwriteEditError::NotHeld`] listing the changed permissions
+/// that the member doesn't have in the channel.
+///
+/// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+/// [`OverwriteEditError::MissingPermission`]: enum.OverwriteEditError.html#variant.MissingPermission
+/// [`OverwriteEditError::NotHeld`]: enum.OverwriteEditError.html#variant.NotHeld
+pub fn check_edit(
+    permissions: Permissions,
+    existing: Option<&PermissionOverwrite>,
+    requested: &PermissionOverwrite,
+) -> Result<(), OverwriteEditError> {
+    if !permissions.contains(Permissions::MANAGE_ROLES) {
+        return Err(OverwriteEditError::MissingPermission);
+    }
+
+    let not_held = changed(existing, requested) - permissions;
+
+    if not_held.is_empty() {
+        Ok(())
+    } else {
+        Err(OverwriteEditError::NotHeld {
+            permissions: not_held,
+        })
+    }
+}
+
+/// Clamp a requested permission overwrite to the permissions that a member may
+/// set.
+///
+/// Permissions that the member may not change, as checked by [`check_edit`],
+/// are kept as they are in the existing overwrite, or left unset if there's
+/// no existing overwrite. The clamped overwrite can then be applied without
+/// the request being rejected. If the member doesn't have the Manage Roles
+/// permission in the channel then they may not change any permission, so the
+/// existing overwrite is returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///     guild::Permissions,
+///     id::UserId,
+/// };
+/// use twilight_permission_calculator::overwrite;
+///
+/// let permissions = Permissions::MANAGE_ROLES | Permissions::VIEW_CHANNEL;
+/// let requested = PermissionOverwrite {
+///     allow: Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL,
+///     deny: Permissions::empty(),
+///     kind: PermissionOverwriteType::Member(UserId(2)),
+/// };
+///
+/// let clamped = overwrite::clamp(permissions, None, &requested);
+///
+/// assert_eq!(Permissions::VIEW_CHANNEL, clamped.allow);
+/// ```
+///
+/// [`check_edit`]: fn.check_edit.html
+pub fn clamp(
+    permissions: Permissions,
+    existing: Option<&PermissionOverwrite>,
+    requested: &PermissionOverwrite,
+) -> PermissionOverwrite {
+    let settable = if permissions.contains(Permissions::MANAGE_ROLES) {
+        permissions
+    } else {
+        Permissions::empty()
+    };
+    let (allow, deny) = existing.map_or((Permissions::empty(), Permissions::empty()), |existing| {
+        (existing.allow, existing.deny)
+    });
+
+    PermissionOverwrite {
+        allow: (requested.allow & settable) | (allow - settable),
+        deny: (requested.deny & settable) | (deny - settable),
+        kind: requested.kind,
+    }
+}
+
+/// Validate the structure of a permission overwrite for a type of channel.
+///
+/// Unlike [`OverwriteBuilder`], which rejects overwrites that the Discord
+/// client wouldn't create, this checks overwrites that already exist or were
+/// built by hand, such as those received from the API. Violations are
+/// returned in the order of [`OverwriteViolation`]'s variants, and no
+/// violations means that the overwrite is valid.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::overwrite::{self, OverwriteViolation};
+///
+/// let overwrite = PermissionOverwrite {
+///     allow: Permissions::CONNECT | Permissions::SEND_MESSAGES,
+///     deny: Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(2)),
+/// };
+///
+/// assert_eq!(
+///     vec![
+///         OverwriteViolation::Conflicting {
+///             permissions: Permissions::SEND_MESSAGES,
+///         },
+///         OverwriteViolation::NotApplicable {
+///             channel_type: ChannelType::GuildText,
+///             permissions: Permissions::CONNECT,
+///         },
+///     ],
+///     overwrite::validate_overwrite(GuildId(1), ChannelType::GuildText, &overwrite),
+/// );
+/// ```
+///
+/// [`OverwriteBuilder`]: struct.OverwriteBuilder.html
+/// [`OverwriteViolation`]: enum.OverwriteViolation.html
+pub fn validate_overwrite(
+    guild_id: GuildId,
+    channel_type: ChannelType,
+    overwrite: &PermissionOverwrite,
+) -> Vec<OverwriteViolation> {
+    let mut violations = Vec::new();
+
+    let conflicting = overwrite.allow & overwrite.deny;
+
+    if !conflicting.is_empty() {
+        violations.push(OverwriteViolation::Conflicting {
+            permissions: conflicting,
+        });
+    }
+
+    if let PermissionOverwriteType::Member(user_id) = overwrite.kind {
+        if user_id.0 == guild_id.0 {
+            violations.push(OverwriteViolation::MemberIsGuild { user_id });
+        }
+    }
+
+    let not_applicable = (overwrite.allow | overwrite.deny) - applicable(channel_type);
+
+    if !not_applicable.is_empty() {
+        violations.push(OverwriteViolation::NotApplicable {
+            channel_type,
+            permissions: not_applicable,
+        });
+    }
+
+    violations
+}
+
+/// Strip permissions from an overwrite that have no effect in a type of
+/// channel, so that it can be sent to Discord.
+///
+/// Permissions that don't apply to the type of channel are removed from both
+/// the allowed and denied permissions. Permissions that are both allowed and
+/// denied are removed from the denied permissions, since allowing a
+/// permission takes precedence over denying it in the same overwrite. The
+/// sanitized overwrite has no [`OverwriteViolation::Conflicting`] or
+/// [`OverwriteViolation::NotApplicable`] violations and results in the same
+/// permissions as the original.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::RoleId,
+/// };
+/// use twilight_permission_calculator::overwrite;
+///
+/// let overwrite = PermissionOverwrite {
+///     allow: Permissions::CONNECT | Permissions::SEND_MESSAGES,
+///     deny: Permissions::ADMINISTRATOR | Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(2)),
+/// };
+///
+/// let sanitized = overwrite::sanitize(ChannelType::GuildText, &overwrite);
+///
+/// assert_eq!(Permissions::SEND_MESSAGES, sanitized.allow);
+/// assert!(sanitized.deny.is_empty());
+/// ```
+///
+/// [`OverwriteViolation::Conflicting`]: enum.OverwriteViolation.html#variant.Conflicting
+/// [`OverwriteViolation::NotApplicable`]: enum.OverwriteViolation.html#variant.NotApplicable
+pub fn sanitize(channel_type: ChannelType, overwrite: &PermissionOverwrite) -> PermissionOverwrite {
+    let applicable = applicable(channel_type);
+
+    PermissionOverwrite {
+        allow: overwrite.allow & applicable,
+        deny: (overwrite.deny - overwrite.allow) & applicable,
+        kind: overwrite.kind,
+    }
+}
+
+/// Permissions that are allowed or denied differently in the requested
+/// overwrite than in the existing one.
+fn changed(existing: Option<&PermissionOverwrite>, requested: &PermissionOverwrite) -> Permissions {
+    let (allow, deny) = existing.map_or((Permissions::empty(), Permissions::empty()), |existing| {
+        (existing.allow, existing.deny)
+    });
+
+    (allow ^ requested.allow) | (deny ^ requested.deny)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        OverwriteBuilder, OverwriteBuilderError, OverwriteEditError, OverwriteViolation,
+        OverwriteWarning,
+    };
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(OverwriteBuilderError::NotApplicable: channel_type, permissions);
+    assert_fields!(OverwriteEditError::NotHeld: permissions);
+    assert_fields!(OverwriteViolation::Conflicting: permissions);
+    assert_fields!(OverwriteViolation::MemberIsGuild: user_id);
+    assert_fields!(OverwriteViolation::NotApplicable: channel_type, permissions);
+    assert_fields!(OverwriteWarning::SendMessagesDenied: permissions);
+    assert_fields!(OverwriteWarning::ViewChannelDenied: permissions);
+    assert_impl_all!(OverwriteBuilder: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        OverwriteBuilderError: Clone,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        OverwriteEditError: Clone,
+        Copy,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        OverwriteViolation: Clone,
+        Copy,
+        Debug,
+        Display,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+    assert_impl_all!(OverwriteWarning: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    const MEMBER: PermissionOverwriteType = PermissionOverwriteType::Member(UserId(1));
+
+    #[test]
+    fn test_toggle() {
+        let overwrite = OverwriteBuilder::new(ChannelType::GuildVoice, MEMBER)
+            .allow(Permissions::SPEAK | Permissions::STREAM)
+            .deny(Permissions::STREAM)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            PermissionOverwrite {
+                allow: Permissions::SPEAK,
+                deny: Permissions::STREAM,
+                kind: MEMBER,
+            },
+            overwrite,
+        );
+    }
+
+    #[test]
+    fn test_check_edit() {
+        let permissions =
+            Permissions::MANAGE_ROLES | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+        let existing = PermissionOverwrite {
+            allow: Permissions::EMBED_LINKS,
+            deny: Permissions::empty(),
+            kind: MEMBER,
+        };
+        let requested = PermissionOverwrite {
+            allow: Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            deny: Permissions::SPEAK,
+            kind: MEMBER,
+        };
+
+        // Embed Links is unchanged, so it isn't checked.
+        assert_eq!(
+            Err(OverwriteEditError::NotHeld {
+                permissions: Permissions::SPEAK,
+            }),
+            super::check_edit(permissions, Some(&existing), &requested),
+        );
+        assert_eq!(
+            Err(OverwriteEditError::NotHeld {
+                permissions: Permissions::EMBED_LINKS | Permissions::SPEAK,
+            }),
+            super::check_edit(permissions, None, &requested),
+        );
+        assert!(super::check_edit(Permissions::all(), None, &requested).is_ok());
+        assert_eq!(
+            Err(OverwriteEditError::MissingPermission),
+            super::check_edit(permissions - Permissions::MANAGE_ROLES, None, &existing),
+        );
+
+        // Removing a permission the member doesn't have is also rejected.
+        assert_eq!(
+            Err(OverwriteEditError::NotHeld {
+                permissions: Permissions::EMBED_LINKS,
+            }),
+            super::check_edit(
+                permissions,
+                Some(&existing),
+                &PermissionOverwrite {
+                    allow: Permissions::empty(),
+                    ..existing
+                },
+            ),
+        );
+    }
+
+    #[test]
+    fn test_clamp() {
+        let permissions =
+            Permissions::MANAGE_ROLES | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+        let existing = PermissionOverwrite {
+            allow: Permissions::EMBED_LINKS,
+            deny: Permissions::ATTACH_FILES,
+            kind: MEMBER,
+        };
+        let requested = PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES | Permissions::SPEAK,
+            deny: Permissions::EMBED_LINKS | Permissions::VIEW_CHANNEL,
+            kind: MEMBER,
+        };
+
+        let clamped = super::clamp(permissions, Some(&existing), &requested);
+        assert_eq!(
+            PermissionOverwrite {
+                allow: Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+                deny: Permissions::ATTACH_FILES | Permissions::VIEW_CHANNEL,
+                kind: MEMBER,
+            },
+            clamped,
+        );
+        assert!(super::check_edit(permissions, Some(&existing), &clamped).is_ok());
+
+        assert_eq!(
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::VIEW_CHANNEL,
+                kind: MEMBER,
+            },
+            super::clamp(permissions, None, &requested),
+        );
+        assert_eq!(
+            existing,
+            super::clamp(Permissions::SEND_MESSAGES, Some(&existing), &requested),
+        );
+        assert_eq!(
+            requested,
+            super::clamp(Permissions::all(), None, &requested)
+        );
+    }
+
+    #[test]
+    fn test_not_applicable() {
+        let root = OverwriteBuilder::new(ChannelType::GuildCategory, MEMBER)
+            .allow(Permissions::SPEAK | Permissions::SEND_MESSAGES)
+            .deny(Permissions::BAN_MEMBERS)
+            .build();
+
+        assert_eq!(
+            Err(OverwriteBuilderError::NotApplicable {
+                channel_type: ChannelType::GuildCategory,
+                permissions: Permissions::BAN_MEMBERS,
+            }),
+            root,
+        );
+
+        let text = OverwriteBuilder::new(ChannelType::GuildNews, MEMBER)
+            .allow(Permissions::SEND_MESSAGES | Permissions::CONNECT)
+            .build();
+
+        assert_eq!(
+            Err(OverwriteBuilderError::NotApplicable {
+                channel_type: ChannelType::GuildNews,
+                permissions: Permissions::CONNECT,
+            }),
+            text,
+        );
+
+        let voice = OverwriteBuilder::new(ChannelType::GuildStageVoice, MEMBER)
+            .deny(Permissions::MANAGE_MESSAGES)
+            .build();
+
+        assert_eq!(
+            Err(OverwriteBuilderError::NotApplicable {
+                channel_type: ChannelType::GuildStageVoice,
+                permissions: Permissions::MANAGE_MESSAGES,
+            }),
+            voice,
+        );
+
+        assert!(OverwriteBuilder::new(ChannelType::Private, MEMBER)
+            .allow(Permissions::VIEW_CHANNEL)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_warnings() {
+        let builder = OverwriteBuilder::new(
+            ChannelType::GuildText,
+            PermissionOverwriteType::Role(RoleId(1)),
+        )
+        .allow(Permissions::ADD_REACTIONS | Permissions::ATTACH_FILES)
+        .deny(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES);
+
+        assert_eq!(
+            vec![
+                OverwriteWarning::ViewChannelDenied {
+                    permissions: Permissions::ADD_REACTIONS | Permissions::ATTACH_FILES,
+                },
+                OverwriteWarning::SendMessagesDenied {
+                    permissions: Permissions::ATTACH_FILES,
+                },
+            ],
+            builder.warnings(),
+        );
+
+        let builder = builder.allow(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES);
+        assert!(builder.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_validate_overwrite() {
+        let guild_id = GuildId(1);
+        let valid = PermissionOverwrite {
+            allow: Permissions::CONNECT,
+            deny: Permissions::SPEAK,
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        };
+        assert!(super::validate_overwrite(guild_id, ChannelType::GuildVoice, &valid).is_empty());
+
+        let invalid = PermissionOverwrite {
+            allow: Permissions::BAN_MEMBERS | Permissions::CONNECT,
+            deny: Permissions::CONNECT,
+            kind: PermissionOverwriteType::Member(UserId(1)),
+        };
+        assert_eq!(
+            vec![
+                OverwriteViolation::Conflicting {
+                    permissions: Permissions::CONNECT,
+                },
+                OverwriteViolation::MemberIsGuild { user_id: UserId(1) },
+                OverwriteViolation::NotApplicable {
+                    channel_type: ChannelType::GuildVoice,
+                    permissions: Permissions::BAN_MEMBERS,
+                },
+            ],
+            super::validate_overwrite(guild_id, ChannelType::GuildVoice, &invalid),
+        );
+
+        // The `@everyone` role is targeted by a role overwrite.
+        let everyone = PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        };
+        assert!(super::validate_overwrite(guild_id, ChannelType::GuildText, &everyone).is_empty());
+    }
+
+    #[test]
+    fn test_sanitize() {
+        let guild_id = GuildId(1);
+        let overwrite = PermissionOverwrite {
+            allow: Permissions::MANAGE_GUILD | Permissions::SPEAK | Permissions::VIEW_CHANNEL,
+            deny: Permissions::CONNECT | Permissions::SEND_MESSAGES | Permissions::SPEAK,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        };
+
+        let sanitized = super::sanitize(ChannelType::GuildVoice, &overwrite);
+        assert_eq!(
+            Permissions::SPEAK | Permissions::VIEW_CHANNEL,
+            sanitized.allow
+        );
+        assert_eq!(Permissions::CONNECT, sanitized.deny);
+        assert_eq!(overwrite.kind, sanitized.kind);
+        assert!(
+            super::validate_overwrite(guild_id, ChannelType::GuildVoice, &sanitized).is_empty()
+        );
+
+        // Overwrites don't exist outside of guilds.
+        let sanitized = super::sanitize(ChannelType::Private, &overwrite);
+        assert!(sanitized.allow.is_empty());
+        assert!(sanitized.deny.is_empty());
+    }
+}
@@ -0,0 +1,562 @@
+//! Calculator that owns the roles of the member.
+//!
+//! [`Calculator`] usually borrows the member's roles, which makes it awkward
+//! to hold across await points or store in a struct, and cloning one that
+//! owns them clones the roles. [`OwnedCalculator`] keeps the roles behind an
+//! [`Arc`] instead, so that it's `'static`, cheap to clone, and can be built
+//! once and reused.
+//!
+//! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+//! [`Calculator`]: ../struct.Calculator.html
+//! [`OwnedCalculator`]: struct.OwnedCalculator.html
+
+use super::{
+    calculated::{CalculatedPermissions, ChannelPermissions},
+    dependency::Dependencies,
+    mask::Masked,
+    role::RoleCalculator,
+    version::DataVersion,
+    Calculator, CalculatorError, CascadeMode,
+};
+#[cfg(feature = "explain")]
+use super::{denial::Denial, explain::Explanation, report::Report, trace::Trace};
+use std::{borrow::Cow, collections::HashMap, sync::Arc, time::SystemTime};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Calculate the permissions of a member, owning the member's roles.
+///
+/// Each calculation borrows the roles into a [`Calculator`] with the same
+/// configuration, so results are the same as the borrowed calculator's.
+/// Calculations that aren't available here directly are available via
+/// [`calculator`].
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::ChannelType,
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::owned::OwnedCalculator;
+///
+/// let member_roles = vec![(RoleId(1), Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL)];
+/// let calculator = OwnedCalculator::new(GuildId(1), UserId(2), member_roles).owner_id(UserId(3));
+///
+/// // The calculator isn't tied to the lifetime of the roles, so it can be
+/// // moved into a task.
+/// let handle = std::thread::spawn(move || calculator.in_channel(ChannelType::GuildText, &[]));
+///
+/// assert!(handle.join().unwrap()?.contains(Permissions::SEND_MESSAGES));
+/// # Ok(()) }
+/// ```
+///
+/// [`Calculator`]: ../struct.Calculator.html
+/// [`calculator`]: #method.calculator
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the member calculator isn't useful if you don't calculate permissions"]
+pub struct OwnedCalculator {
+    /// Configuration of the calculator, with no roles.
+    config: Calculator<'static>,
+    member_roles: Arc<[(RoleId, Permissions)]>,
+}
+
+impl OwnedCalculator {
+    /// Create a calculator to calculate the permissions of a member.
+    ///
+    /// Member roles are given like they are to [`Calculator::new`], as
+    /// anything that can be converted into an [`Arc`] slice, such as a
+    /// [`Vec`].
+    ///
+    /// [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+    /// [`Calculator::new`]: ../struct.Calculator.html#method.new
+    /// [`Vec`]: https://doc.rust-lang.org/std/vec/struct.Vec.html
+    pub fn new(
+        guild_id: GuildId,
+        user_id: UserId,
+        member_roles: impl Into<Arc<[(RoleId, Permissions)]>>,
+    ) -> Self {
+        Self {
+            config: Calculator::new(guild_id, user_id, &[]),
+            member_roles: member_roles.into(),
+        }
+    }
+
+    /// Configure whether administrators have every permission in every
+    /// channel.
+    ///
+    /// Refer to [`Calculator::administrator_bypass`] for more information.
+    ///
+    /// [`Calculator::administrator_bypass`]: ../struct.Calculator.html#method.administrator_bypass
+    pub fn administrator_bypass(mut self, administrator_bypass: bool) -> Self {
+        self.config = self.config.administrator_bypass(administrator_bypass);
+
+        self
+    }
+
+    /// Configure whether denying a permission in a channel implicitly denies
+    /// the permissions that depend on it.
+    ///
+    /// Refer to [`Calculator::cascade_mode`] for more information.
+    ///
+    /// [`Calculator::cascade_mode`]: ../struct.Calculator.html#method.cascade_mode
+    pub fn cascade_mode(mut self, cascade_mode: CascadeMode) -> Self {
+        self.config = self.config.cascade_mode(cascade_mode);
+
+        self
+    }
+
+    /// Configure which permissions are implicitly denied in a channel when the
+    /// permissions they depend on are denied.
+    ///
+    /// Refer to [`Calculator::dependencies`] for more information.
+    ///
+    /// [`Calculator::dependencies`]: ../struct.Calculator.html#method.dependencies
+    pub fn dependencies(mut self, dependencies: Dependencies) -> Self {
+        self.config = self.config.dependencies(dependencies);
+
+        self
+    }
+
+    /// Set when the member's timeout expires.
+    ///
+    /// Refer to [`Calculator::communication_disabled_until`] for more
+    /// information.
+    ///
+    /// [`Calculator::communication_disabled_until`]: ../struct.Calculator.html#method.communication_disabled_until
+    pub fn communication_disabled_until(
+        mut self,
+        communication_disabled_until: SystemTime,
+    ) -> Self {
+        self.config = self
+            .config
+            .communication_disabled_until(communication_disabled_until);
+
+        self
+    }
+
+    /// Configure the version of the data that permissions are calculated
+    /// from.
+    ///
+    /// Refer to [`Calculator::data_version`] for more information.
+    ///
+    /// [`Calculator::data_version`]: ../struct.Calculator.html#method.data_version
+    pub fn data_version(mut self, data_version: DataVersion) -> Self {
+        self.config = self.config.data_version(data_version);
+
+        self
+    }
+
+    /// Configure the ID of the owner of the guild.
+    ///
+    /// Refer to [`Calculator::owner_id`] for more information.
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.config = self.config.owner_id(owner_id);
+
+        self
+    }
+
+    /// Configure whether to keep guild-level permissions in the permissions
+    /// calculated in channels.
+    ///
+    /// Refer to [`Calculator::retain_guild_permissions`] for more
+    /// information.
+    ///
+    /// [`Calculator::retain_guild_permissions`]: ../struct.Calculator.html#method.retain_guild_permissions
+    pub fn retain_guild_permissions(mut self, retain_guild_permissions: bool) -> Self {
+        self.config = self
+            .config
+            .retain_guild_permissions(retain_guild_permissions);
+
+        self
+    }
+
+    /// Configure whether IDs are validated before calculating.
+    ///
+    /// Refer to [`Calculator::validate_ids`] for more information.
+    ///
+    /// [`Calculator::validate_ids`]: ../struct.Calculator.html#method.validate_ids
+    pub fn validate_ids(mut self, validate_ids: bool) -> Self {
+        self.config = self.config.validate_ids(validate_ids);
+
+        self
+    }
+
+    /// Roles of the member, along with their permissions.
+    pub fn member_roles(&self) -> &[(RoleId, Permissions)] {
+        &self.member_roles
+    }
+
+    /// Create a calculator with the same configuration that borrows the
+    /// member's roles.
+    pub fn calculator(&self) -> Calculator<'_> {
+        let mut calculator = self.config.clone();
+        calculator.member_roles = Cow::Borrowed(&self.member_roles);
+
+        calculator
+    }
+
+    /// Calculate the guild-level permissions of the member.
+    ///
+    /// Refer to [`Calculator::root`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::root`].
+    ///
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    pub fn root(&self) -> Result<Permissions, CalculatorError> {
+        self.calculator().root()
+    }
+
+    /// Create a calculator for the permissions of a role of the guild.
+    ///
+    /// Refer to [`Calculator::role`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::role`].
+    ///
+    /// [`Calculator::role`]: ../struct.Calculator.html#method.role
+    pub fn role(
+        &self,
+        guild_roles: &[(RoleId, Permissions)],
+        role_id: RoleId,
+    ) -> Result<RoleCalculator, CalculatorError> {
+        self.calculator().role(guild_roles, role_id)
+    }
+
+    /// Calculate the permissions of the member in a channel.
+    ///
+    /// Refer to [`Calculator::in_channel`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    pub fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Permissions, CalculatorError> {
+        self.calculator()
+            .in_channel(channel_type, channel_overwrites)
+    }
+
+    /// Calculate the permissions of the member in a channel, wrapped with
+    /// helpers for common checks.
+    ///
+    /// Refer to [`Calculator::in_channel_calculated`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::in_channel_calculated`]: ../struct.Calculator.html#method.in_channel_calculated
+    pub fn in_channel_calculated<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<CalculatedPermissions, CalculatorError> {
+        self.calculator()
+            .in_channel_calculated(channel_type, channel_overwrites)
+    }
+
+    /// Calculate the permissions of the member in a channel, depending on
+    /// whether the channel is visible to them.
+    ///
+    /// Refer to [`Calculator::in_channel_visibility`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::in_channel_visibility`]: ../struct.Calculator.html#method.in_channel_visibility
+    pub fn in_channel_visibility<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<ChannelPermissions, CalculatorError> {
+        self.calculator()
+            .in_channel_visibility(channel_type, channel_overwrites)
+    }
+
+    /// Calculate the permissions of the member in a channel along with the
+    /// permissions that were removed because they don't apply to the channel.
+    ///
+    /// Refer to [`Calculator::in_channel_masked`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::in_channel_masked`]: ../struct.Calculator.html#method.in_channel_masked
+    pub fn in_channel_masked<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Masked, CalculatorError> {
+        self.calculator()
+            .in_channel_masked(channel_type, channel_overwrites)
+    }
+
+    /// Calculate the permissions of the member in each of a set of channels.
+    ///
+    /// Refer to [`Calculator::in_channels`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channels`].
+    ///
+    /// [`Calculator::in_channels`]: ../struct.Calculator.html#method.in_channels
+    pub fn in_channels<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+    ) -> Result<HashMap<ChannelId, Permissions>, CalculatorError> {
+        self.calculator().in_channels(channels)
+    }
+
+    /// Find the channels of a set in which the member has permissions.
+    ///
+    /// Refer to [`Calculator::channels_with`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::channels_with`].
+    ///
+    /// [`Calculator::channels_with`]: ../struct.Calculator.html#method.channels_with
+    pub fn channels_with<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        channels: U,
+        permissions: Permissions,
+    ) -> Result<Vec<ChannelId>, CalculatorError> {
+        self.calculator().channels_with(channels, permissions)
+    }
+
+    /// Preview the permissions of the member in a channel as if it were synced
+    /// with its category.
+    ///
+    /// Refer to [`Calculator::in_channel_with_parent`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::in_channel_with_parent`]: ../struct.Calculator.html#method.in_channel_with_parent
+    pub fn in_channel_with_parent<'b, P: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        parent_overwrites: P,
+    ) -> Result<Permissions, CalculatorError> {
+        self.calculator()
+            .in_channel_with_parent(channel_type, parent_overwrites)
+    }
+
+    /// Calculate the permissions of the member in a channel along with where
+    /// each of them comes from.
+    ///
+    /// Refer to [`Calculator::explain`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::explain`]: ../struct.Calculator.html#method.explain
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    #[cfg(feature = "explain")]
+    pub fn explain<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Explanation, CalculatorError> {
+        self.calculator().explain(channel_type, channel_overwrites)
+    }
+
+    /// Calculate the permissions of the member in a channel, recording each
+    /// step of the calculation.
+    ///
+    /// Refer to [`Calculator::trace`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::trace`]: ../struct.Calculator.html#method.trace
+    #[cfg(feature = "explain")]
+    pub fn trace<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Trace, CalculatorError> {
+        self.calculator().trace(channel_type, channel_overwrites)
+    }
+
+    /// Find what's responsible for the member missing a permission in a
+    /// channel.
+    ///
+    /// Refer to [`Calculator::why_denied`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::why_denied`]: ../struct.Calculator.html#method.why_denied
+    #[cfg(feature = "explain")]
+    pub fn why_denied<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+        permission: Permissions,
+    ) -> Result<Option<Denial>, CalculatorError> {
+        self.calculator()
+            .why_denied(channel_type, channel_overwrites, permission)
+    }
+
+    /// Create a report of the guild-level permissions of the member.
+    ///
+    /// Refer to [`Calculator::report_root`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::root`].
+    ///
+    /// [`Calculator::report_root`]: ../struct.Calculator.html#method.report_root
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    #[cfg(feature = "explain")]
+    pub fn report_root(&self) -> Result<Report, CalculatorError> {
+        self.calculator().report_root()
+    }
+
+    /// Create a report of the permissions of the member in a channel.
+    ///
+    /// Refer to [`Calculator::report`] for more information.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Calculator::in_channel`].
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::report`]: ../struct.Calculator.html#method.report
+    #[cfg(feature = "explain")]
+    pub fn report<'b, U: IntoIterator<Item = &'b PermissionOverwrite> + Clone>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Result<Report, CalculatorError> {
+        self.calculator().report(channel_type, channel_overwrites)
+    }
+}
+
+impl From<Calculator<'_>> for OwnedCalculator {
+    fn from(calculator: Calculator<'_>) -> Self {
+        let member_roles = Arc::from(&*calculator.member_roles);
+        let mut config = Calculator::new(calculator.guild_id, calculator.user_id, &[]);
+        config.administrator_bypass = calculator.administrator_bypass;
+        config.communication_disabled_until = calculator.communication_disabled_until;
+        config.continue_on_missing_items = calculator.continue_on_missing_items;
+        config.data_version = calculator.data_version;
+        config.dependencies = calculator.dependencies;
+        config.owner_id = calculator.owner_id;
+        config.retain_guild_permissions = calculator.retain_guild_permissions;
+        config.validate_ids = calculator.validate_ids;
+
+        Self {
+            config,
+            member_roles,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OwnedCalculator;
+    use crate::Calculator;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(
+        OwnedCalculator: Clone,
+        Debug,
+        Eq,
+        From<Calculator<'static>>,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_owned_calculator() {
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::MANAGE_MESSAGES),
+        ];
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(3)),
+        }];
+        let calculator = Calculator::new(GuildId(1), UserId(3), member_roles).owner_id(UserId(4));
+        let owned =
+            OwnedCalculator::new(GuildId(1), UserId(3), member_roles.to_vec()).owner_id(UserId(4));
+
+        assert_eq!(calculator, owned.calculator());
+        assert_eq!(owned, OwnedCalculator::from(calculator.clone()));
+        assert_eq!(calculator.root(), owned.root());
+        assert_eq!(
+            calculator
+                .clone()
+                .in_channel(ChannelType::GuildText, overwrites),
+            owned.in_channel(ChannelType::GuildText, overwrites),
+        );
+        #[cfg(feature = "explain")]
+        {
+            assert_eq!(
+                calculator
+                    .clone()
+                    .explain(ChannelType::GuildText, overwrites),
+                owned.explain(ChannelType::GuildText, overwrites),
+            );
+            assert_eq!(
+                calculator.clone().why_denied(
+                    ChannelType::GuildText,
+                    overwrites,
+                    Permissions::CONNECT
+                ),
+                owned.why_denied(ChannelType::GuildText, overwrites, Permissions::CONNECT),
+            );
+        }
+        assert_eq!(member_roles, owned.member_roles());
+
+        let owner = OwnedCalculator::new(GuildId(1), UserId(4), Vec::new()).owner_id(UserId(4));
+        assert_eq!(Ok(Permissions::all()), owner.root());
+    }
+}
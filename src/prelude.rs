@@ -1,9 +1,20 @@
 //! A re-export of all the types that you'll need to use the calculator.
 
-pub use super::{Calculator, CalculatorError};
+#[cfg(feature = "ext")]
+pub use super::ext::MemberExt;
+pub use super::{
+    action::Action,
+    context::{ChannelContext, Context},
+    diff::PermissionDiff,
+    model::GuildMember,
+    owned::OwnedCalculator,
+    require::RequireError,
+    role::RoleCalculator,
+    Calculator, CalculatorError, CascadeMode, IdKind,
+};
 pub use std::collections::HashMap;
 pub use twilight_model::{
     channel::permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
     guild::Permissions,
-    id::{GuildId, RoleId, UserId},
+    id::{ChannelId, GuildId, RoleId, UserId},
 };
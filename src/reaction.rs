@@ -0,0 +1,106 @@
+//! Checking whether a member can react to messages.
+//!
+//! Adding a reaction depends on several permissions: View Channel and Read
+//! Message History are always required, Add Reactions is required unless
+//! someone has already reacted with the emoji, and Use External Emojis is
+//! required for custom emojis from other guilds.
+//!
+//! Super reactions have the same permission requirements as reactions.
+//! Whether the member is able to use super reactions at all, such as via
+//! Nitro, isn't a permission and isn't checked.
+
+use twilight_model::guild::Permissions;
+
+/// Where the emoji of a reaction is from.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EmojiSource {
+    /// Custom emoji from another guild.
+    External,
+    /// Custom emoji from the guild the message is in.
+    Guild,
+    /// Unicode emoji, which is available everywhere.
+    Unicode,
+}
+
+/// Permissions required to react to a message with an emoji.
+///
+/// `existing` is whether the message already has a reaction with the emoji,
+/// in which case the Add Reactions permission isn't required.
+pub fn required(emoji: EmojiSource, existing: bool) -> Permissions {
+    let mut required = Permissions::READ_MESSAGE_HISTORY | Permissions::VIEW_CHANNEL;
+
+    if !existing {
+        required.insert(Permissions::ADD_REACTIONS);
+    }
+
+    if emoji == EmojiSource::External {
+        required.insert(Permissions::USE_EXTERNAL_EMOJIS);
+    }
+
+    required
+}
+
+/// Whether a member with permissions in a channel can react to a message with
+/// an emoji.
+///
+/// Refer to [`required`] for the meaning of `existing`.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::reaction::{self, EmojiSource};
+///
+/// let permissions = Permissions::VIEW_CHANNEL | Permissions::READ_MESSAGE_HISTORY;
+///
+/// // Without Add Reactions, the member can only add to existing reactions.
+/// assert!(!reaction::can_react(permissions, EmojiSource::Unicode, false));
+/// assert!(reaction::can_react(permissions, EmojiSource::Unicode, true));
+///
+/// // Emojis from other guilds require Use External Emojis.
+/// assert!(!reaction::can_react(permissions, EmojiSource::External, true));
+/// ```
+///
+/// [`required`]: fn.required.html
+pub fn can_react(permissions: Permissions, emoji: EmojiSource, existing: bool) -> bool {
+    permissions.contains(required(emoji, existing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EmojiSource;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::Permissions;
+
+    assert_impl_all!(EmojiSource: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_required() {
+        let base = Permissions::READ_MESSAGE_HISTORY | Permissions::VIEW_CHANNEL;
+
+        assert_eq!(
+            base | Permissions::ADD_REACTIONS,
+            super::required(EmojiSource::Guild, false)
+        );
+        assert_eq!(base, super::required(EmojiSource::Unicode, true));
+        assert_eq!(
+            base | Permissions::ADD_REACTIONS | Permissions::USE_EXTERNAL_EMOJIS,
+            super::required(EmojiSource::External, false),
+        );
+    }
+
+    #[test]
+    fn test_can_react() {
+        assert!(super::can_react(
+            Permissions::all(),
+            EmojiSource::External,
+            false
+        ));
+        assert!(!super::can_react(
+            Permissions::ADD_REACTIONS | Permissions::VIEW_CHANNEL,
+            EmojiSource::Unicode,
+            false,
+        ));
+    }
+}
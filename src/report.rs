@@ -0,0 +1,283 @@
+//! Human-readable reports of calculated permissions.
+//!
+//! Support tickets and bot replies need more than a bitfield. A [`Report`]
+//! lists the permissions a member has and where each of them comes from, as
+//! well as the permissions they're missing and what's responsible for each of
+//! them, and renders them as plain text or Markdown.
+//!
+//! Reports are created via [`Calculator::report_root`] for guild-level
+//! permissions and [`Calculator::report`] for permissions in a channel.
+//!
+//! [`Calculator::report`]: ../struct.Calculator.html#method.report
+//! [`Calculator::report_root`]: ../struct.Calculator.html#method.report_root
+//! [`Report`]: struct.Report.html
+
+use super::{
+    denial::{Culprit, Denial},
+    explain::Source,
+    names,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter, Result as FmtResult, Write};
+use twilight_model::{channel::ChannelType, guild::Permissions};
+
+/// Format that a report is rendered in.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Format {
+    /// Markdown, as rendered by Discord.
+    ///
+    /// IDs and role names are placed in inline code, so mentions such as
+    /// `@everyone` don't notify anyone when the report is sent in a message.
+    Markdown,
+    /// Plain text.
+    Plain,
+}
+
+/// Granted and denied permissions of a member along with their sources.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Report {
+    /// Permissions that the member is missing and what's responsible for each
+    /// of them, in order of the permissions' bit positions.
+    ///
+    /// Reports of permissions in a channel only include permissions that can
+    /// be used in the channel.
+    pub denied: Vec<Denial>,
+    /// Permissions that the member has and where each of them comes from, in
+    /// order of the permissions' bit positions.
+    pub granted: Vec<(Permissions, Source)>,
+    /// Calculated permissions of the member.
+    pub permissions: Permissions,
+}
+
+impl Report {
+    /// Render the report in a format.
+    ///
+    /// The [`Display`] implementation renders the report as
+    /// [`Format::Plain`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use twilight_model::{
+    ///     guild::Permissions,
+    ///     id::{GuildId, RoleId, UserId},
+    /// };
+    /// use twilight_permission_calculator::{report::Format, Calculator};
+    ///
+    /// let member_roles = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+    /// let report = Calculator::new(GuildId(1), UserId(2), member_roles).report_root()?;
+    ///
+    /// let markdown = report.render(Format::Markdown);
+    ///
+    /// assert!(markdown.starts_with("**Granted**\n- View Channel: granted to the `@everyone` role\n"));
+    /// # Ok(()) }
+    /// ```
+    ///
+    /// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+    /// [`Format::Plain`]: enum.Format.html#variant.Plain
+    pub fn render(&self, format: Format) -> String {
+        let mut out = String::new();
+        let heading = |out: &mut String, title: &str| match format {
+            Format::Markdown => {
+                let _ = writeln!(out, "**{}**", title);
+            }
+            Format::Plain => {
+                let _ = writeln!(out, "{}:", title);
+            }
+        };
+
+        heading(&mut out, "Granted");
+
+        for (permission, source) in &self.granted {
+            let _ = writeln!(
+                out,
+                "- {}: {}",
+                name(*permission),
+                describe_source(*source, format),
+            );
+        }
+
+        if self.granted.is_empty() {
+            out.push_str("- None\n");
+        }
+
+        out.push('\n');
+        heading(&mut out, "Denied");
+
+        for denial in &self.denied {
+            let culprit = describe_culprit(denial.culprit, format);
+
+            if denial.denied == denial.permission {
+                let _ = writeln!(out, "- {}: {}", name(denial.permission), culprit);
+            } else {
+                let _ = writeln!(
+                    out,
+                    "- {}: {} is {}",
+                    name(denial.permission),
+                    name(denial.denied),
+                    culprit,
+                );
+            }
+        }
+
+        if self.denied.is_empty() {
+            out.push_str("- None\n");
+        }
+
+        out
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.render(Format::Plain))
+    }
+}
+
+/// Name of a permission, falling back to its bits if it's unknown.
+fn name(permission: Permissions) -> String {
+    names::get(permission).map_or_else(
+        || format!("permission {:#x}", permission.bits()),
+        ToOwned::to_owned,
+    )
+}
+
+/// Format an ID or mention so that it's rendered literally.
+fn literal(value: &str, format: Format) -> String {
+    match format {
+        Format::Markdown => format!("`{}`", value),
+        Format::Plain => value.to_owned(),
+    }
+}
+
+fn describe_source(source: Source, format: Format) -> String {
+    match source {
+        Source::Administrator => "member is an administrator".to_owned(),
+        Source::Everyone => format!("granted to the {} role", literal("@everyone", format)),
+        Source::EveryoneOverwrite => format!(
+            "allowed by the overwrite of the {} role",
+            literal("@everyone", format)
+        ),
+        Source::MemberOverwrite => "allowed by the overwrite of the member".to_owned(),
+        Source::Owner => "member owns the guild".to_owned(),
+        Source::Role { role_id } => {
+            format!("granted to role {}", literal(&role_id.to_string(), format))
+        }
+        Source::RoleOverwrite { role_id } => format!(
+            "allowed by the overwrite of role {}",
+            literal(&role_id.to_string(), format)
+        ),
+    }
+}
+
+fn describe_culprit(culprit: Culprit, format: Format) -> String {
+    match culprit {
+        Culprit::ChannelType { kind } => {
+            format!("can't be used in {} channels", channel_type_name(kind))
+        }
+        Culprit::EveryoneOverwrite => format!(
+            "denied by the overwrite of the {} role",
+            literal("@everyone", format)
+        ),
+        Culprit::GuildPermission => "can't be used in channels".to_owned(),
+        Culprit::MemberOverwrite => "denied by the overwrite of the member".to_owned(),
+        Culprit::NotGranted => "not granted by any role".to_owned(),
+        Culprit::RoleOverwrite { role_id } => format!(
+            "denied by the overwrite of role {}",
+            literal(&role_id.to_string(), format)
+        ),
+        Culprit::TimedOut => "member is timed out".to_owned(),
+    }
+}
+
+fn channel_type_name(kind: ChannelType) -> &'static str {
+    match kind {
+        ChannelType::GuildCategory => "category",
+        ChannelType::GuildNews => "news",
+        ChannelType::GuildStageVoice => "stage",
+        ChannelType::GuildStore => "store",
+        ChannelType::GuildText => "text",
+        ChannelType::GuildVoice => "voice",
+        ChannelType::Group | ChannelType::Private => "private",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Format, Report};
+    use crate::{
+        denial::{Culprit, Denial},
+        explain::Source,
+    };
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{channel::ChannelType, guild::Permissions, id::RoleId};
+
+    assert_fields!(Report: denied, granted, permissions);
+    assert_impl_all!(Format: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Report: Clone, Debug, Display, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_render() {
+        let report = Report {
+            denied: vec![
+                Denial {
+                    culprit: Culprit::MemberOverwrite,
+                    denied: Permissions::SEND_MESSAGES,
+                    permission: Permissions::ATTACH_FILES,
+                },
+                Denial {
+                    culprit: Culprit::ChannelType {
+                        kind: ChannelType::GuildText,
+                    },
+                    denied: Permissions::CONNECT,
+                    permission: Permissions::CONNECT,
+                },
+            ],
+            granted: vec![
+                (Permissions::VIEW_CHANNEL, Source::Everyone),
+                (
+                    Permissions::SEND_MESSAGES,
+                    Source::RoleOverwrite { role_id: RoleId(3) },
+                ),
+            ],
+            permissions: Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+        };
+
+        assert_eq!(
+            "Granted:\n\
+             - View Channel: granted to the @everyone role\n\
+             - Send Messages: allowed by the overwrite of role 3\n\
+             \n\
+             Denied:\n\
+             - Attach Files: Send Messages is denied by the overwrite of the member\n\
+             - Connect: can't be used in text channels\n",
+            report.to_string(),
+        );
+        assert_eq!(
+            "**Granted**\n\
+             - View Channel: granted to the `@everyone` role\n\
+             - Send Messages: allowed by the overwrite of role `3`\n\
+             \n\
+             **Denied**\n\
+             - Attach Files: Send Messages is denied by the overwrite of the member\n\
+             - Connect: can't be used in text channels\n",
+            report.render(Format::Markdown),
+        );
+
+        let empty = Report {
+            denied: Vec::new(),
+            granted: Vec::new(),
+            permissions: Permissions::empty(),
+        };
+        assert_eq!("Granted:\n- None\n\nDenied:\n- None\n", empty.to_string());
+    }
+}
@@ -0,0 +1,375 @@
+//! Requiring that members have permissions, reporting what they're missing.
+
+use super::{names, CalculatorError, PERMISSIONS_ROOT};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{guild::Permissions, id::ChannelId};
+
+/// Permissions a member is missing in a channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MissingInChannel {
+    /// ID of the channel.
+    pub channel_id: ChannelId,
+    /// Required permissions that the member doesn't have in the channel.
+    pub missing: Permissions,
+}
+
+impl MissingInChannel {
+    /// Create the missing permissions of a channel if the calculated
+    /// permissions don't meet the requirement.
+    ///
+    /// Required guild-level permissions are checked against the root
+    /// permissions, while the rest are checked against the channel
+    /// permissions.
+    pub(super) fn new(
+        channel_id: ChannelId,
+        root: Permissions,
+        permissions: Permissions,
+        required: Permissions,
+    ) -> Option<Self> {
+        let required_root = required & PERMISSIONS_ROOT;
+        let missing = (required_root - root) | (required - required_root - permissions);
+
+        if missing.is_empty() {
+            None
+        } else {
+            Some(Self {
+                channel_id,
+                missing,
+            })
+        }
+    }
+}
+
+/// Permissions a member is missing in a channel for one of its purposes.
+///
+/// Created via [`Calculator::self_audit`].
+///
+/// [`Calculator::self_audit`]: ../struct.Calculator.html#method.self_audit
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Deficiency<P> {
+    /// ID of the channel.
+    pub channel_id: ChannelId,
+    /// Required permissions that the member doesn't have in the channel.
+    pub missing: Permissions,
+    /// Purpose of the channel.
+    pub purpose: P,
+}
+
+/// Consolidated report of the channels a member is missing required
+/// permissions in.
+///
+/// Created via [`Calculator::self_audit`].
+///
+/// [`Calculator::self_audit`]: ../struct.Calculator.html#method.self_audit
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SelfAuditReport<P> {
+    /// Channels missing permissions, in the order they were given.
+    pub deficiencies: Vec<Deficiency<P>>,
+}
+
+impl<P> SelfAuditReport<P> {
+    /// Whether the member has every required permission in every channel.
+    pub fn is_ok(&self) -> bool {
+        self.deficiencies.is_empty()
+    }
+}
+
+/// Error returned when a member doesn't meet a permission requirement.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum RequireError {
+    /// Permissions couldn't be calculated.
+    Calculating {
+        /// Reason the permissions couldn't be calculated.
+        source: CalculatorError,
+    },
+    /// Member is missing required permissions in one or more channels.
+    MissingPermissions {
+        /// Channels in which permissions are missing, in the order they were
+        /// given.
+        channels: Vec<MissingInChannel>,
+    },
+    /// Member is missing required permissions in a channel.
+    ///
+    /// Returned by [`Calculator::require_in_channel`].
+    ///
+    /// [`Calculator::require_in_channel`]: ../struct.Calculator.html#method.require_in_channel
+    MissingInChannel {
+        /// ID of the channel.
+        channel_id: ChannelId,
+        /// Required permissions that the member doesn't have in the channel.
+        missing: Permissions,
+    },
+}
+
+impl Display for RequireError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Calculating { .. } => f.write_str("permissions couldn't be calculated"),
+            Self::MissingPermissions { channels } => f.write_fmt(format_args!(
+                "member is missing required permissions in {} channel(s)",
+                channels.len()
+            )),
+            Self::MissingInChannel {
+                channel_id,
+                missing,
+            } => {
+                f.write_fmt(format_args!(
+                    "member is missing required permissions in channel {}: ",
+                    channel_id
+                ))?;
+
+                names::write_list(f, *missing)
+            }
+        }
+    }
+}
+
+impl Error for RequireError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Calculating { source } => Some(source),
+            Self::MissingInChannel { .. } | Self::MissingPermissions { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Deficiency, MissingInChannel, RequireError, SelfAuditReport};
+    use crate::{Calculator, CalculatorError};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(Deficiency<()>: channel_id, missing, purpose);
+    assert_fields!(MissingInChannel: channel_id, missing);
+    assert_fields!(RequireError::Calculating: source);
+    assert_fields!(RequireError::MissingInChannel: channel_id, missing);
+    assert_fields!(RequireError::MissingPermissions: channels);
+    assert_fields!(SelfAuditReport<()>: deficiencies);
+    assert_impl_all!(Deficiency<()>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(MissingInChannel: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(SelfAuditReport<()>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        RequireError: Clone,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    fn deny_send(role_id: RoleId) -> [PermissionOverwrite; 1] {
+        [PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(role_id),
+        }]
+    }
+
+    #[test]
+    fn test_require_in_all() {
+        let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES | Permissions::SPEAK)];
+        let locked = deny_send(RoleId(1));
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        let open = vec![
+            (ChannelId(3), ChannelType::GuildText, &[][..]),
+            (ChannelId(4), ChannelType::GuildText, &[][..]),
+        ];
+        assert!(calculator
+            .require_in_all(open, Permissions::SEND_MESSAGES)
+            .is_ok());
+
+        let channels = vec![
+            (ChannelId(3), ChannelType::GuildText, &locked[..]),
+            (ChannelId(4), ChannelType::GuildText, &[][..]),
+            (ChannelId(5), ChannelType::GuildVoice, &[][..]),
+        ];
+        let required = Permissions::SEND_MESSAGES | Permissions::SPEAK;
+
+        assert_eq!(
+            Err(RequireError::MissingPermissions {
+                channels: vec![
+                    MissingInChannel {
+                        channel_id: ChannelId(3),
+                        missing: Permissions::SEND_MESSAGES | Permissions::SPEAK,
+                    },
+                    MissingInChannel {
+                        channel_id: ChannelId(4),
+                        missing: Permissions::SPEAK,
+                    },
+                    MissingInChannel {
+                        channel_id: ChannelId(5),
+                        missing: Permissions::SEND_MESSAGES,
+                    },
+                ],
+            }),
+            calculator.require_in_all(channels, required),
+        );
+    }
+
+    #[test]
+    fn test_require_in_channel() {
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (
+                RoleId(2),
+                Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            ),
+        ];
+        let locked = deny_send(RoleId(2));
+        let calculator = Calculator::new(GuildId(1), UserId(4), member_roles);
+        let required = Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES;
+
+        assert!(calculator
+            .require_in_channel((ChannelId(3), ChannelType::GuildText, &[]), required)
+            .is_ok());
+
+        // Embed Links is implicitly denied along with Send Messages.
+        let result = calculator
+            .require_in_channel((ChannelId(3), ChannelType::GuildText, &locked), required);
+        assert_eq!(
+            Err(RequireError::MissingInChannel {
+                channel_id: ChannelId(3),
+                missing: Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            }),
+            result,
+        );
+        assert_eq!(
+            "member is missing required permissions in channel 3: Send Messages, Embed Links",
+            result.unwrap_err().to_string(),
+        );
+    }
+
+    #[test]
+    fn test_require_in_any() {
+        let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES)];
+        let locked = deny_send(RoleId(1));
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+
+        let channels = vec![
+            (ChannelId(3), ChannelType::GuildText, &locked[..]),
+            (ChannelId(4), ChannelType::GuildText, &[][..]),
+        ];
+        assert_eq!(
+            Ok(ChannelId(4)),
+            calculator.require_in_any(channels, Permissions::SEND_MESSAGES),
+        );
+
+        let channels = vec![(ChannelId(3), ChannelType::GuildText, &locked[..])];
+        assert_eq!(
+            Err(RequireError::MissingPermissions {
+                channels: vec![MissingInChannel {
+                    channel_id: ChannelId(3),
+                    missing: Permissions::SEND_MESSAGES,
+                }],
+            }),
+            calculator.require_in_any(channels, Permissions::SEND_MESSAGES),
+        );
+    }
+
+    /// Test that required guild-level permissions are checked at the guild
+    /// level rather than always being missing in channels.
+    #[test]
+    fn test_require_root_permissions() {
+        let member_roles = &[(
+            RoleId(1),
+            Permissions::BAN_MEMBERS | Permissions::SEND_MESSAGES,
+        )];
+        let calculator = Calculator::new(GuildId(1), UserId(2), member_roles);
+        let channels = vec![(ChannelId(3), ChannelType::GuildText, &[][..])];
+        let required = Permissions::BAN_MEMBERS | Permissions::SEND_MESSAGES;
+
+        assert!(calculator.require_in_all(channels, required).is_ok());
+
+        let channels = vec![(ChannelId(3), ChannelType::GuildText, &[][..])];
+        let required = Permissions::KICK_MEMBERS | Permissions::SEND_MESSAGES;
+
+        assert_eq!(
+            Err(RequireError::MissingPermissions {
+                channels: vec![MissingInChannel {
+                    channel_id: ChannelId(3),
+                    missing: Permissions::KICK_MEMBERS,
+                }],
+            }),
+            calculator.require_in_all(channels, required),
+        );
+    }
+
+    #[test]
+    fn test_self_audit() {
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES | Permissions::CONNECT),
+        ];
+        let locked = deny_send(RoleId(2));
+        let requirements = &[
+            ("logging", Permissions::SEND_MESSAGES),
+            ("logging", Permissions::VIEW_CHANNEL),
+            ("moderation", Permissions::BAN_MEMBERS),
+            ("music", Permissions::CONNECT),
+        ];
+        let channels = vec![
+            ("logging", ChannelId(3), ChannelType::GuildText, &[][..]),
+            ("logging", ChannelId(4), ChannelType::GuildText, &locked[..]),
+            ("moderation", ChannelId(5), ChannelType::GuildText, &[][..]),
+            ("music", ChannelId(6), ChannelType::GuildVoice, &[][..]),
+            ("unknown", ChannelId(7), ChannelType::GuildVoice, &[][..]),
+        ];
+
+        let report = Calculator::new(GuildId(1), UserId(8), member_roles)
+            .self_audit(requirements, channels)
+            .unwrap();
+
+        assert!(!report.is_ok());
+        assert_eq!(
+            vec![
+                Deficiency {
+                    channel_id: ChannelId(4),
+                    missing: Permissions::SEND_MESSAGES,
+                    purpose: "logging",
+                },
+                Deficiency {
+                    channel_id: ChannelId(5),
+                    missing: Permissions::BAN_MEMBERS,
+                    purpose: "moderation",
+                },
+            ],
+            report.deficiencies,
+        );
+    }
+
+    #[test]
+    fn test_require_calculating_error() {
+        let calculator = Calculator::new(GuildId(1), UserId(2), &[]);
+        let result = calculator.require_in_all(Vec::new(), Permissions::SEND_MESSAGES);
+
+        assert_eq!(
+            Err(RequireError::Calculating {
+                source: CalculatorError::EveryoneRoleMissing {
+                    guild_id: GuildId(1)
+                },
+            }),
+            result,
+        );
+    }
+}
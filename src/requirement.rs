@@ -0,0 +1,287 @@
+//! Composable requirements of permissions.
+//!
+//! Command frameworks often need more than "has all of these permissions":
+//! a command may be usable by members who can either manage messages or
+//! moderate members, or only by members who aren't administrators. A
+//! [`Requirement`] combines permissions with [`all`], [`any`], and [`not`],
+//! and [`Requirement::evaluate`] reports which parts of it weren't met.
+//!
+//! [`Requirement`]: enum.Requirement.html
+//! [`Requirement::evaluate`]: enum.Requirement.html#method.evaluate
+//! [`all`]: fn.all.html
+//! [`any`]: fn.any.html
+//! [`not`]: fn.not.html
+
+use twilight_model::guild::Permissions;
+
+/// Requirement of permissions, which may be combined with other
+/// requirements.
+///
+/// # Examples
+///
+/// Require that a member can either manage messages or both kick and ban
+/// members, but isn't an administrator:
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::requirement::{self, Requirement};
+///
+/// let requirement = requirement::all(vec![
+///     requirement::any(vec![
+///         Requirement::from(Permissions::MANAGE_MESSAGES),
+///         Requirement::from(Permissions::BAN_MEMBERS | Permissions::KICK_MEMBERS),
+///     ]),
+///     requirement::not(Permissions::ADMINISTRATOR),
+/// ]);
+///
+/// assert!(requirement.evaluate(Permissions::MANAGE_MESSAGES).is_met());
+///
+/// let evaluation = requirement.evaluate(Permissions::KICK_MEMBERS);
+/// assert!(!evaluation.is_met());
+/// assert_eq!(
+///     &[requirement::any(vec![
+///         Requirement::from(Permissions::MANAGE_MESSAGES),
+///         Requirement::from(Permissions::BAN_MEMBERS),
+///     ])],
+///     evaluation.unmet(),
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Requirement {
+    /// Every requirement must be met.
+    ///
+    /// An empty list of requirements is always met.
+    All(Vec<Requirement>),
+    /// At least one requirement must be met.
+    ///
+    /// An empty list of requirements is never met.
+    Any(Vec<Requirement>),
+    /// Requirement must not be met.
+    Not(Box<Requirement>),
+    /// Every one of the permissions must be present.
+    Permissions(Permissions),
+}
+
+impl Requirement {
+    /// Evaluate the requirement against permissions.
+    ///
+    /// The returned evaluation contains the unmet sub-requirements, which are
+    /// narrowed down to what's missing: unmet permissions only contain the
+    /// missing permissions, and unmet [`All`] requirements are flattened into
+    /// their unmet sub-requirements.
+    ///
+    /// [`All`]: #variant.All
+    pub fn evaluate(&self, permissions: Permissions) -> Evaluation {
+        let mut unmet = Vec::new();
+        self.unmet(permissions, &mut unmet);
+
+        Evaluation { unmet }
+    }
+
+    /// Whether the requirement is met by permissions.
+    pub fn is_met(&self, permissions: Permissions) -> bool {
+        match self {
+            Self::All(requirements) => requirements.iter().all(|r| r.is_met(permissions)),
+            Self::Any(requirements) => requirements.iter().any(|r| r.is_met(permissions)),
+            Self::Not(requirement) => !requirement.is_met(permissions),
+            Self::Permissions(required) => permissions.contains(*required),
+        }
+    }
+
+    /// Push the unmet sub-requirements onto a list.
+    fn unmet(&self, permissions: Permissions, unmet: &mut Vec<Requirement>) {
+        match self {
+            Self::All(requirements) => {
+                for requirement in requirements {
+                    requirement.unmet(permissions, unmet);
+                }
+            }
+            Self::Any(requirements) => {
+                if self.is_met(permissions) {
+                    return;
+                }
+
+                let alternatives = requirements
+                    .iter()
+                    .map(|requirement| {
+                        let mut inner = Vec::new();
+                        requirement.unmet(permissions, &mut inner);
+
+                        if inner.len() == 1 {
+                            inner.remove(0)
+                        } else {
+                            Self::All(inner)
+                        }
+                    })
+                    .collect();
+
+                unmet.push(Self::Any(alternatives));
+            }
+            Self::Not(requirement) => {
+                if requirement.is_met(permissions) {
+                    unmet.push(self.clone());
+                }
+            }
+            Self::Permissions(required) => {
+                let missing = *required - permissions;
+
+                if !missing.is_empty() {
+                    unmet.push(Self::Permissions(missing));
+                }
+            }
+        }
+    }
+}
+
+impl From<Permissions> for Requirement {
+    fn from(permissions: Permissions) -> Self {
+        Self::Permissions(permissions)
+    }
+}
+
+/// Result of evaluating a [`Requirement`] against permissions.
+///
+/// [`Requirement`]: enum.Requirement.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Evaluation {
+    unmet: Vec<Requirement>,
+}
+
+impl Evaluation {
+    /// Whether the requirement was met.
+    pub fn is_met(&self) -> bool {
+        self.unmet.is_empty()
+    }
+
+    /// Sub-requirements that weren't met, which are all required.
+    pub fn unmet(&self) -> &[Requirement] {
+        &self.unmet
+    }
+
+    /// Permissions that were missing from the unmet sub-requirements that
+    /// only require permissions.
+    ///
+    /// Unmet [`Any`] and [`Not`] requirements aren't included, since there's
+    /// no single set of permissions that's missing for them.
+    ///
+    /// [`Any`]: enum.Requirement.html#variant.Any
+    /// [`Not`]: enum.Requirement.html#variant.Not
+    pub fn missing(&self) -> Permissions {
+        self.unmet.iter().fold(
+            Permissions::empty(),
+            |missing, requirement| match requirement {
+                Requirement::Permissions(permissions) => missing | *permissions,
+                _ => missing,
+            },
+        )
+    }
+}
+
+/// Require that every one of the requirements is met.
+pub fn all<I: IntoIterator<Item = Requirement>>(requirements: I) -> Requirement {
+    Requirement::All(requirements.into_iter().collect())
+}
+
+/// Require that at least one of the requirements is met.
+pub fn any<I: IntoIterator<Item = Requirement>>(requirements: I) -> Requirement {
+    Requirement::Any(requirements.into_iter().collect())
+}
+
+/// Require that a requirement isn't met.
+pub fn not(requirement: impl Into<Requirement>) -> Requirement {
+    Requirement::Not(Box::new(requirement.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all, any, not, Evaluation, Requirement};
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::Permissions;
+
+    assert_impl_all!(Evaluation: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Requirement: Clone, Debug, Eq, From<Permissions>, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_permissions() {
+        let requirement = Requirement::from(Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES);
+
+        assert!(requirement
+            .evaluate(Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES)
+            .is_met());
+
+        let evaluation = requirement.evaluate(Permissions::SEND_MESSAGES);
+        assert!(!evaluation.is_met());
+        assert_eq!(
+            &[Requirement::Permissions(Permissions::EMBED_LINKS)],
+            evaluation.unmet()
+        );
+        assert_eq!(Permissions::EMBED_LINKS, evaluation.missing());
+    }
+
+    #[test]
+    fn test_all() {
+        let requirement = all(vec![
+            Requirement::from(Permissions::SEND_MESSAGES),
+            Requirement::from(Permissions::EMBED_LINKS),
+            not(Permissions::ADMINISTRATOR),
+        ]);
+
+        let evaluation = requirement.evaluate(Permissions::ADMINISTRATOR);
+        assert_eq!(
+            &[
+                Requirement::Permissions(Permissions::SEND_MESSAGES),
+                Requirement::Permissions(Permissions::EMBED_LINKS),
+                not(Permissions::ADMINISTRATOR),
+            ],
+            evaluation.unmet()
+        );
+        assert_eq!(
+            Permissions::EMBED_LINKS | Permissions::SEND_MESSAGES,
+            evaluation.missing()
+        );
+
+        assert!(all(Vec::new()).evaluate(Permissions::empty()).is_met());
+    }
+
+    #[test]
+    fn test_any() {
+        let requirement = any(vec![
+            Requirement::from(Permissions::MANAGE_MESSAGES),
+            all(vec![
+                Requirement::from(Permissions::BAN_MEMBERS),
+                Requirement::from(Permissions::KICK_MEMBERS),
+            ]),
+        ]);
+
+        assert!(requirement
+            .evaluate(Permissions::BAN_MEMBERS | Permissions::KICK_MEMBERS)
+            .is_met());
+
+        let evaluation = requirement.evaluate(Permissions::empty());
+        assert_eq!(
+            &[any(vec![
+                Requirement::Permissions(Permissions::MANAGE_MESSAGES),
+                all(vec![
+                    Requirement::Permissions(Permissions::BAN_MEMBERS),
+                    Requirement::Permissions(Permissions::KICK_MEMBERS),
+                ]),
+            ])],
+            evaluation.unmet()
+        );
+        assert!(evaluation.missing().is_empty());
+
+        assert!(!any(Vec::new()).evaluate(Permissions::all()).is_met());
+    }
+
+    #[test]
+    fn test_not() {
+        let requirement = not(not(Permissions::VIEW_CHANNEL));
+
+        assert!(requirement.evaluate(Permissions::VIEW_CHANNEL).is_met());
+        assert_eq!(
+            &[not(not(Permissions::VIEW_CHANNEL))],
+            requirement.evaluate(Permissions::empty()).unmet()
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Classification of permissions by how much damage they can do in the wrong
+//! hands.
+//!
+//! The classification is a canonical table meant for consistently presenting
+//! permissions, such as color coding permission lists in a dashboard or
+//! flagging dangerous grants in an audit. Permissions that Discord requires
+//! two-factor authentication for when the guild enables it, such as Ban
+//! Members and Manage Roles, are at least [`RiskLevel::Elevated`].
+//!
+//! [`RiskLevel::Elevated`]: enum.RiskLevel.html#variant.Elevated
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use twilight_model::guild::Permissions;
+
+/// Permissions that allow taking over or destroying a guild, or which lead to
+/// other permissions.
+pub const DANGEROUS: Permissions = Permissions::from_bits_truncate(
+    Permissions::ADMINISTRATOR.bits()
+        | Permissions::BAN_MEMBERS.bits()
+        | Permissions::KICK_MEMBERS.bits()
+        | Permissions::MANAGE_CHANNELS.bits()
+        | Permissions::MANAGE_GUILD.bits()
+        | Permissions::MANAGE_ROLES.bits()
+        | Permissions::MANAGE_WEBHOOKS.bits(),
+);
+
+/// Permissions that allow moderating other members or disrupting a guild,
+/// but not taking it over.
+pub const ELEVATED: Permissions = Permissions::from_bits_truncate(
+    Permissions::DEAFEN_MEMBERS.bits()
+        | Permissions::MANAGE_EMOJIS.bits()
+        | Permissions::MANAGE_MESSAGES.bits()
+        | Permissions::MANAGE_NICKNAMES.bits()
+        | Permissions::MENTION_EVERYONE.bits()
+        | Permissions::MOVE_MEMBERS.bits()
+        | Permissions::MUTE_MEMBERS.bits()
+        | Permissions::PRIORITY_SPEAKER.bits()
+        | Permissions::VIEW_AUDIT_LOG.bits()
+        | Permissions::VIEW_GUILD_INSIGHTS.bits(),
+);
+
+/// How much damage a permission can do in the wrong hands.
+///
+/// Levels are ordered from least to most risky.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum RiskLevel {
+    /// Permission is part of regular participation in a guild, such as Send
+    /// Messages or Connect.
+    Benign,
+    /// Permission allows moderating other members or disrupting the guild.
+    Elevated,
+    /// Permission allows taking over or destroying the guild.
+    Dangerous,
+}
+
+impl RiskLevel {
+    /// All permissions classified at this level.
+    pub fn permissions(self) -> Permissions {
+        match self {
+            Self::Benign => Permissions::all() - DANGEROUS - ELEVATED,
+            Self::Elevated => ELEVATED,
+            Self::Dangerous => DANGEROUS,
+        }
+    }
+}
+
+impl Display for RiskLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            Self::Benign => "benign",
+            Self::Elevated => "elevated",
+            Self::Dangerous => "dangerous",
+        })
+    }
+}
+
+/// Classify the risk of a set of permissions, which is the highest risk of
+/// any of the permissions in it.
+///
+/// An empty set is [`RiskLevel::Benign`].
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::risk::{self, RiskLevel};
+///
+/// assert_eq!(RiskLevel::Benign, risk::risk(Permissions::SEND_MESSAGES));
+/// assert_eq!(
+///     RiskLevel::Dangerous,
+///     risk::risk(Permissions::SEND_MESSAGES | Permissions::MANAGE_ROLES),
+/// );
+/// ```
+///
+/// [`RiskLevel::Benign`]: enum.RiskLevel.html#variant.Benign
+pub fn risk(permissions: Permissions) -> RiskLevel {
+    if permissions.intersects(DANGEROUS) {
+        RiskLevel::Dangerous
+    } else if permissions.intersects(ELEVATED) {
+        RiskLevel::Elevated
+    } else {
+        RiskLevel::Benign
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RiskLevel, DANGEROUS, ELEVATED};
+    use static_assertions::assert_impl_all;
+    use std::{
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::guild::Permissions;
+
+    assert_impl_all!(
+        RiskLevel: Clone,
+        Copy,
+        Debug,
+        Display,
+        Eq,
+        Hash,
+        Ord,
+        PartialEq,
+        PartialOrd,
+        Send,
+        Sync
+    );
+
+    /// Test that every permission is classified at exactly one level.
+    #[test]
+    fn test_levels_partition() {
+        assert!(!DANGEROUS.intersects(ELEVATED));
+
+        let levels = [RiskLevel::Benign, RiskLevel::Elevated, RiskLevel::Dangerous];
+        let all = levels
+            .iter()
+            .fold(Permissions::empty(), |all, level| all | level.permissions());
+
+        assert_eq!(Permissions::all(), all);
+    }
+
+    #[test]
+    fn test_risk() {
+        assert_eq!(RiskLevel::Benign, super::risk(Permissions::empty()));
+        assert_eq!(RiskLevel::Benign, super::risk(Permissions::CONNECT));
+        assert_eq!(
+            RiskLevel::Elevated,
+            super::risk(Permissions::CONNECT | Permissions::MOVE_MEMBERS)
+        );
+        assert_eq!(RiskLevel::Dangerous, super::risk(Permissions::all()));
+        assert!(RiskLevel::Benign < RiskLevel::Elevated);
+        assert!(RiskLevel::Elevated < RiskLevel::Dangerous);
+    }
+}
@@ -0,0 +1,756 @@
+//! Calculating the permissions of roles rather than of members.
+//!
+//! The permissions of a role are those that a member with only the
+//! `@everyone` role and that role would have. [`RoleCalculator`] calculates
+//! them for one role in any number of channels.
+//!
+//! [`RoleCalculator`]: struct.RoleCalculator.html
+
+use super::{
+    channel_permissions, channel_permissions_traced, dependency::Dependencies,
+    diff::PermissionDiff, CalculatorError, CascadeMode, ChannelOptions,
+};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId},
+};
+
+/// Calculate the permissions of a role, both at the guild level and in
+/// channels.
+///
+/// Channel permissions are calculated like Discord does for a member with
+/// only the `@everyone` role and the role: the `@everyone` role's overwrite
+/// is applied first and the role's overwrite after it, so the role's
+/// overwrite takes precedence. Overwrites for other roles and for members
+/// don't affect the role.
+///
+/// Like [`Calculator`], administrators have every permission in every channel
+/// by default and denying a permission implicitly denies the permissions that
+/// depend on it. Calculators created via [`Calculator::role`] are configured
+/// like the calculator they were created from.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::role::RoleCalculator;
+///
+/// let everyone = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+/// let overwrites = &[
+///     PermissionOverwrite {
+///         allow: Permissions::empty(),
+///         deny: Permissions::SEND_MESSAGES,
+///         kind: PermissionOverwriteType::Role(RoleId(1)),
+///     },
+///     PermissionOverwrite {
+///         allow: Permissions::SEND_MESSAGES,
+///         deny: Permissions::empty(),
+///         kind: PermissionOverwriteType::Role(RoleId(2)),
+///     },
+/// ];
+///
+/// let moderators = RoleCalculator::new(GuildId(1), everyone, (RoleId(2), Permissions::KICK_MEMBERS));
+///
+/// assert_eq!(everyone | Permissions::KICK_MEMBERS, moderators.root());
+/// assert_eq!(
+///     everyone,
+///     moderators.in_channel(ChannelType::GuildText, overwrites),
+/// );
+/// ```
+///
+/// [`Calculator`]: ../struct.Calculator.html
+/// [`Calculator::role`]: ../struct.Calculator.html#method.role
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the role calculator isn't useful if you don't calculate with it"]
+pub struct RoleCalculator {
+    administrator_bypass: bool,
+    dependencies: Dependencies,
+    everyone: Permissions,
+    guild_id: GuildId,
+    retain_guild_permissions: bool,
+    role: (RoleId, Permissions),
+}
+
+impl RoleCalculator {
+    /// Create a calculator for a role of a guild.
+    ///
+    /// `everyone` is the guild-level permissions of the `@everyone` role and
+    /// `role` is a tuple of the role's ID and guild-level permissions.
+    pub const fn new(
+        guild_id: GuildId,
+        everyone: Permissions,
+        role: (RoleId, Permissions),
+    ) -> Self {
+        Self {
+            administrator_bypass: true,
+            dependencies: Dependencies::PRACTICAL,
+            everyone,
+            guild_id,
+            retain_guild_permissions: false,
+            role,
+        }
+    }
+
+    /// Create a calculator for a role from a guild's roles.
+    ///
+    /// Guild roles are given as tuples of the role's ID and permissions, and
+    /// must include the `@everyone` role, which has the same ID as the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone`
+    /// role isn't in the guild's roles, and [`CalculatorError::RoleMissing`]
+    /// if the role isn't.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    /// [`CalculatorError::RoleMissing`]: ../enum.CalculatorError.html#variant.RoleMissing
+    pub fn from_guild_roles(
+        guild_id: GuildId,
+        guild_roles: &[(RoleId, Permissions)],
+        role_id: RoleId,
+    ) -> Result<Self, CalculatorError> {
+        let find = |id: RoleId| {
+            guild_roles
+                .iter()
+                .find(|(role_id, _)| *role_id == id)
+                .map(|(_, permissions)| *permissions)
+        };
+
+        let everyone =
+            find(RoleId(guild_id.0)).ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+        let permissions = find(role_id).ok_or(CalculatorError::RoleMissing { role_id })?;
+
+        Ok(Self::new(guild_id, everyone, (role_id, permissions)))
+    }
+
+    /// Configure whether administrators have every permission in every
+    /// channel.
+    ///
+    /// Refer to [`Calculator::administrator_bypass`] for more information.
+    ///
+    /// [`Calculator::administrator_bypass`]: ../struct.Calculator.html#method.administrator_bypass
+    pub fn administrator_bypass(mut self, administrator_bypass: bool) -> Self {
+        self.administrator_bypass = administrator_bypass;
+
+        self
+    }
+
+    /// Configure whether denying a permission in a channel implicitly denies
+    /// the permissions that depend on it.
+    ///
+    /// Refer to [`Calculator::cascade_mode`] for more information.
+    ///
+    /// [`Calculator::cascade_mode`]: ../struct.Calculator.html#method.cascade_mode
+    pub fn cascade_mode(self, cascade_mode: CascadeMode) -> Self {
+        self.dependencies(cascade_mode.dependencies())
+    }
+
+    /// Configure which permissions are implicitly denied in a channel when the
+    /// permissions they depend on are denied.
+    ///
+    /// Refer to [`Calculator::dependencies`] for more information.
+    ///
+    /// [`Calculator::dependencies`]: ../struct.Calculator.html#method.dependencies
+    pub fn dependencies(mut self, dependencies: Dependencies) -> Self {
+        self.dependencies = dependencies;
+
+        self
+    }
+
+    /// Configure whether to keep guild-level permissions in the permissions
+    /// calculated in channels.
+    ///
+    /// Refer to [`Calculator::retain_guild_permissions`] for more information.
+    ///
+    /// [`Calculator::retain_guild_permissions`]: ../struct.Calculator.html#method.retain_guild_permissions
+    pub fn retain_guild_permissions(mut self, retain_guild_permissions: bool) -> Self {
+        self.retain_guild_permissions = retain_guild_permissions;
+
+        self
+    }
+
+    /// ID of the role.
+    pub const fn role_id(&self) -> RoleId {
+        self.role.0
+    }
+
+    /// Calculate the guild-level permissions of the role, including those of
+    /// the `@everyone` role.
+    ///
+    /// Roles with the Administrator permission have every permission.
+    pub fn root(&self) -> Permissions {
+        root(self.everyone, self.role.1)
+    }
+
+    /// Calculate the permissions of the role in a channel from the channel's
+    /// permission overwrites.
+    pub fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Permissions {
+        let roles = &[(RoleId(self.guild_id.0), self.everyone), self.role];
+
+        channel_permissions_traced(
+            self.guild_id,
+            None,
+            roles,
+            root(self.everyone, self.role.1),
+            ChannelOptions {
+                administrator_bypass: self.administrator_bypass,
+                channel_type,
+                dependencies: &self.dependencies,
+                retain_guild_permissions: self.retain_guild_permissions,
+                timed_out: false,
+            },
+            channel_overwrites,
+            &mut |_, _, _| {},
+        )
+    }
+}
+
+/// Calculate the guild-level permissions of a role.
+pub(crate) fn root(everyone: Permissions, role: Permissions) -> Permissions {
+    let permissions = everyone | role;
+
+    if permissions.contains(Permissions::ADMINISTRATOR) {
+        Permissions::all()
+    } else {
+        permissions
+    }
+}
+
+/// Calculate the permissions of a role in a channel.
+pub(crate) fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+    guild_id: GuildId,
+    everyone: Permissions,
+    role: (RoleId, Permissions),
+    channel_type: ChannelType,
+    channel_overwrites: U,
+) -> Permissions {
+    let roles = &[(RoleId(guild_id.0), everyone), role];
+
+    channel_permissions(
+        guild_id,
+        None,
+        roles,
+        root(everyone, role.1),
+        channel_type,
+        channel_overwrites,
+    )
+}
+
+/// Calculate the permissions that a member with only the `@everyone` role
+/// would have in a channel.
+///
+/// Only the channel's overwrite of the `@everyone` role is applied, along with
+/// the permissions that apply to the type of channel. This is the channel as
+/// anyone without other roles or member overwrites sees it.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::role;
+///
+/// let everyone = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::SPEAK;
+/// let overwrites = &[
+///     PermissionOverwrite {
+///         allow: Permissions::empty(),
+///         deny: Permissions::SEND_MESSAGES,
+///         kind: PermissionOverwriteType::Role(RoleId(1)),
+///     },
+///     PermissionOverwrite {
+///         allow: Permissions::SEND_MESSAGES,
+///         deny: Permissions::empty(),
+///         kind: PermissionOverwriteType::Role(RoleId(2)),
+///     },
+/// ];
+///
+/// assert_eq!(
+///     Permissions::VIEW_CHANNEL,
+///     role::everyone_in_channel(GuildId(1), everyone, ChannelType::GuildText, overwrites),
+/// );
+/// ```
+pub fn everyone_in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+    guild_id: GuildId,
+    everyone: Permissions,
+    channel_type: ChannelType,
+    channel_overwrites: U,
+) -> Permissions {
+    in_channel(
+        guild_id,
+        everyone,
+        (RoleId(guild_id.0), everyone),
+        channel_type,
+        channel_overwrites,
+    )
+}
+
+/// Report of the channels in which overwrites change a role's permissions.
+///
+/// Created via [`overrides`].
+///
+/// [`overrides`]: fn.overrides.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OverrideReport {
+    /// Channels in which overwrites change the role's permissions, in the
+    /// order they were given.
+    ///
+    /// Each difference is between the role's permissions without and with the
+    /// channel's overwrites.
+    pub channels: Vec<(ChannelId, PermissionDiff)>,
+    /// ID of the role.
+    pub role_id: RoleId,
+}
+
+/// Find every channel in which overwrites change a role's permissions
+/// relative to its guild-level permissions, answering where a role is
+/// special-cased.
+///
+/// Channels are given as tuples of the channel's ID, type, and permission
+/// overwrites. Permissions that don't apply to a channel's type, such as
+/// voice permissions in a text channel, aren't considered changes.
+///
+/// Overwrites on the `@everyone` role change the permissions of every role
+/// and are included.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::role;
+///
+/// let everyone = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+/// let muted = (RoleId(2), Permissions::empty());
+/// let overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::SEND_MESSAGES,
+///     kind: PermissionOverwriteType::Role(RoleId(2)),
+/// }];
+/// let channels = vec![
+///     (ChannelId(3), ChannelType::GuildText, &overwrites[..]),
+///     (ChannelId(4), ChannelType::GuildText, &[][..]),
+/// ];
+///
+/// let report = role::overrides(GuildId(1), everyone, muted, channels);
+///
+/// assert_eq!(1, report.channels.len());
+/// assert_eq!(ChannelId(3), report.channels[0].0);
+/// assert_eq!(Permissions::SEND_MESSAGES, report.channels[0].1.removed);
+/// ```
+pub fn overrides<
+    'b,
+    U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+>(
+    guild_id: GuildId,
+    everyone: Permissions,
+    role: (RoleId, Permissions),
+    channels: U,
+) -> OverrideReport {
+    let channels = channels
+        .into_iter()
+        .filter_map(|(channel_id, channel_type, channel_overwrites)| {
+            let before = in_channel(guild_id, everyone, role, channel_type, &[]);
+            let after = in_channel(guild_id, everyone, role, channel_type, channel_overwrites);
+            let diff = PermissionDiff::new(before, after);
+
+            if diff.is_empty() {
+                None
+            } else {
+                Some((channel_id, diff))
+            }
+        })
+        .collect();
+
+    OverrideReport {
+        channels,
+        role_id: role.0,
+    }
+}
+
+/// Report of what members without a role can do in channels.
+///
+/// Created via [`without`].
+///
+/// [`without`]: fn.without.html
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct WithoutRoleReport {
+    /// Permissions that members without the role may have in each channel, in
+    /// the order the channels were given.
+    pub channels: Vec<(ChannelId, Permissions)>,
+    /// ID of the role that members don't have.
+    pub role_id: RoleId,
+}
+
+impl WithoutRoleReport {
+    /// Channels in which members without the role may have all of the given
+    /// permissions.
+    pub fn channels_with(&self, permissions: Permissions) -> Vec<ChannelId> {
+        self.channels
+            .iter()
+            .filter(|(_, channel_permissions)| channel_permissions.contains(permissions))
+            .map(|(channel_id, _)| *channel_id)
+            .collect()
+    }
+}
+
+/// Find what members without a role may be able to do in each channel,
+/// answering questions such as whether unverified members can't send messages
+/// anywhere.
+///
+/// Members without the role may have any combination of the guild's other
+/// roles. The permissions in each channel are those of a member with only the
+/// `@everyone` role, a member with any one of the other roles, and a member
+/// with all of the other roles, combined. This is an upper bound: it includes
+/// every permission that a member without the role could plausibly have.
+///
+/// Guild roles are given as tuples of the role's ID and permissions, and must
+/// include the `@everyone` role, which has the same ID as the guild. Channels
+/// are given as tuples of the channel's ID, type, and permission overwrites.
+///
+/// # Examples
+///
+/// Check that unverified members can't send messages in any channel:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::role;
+///
+/// let verified = RoleId(2);
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::VIEW_CHANNEL),
+///     (verified, Permissions::SEND_MESSAGES),
+/// ];
+/// let welcome = &[PermissionOverwrite {
+///     allow: Permissions::SEND_MESSAGES,
+///     deny: Permissions::empty(),
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+/// let channels = vec![
+///     (ChannelId(3), ChannelType::GuildText, &[][..]),
+///     (ChannelId(4), ChannelType::GuildText, &welcome[..]),
+/// ];
+///
+/// let report = role::without(GuildId(1), guild_roles, verified, channels)?;
+///
+/// assert_eq!(vec![ChannelId(4)], report.channels_with(Permissions::SEND_MESSAGES));
+/// # Ok(()) }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+/// isn't in the guild's roles.
+///
+/// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+pub fn without<'b, U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>>(
+    guild_id: GuildId,
+    guild_roles: &[(RoleId, Permissions)],
+    role_id: RoleId,
+    channels: U,
+) -> Result<WithoutRoleReport, CalculatorError> {
+    let everyone = guild_roles
+        .iter()
+        .find(|(id, _)| id.0 == guild_id.0)
+        .map(|(_, permissions)| *permissions)
+        .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })?;
+    let others = guild_roles
+        .iter()
+        .filter(|(id, _)| *id != role_id)
+        .copied()
+        .collect::<Vec<_>>();
+    let others_root = root(
+        everyone,
+        others
+            .iter()
+            .fold(Permissions::empty(), |all, (_, permissions)| {
+                all | *permissions
+            }),
+    );
+
+    let channels = channels
+        .into_iter()
+        .map(|(channel_id, channel_type, channel_overwrites)| {
+            let combined = channel_permissions(
+                guild_id,
+                None,
+                &others,
+                others_root,
+                channel_type,
+                channel_overwrites,
+            );
+
+            let permissions = others.iter().fold(combined, |permissions, role| {
+                permissions
+                    | in_channel(guild_id, everyone, *role, channel_type, channel_overwrites)
+            });
+
+            (channel_id, permissions)
+        })
+        .collect();
+
+    Ok(WithoutRoleReport { channels, role_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OverrideReport, RoleCalculator, WithoutRoleReport};
+    use crate::{diff::PermissionDiff, CalculatorError};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId},
+    };
+
+    assert_fields!(OverrideReport: channels, role_id);
+    assert_fields!(WithoutRoleReport: channels, role_id);
+    assert_impl_all!(OverrideReport: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(RoleCalculator: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(WithoutRoleReport: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_role_calculator() {
+        let everyone = Permissions::VIEW_CHANNEL;
+        let calculator = RoleCalculator::new(
+            GuildId(1),
+            everyone,
+            (RoleId(2), Permissions::SEND_MESSAGES),
+        );
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::VIEW_CHANNEL,
+                kind: PermissionOverwriteType::Role(RoleId(1)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::VIEW_CHANNEL,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+
+        assert_eq!(RoleId(2), calculator.role_id());
+        assert_eq!(
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            calculator.root()
+        );
+        // The role's overwrite allows View Channel back after the `@everyone`
+        // role's overwrite denies it, and other roles' overwrites are ignored.
+        assert_eq!(
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            calculator.in_channel(ChannelType::GuildText, overwrites),
+        );
+        assert_eq!(
+            Permissions::all(),
+            RoleCalculator::new(
+                GuildId(1),
+                everyone,
+                (RoleId(2), Permissions::ADMINISTRATOR)
+            )
+            .in_channel(ChannelType::GuildText, overwrites),
+        );
+    }
+
+    #[test]
+    fn test_from_guild_roles() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES),
+        ];
+
+        assert_eq!(
+            Ok(RoleCalculator::new(
+                GuildId(1),
+                Permissions::VIEW_CHANNEL,
+                (RoleId(2), Permissions::SEND_MESSAGES)
+            )),
+            RoleCalculator::from_guild_roles(GuildId(1), guild_roles, RoleId(2)),
+        );
+        assert_eq!(
+            Err(CalculatorError::RoleMissing { role_id: RoleId(3) }),
+            RoleCalculator::from_guild_roles(GuildId(1), guild_roles, RoleId(3)),
+        );
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            RoleCalculator::from_guild_roles(GuildId(1), &guild_roles[1..], RoleId(2)),
+        );
+    }
+
+    #[test]
+    fn test_everyone_in_channel() {
+        let guild_id = GuildId(1);
+        let everyone =
+            Permissions::CONNECT | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+        let hidden = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+
+        assert_eq!(
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            super::everyone_in_channel(guild_id, everyone, ChannelType::GuildText, &[]),
+        );
+        assert!(
+            !super::everyone_in_channel(guild_id, everyone, ChannelType::GuildVoice, hidden)
+                .contains(Permissions::VIEW_CHANNEL)
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let everyone = Permissions::VIEW_CHANNEL;
+        let role = (RoleId(2), Permissions::SEND_MESSAGES | Permissions::SPEAK);
+        let everyone_overwrite = &[PermissionOverwrite {
+            allow: Permissions::ADD_REACTIONS,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        let other_role = &[PermissionOverwrite {
+            allow: Permissions::MANAGE_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(RoleId(3)),
+        }];
+        let role_overwrite = &[PermissionOverwrite {
+            allow: Permissions::CONNECT,
+            deny: Permissions::SPEAK,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        }];
+        let channels = vec![
+            (
+                ChannelId(4),
+                ChannelType::GuildText,
+                &everyone_overwrite[..],
+            ),
+            (ChannelId(5), ChannelType::GuildText, &other_role[..]),
+            (ChannelId(6), ChannelType::GuildVoice, &role_overwrite[..]),
+            (ChannelId(7), ChannelType::GuildVoice, &[][..]),
+        ];
+
+        let report = super::overrides(GuildId(1), everyone, role, channels);
+
+        assert_eq!(
+            OverrideReport {
+                channels: vec![
+                    (
+                        ChannelId(4),
+                        PermissionDiff {
+                            added: Permissions::ADD_REACTIONS,
+                            removed: Permissions::empty(),
+                        },
+                    ),
+                    (
+                        ChannelId(6),
+                        PermissionDiff {
+                            added: Permissions::CONNECT,
+                            removed: Permissions::SPEAK,
+                        },
+                    ),
+                ],
+                role_id: RoleId(2),
+            },
+            report,
+        );
+    }
+
+    #[test]
+    fn test_overrides_admin() {
+        let role = (RoleId(2), Permissions::ADMINISTRATOR);
+        let overwrites = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        }];
+        let channels = vec![(ChannelId(3), ChannelType::GuildText, &overwrites[..])];
+
+        let report = super::overrides(GuildId(1), Permissions::empty(), role, channels);
+
+        assert!(report.channels.is_empty());
+    }
+
+    #[test]
+    fn test_without() {
+        let guild_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES),
+            (RoleId(3), Permissions::ATTACH_FILES),
+            (RoleId(4), Permissions::ADMINISTRATOR),
+        ];
+        // Members with role 3 can send messages in the channel, but role 2 is
+        // denied the permission.
+        let overwrites = &[
+            PermissionOverwrite {
+                allow: Permissions::SEND_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES,
+                kind: PermissionOverwriteType::Role(RoleId(2)),
+            },
+        ];
+        let channels = vec![(ChannelId(5), ChannelType::GuildText, &overwrites[..])];
+
+        let report =
+            super::without(GuildId(1), &guild_roles[..3], RoleId(2), channels.clone()).unwrap();
+        assert_eq!(
+            vec![(
+                ChannelId(5),
+                Permissions::ATTACH_FILES | Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            )],
+            report.channels,
+        );
+
+        let report =
+            super::without(GuildId(1), &guild_roles[..3], RoleId(3), channels.clone()).unwrap();
+        assert!(report.channels_with(Permissions::SEND_MESSAGES).is_empty());
+
+        // Members without the role may still be administrators.
+        let report = super::without(GuildId(1), guild_roles, RoleId(3), channels).unwrap();
+        assert_eq!(vec![(ChannelId(5), Permissions::all())], report.channels);
+    }
+}
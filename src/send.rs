@@ -0,0 +1,239 @@
+//! Checking whether a member can send messages in a channel.
+//!
+//! Sending a message depends on more than the Send Messages permission:
+//! messages can only be sent in text and news channels, View Channel is
+//! required to send anything at all, and other permissions limit what can be
+//! sent or how often. [`check`] folds these together into a single
+//! [`SendCapability`].
+//!
+//! Members who can send messages in a news channel can always publish their
+//! own messages, so news channels aren't limited any further. Threads aren't
+//! supported by this version of the model and aren't considered.
+//!
+//! [`check`]: fn.check.html
+//! [`SendCapability`]: enum.SendCapability.html
+
+use twilight_model::{channel::ChannelType, guild::Permissions};
+
+/// Permissions that exempt a member from a channel's slowmode.
+const SLOWMODE_EXEMPT: Permissions = Permissions::from_bits_truncate(
+    Permissions::MANAGE_CHANNELS.bits() | Permissions::MANAGE_MESSAGES.bits(),
+);
+
+/// Whether a member can send messages in a channel.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SendCapability {
+    /// Member can send messages, but with limits.
+    ///
+    /// Limits are in the order they're declared in [`SendLimit`].
+    ///
+    /// [`SendLimit`]: enum.SendLimit.html
+    Limited(Vec<SendLimit>),
+    /// Member can't send messages.
+    No(SendDenial),
+    /// Member can send messages without limits.
+    Yes,
+}
+
+impl SendCapability {
+    /// Whether the member can send messages at all, with or without limits.
+    pub fn can_send(&self) -> bool {
+        !matches!(self, Self::No(_))
+    }
+}
+
+/// Reason that a member can't send messages in a channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SendDenial {
+    /// Messages can't be sent in channels of the type.
+    ChannelType {
+        /// Type of the channel.
+        kind: ChannelType,
+    },
+    /// Member is missing permissions required to send messages.
+    MissingPermissions {
+        /// Missing permissions, out of Send Messages and View Channel.
+        missing: Permissions,
+    },
+}
+
+/// Limit on the messages a member can send in a channel.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SendLimit {
+    /// Member can't attach files, since they don't have the Attach Files
+    /// permission.
+    Attachments,
+    /// Member can't read the channel's message history, and so can't reply to
+    /// messages sent before they opened the channel.
+    History,
+    /// Member is subject to the channel's slowmode, since they have neither
+    /// the Manage Channels nor the Manage Messages permission.
+    Slowmode,
+}
+
+/// Whether a member with permissions in a channel can send messages in it.
+///
+/// `slowmode` is whether the channel has a slowmode enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{channel::ChannelType, guild::Permissions};
+/// use twilight_permission_calculator::send::{self, SendCapability, SendDenial, SendLimit};
+///
+/// let permissions = Permissions::ATTACH_FILES
+///     | Permissions::READ_MESSAGE_HISTORY
+///     | Permissions::SEND_MESSAGES
+///     | Permissions::VIEW_CHANNEL;
+///
+/// assert_eq!(SendCapability::Yes, send::check(permissions, ChannelType::GuildText, false));
+/// assert_eq!(
+///     SendCapability::Limited(vec![SendLimit::Slowmode]),
+///     send::check(permissions, ChannelType::GuildText, true),
+/// );
+/// assert_eq!(
+///     SendCapability::No(SendDenial::ChannelType {
+///         kind: ChannelType::GuildVoice,
+///     }),
+///     send::check(permissions, ChannelType::GuildVoice, false),
+/// );
+/// ```
+pub fn check(
+    permissions: Permissions,
+    channel_type: ChannelType,
+    slowmode: bool,
+) -> SendCapability {
+    if !matches!(
+        channel_type,
+        ChannelType::GuildNews | ChannelType::GuildText
+    ) {
+        return SendCapability::No(SendDenial::ChannelType { kind: channel_type });
+    }
+
+    let missing = (Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL) - permissions;
+
+    if !missing.is_empty() {
+        return SendCapability::No(SendDenial::MissingPermissions { missing });
+    }
+
+    let mut limits = Vec::new();
+
+    if !permissions.contains(Permissions::ATTACH_FILES) {
+        limits.push(SendLimit::Attachments);
+    }
+
+    if !permissions.contains(Permissions::READ_MESSAGE_HISTORY) {
+        limits.push(SendLimit::History);
+    }
+
+    if slowmode && !is_slowmode_exempt(permissions, false) {
+        limits.push(SendLimit::Slowmode);
+    }
+
+    if limits.is_empty() {
+        SendCapability::Yes
+    } else {
+        SendCapability::Limited(limits)
+    }
+}
+
+/// Whether a member with permissions in a channel is exempt from the
+/// channel's slowmode.
+///
+/// Bots are always exempt, as are members with the Manage Channels or Manage
+/// Messages permission, which includes administrators.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::send;
+///
+/// assert!(send::is_slowmode_exempt(Permissions::MANAGE_MESSAGES, false));
+/// assert!(send::is_slowmode_exempt(Permissions::SEND_MESSAGES, true));
+/// assert!(!send::is_slowmode_exempt(Permissions::SEND_MESSAGES, false));
+/// ```
+pub fn is_slowmode_exempt(permissions: Permissions, bot: bool) -> bool {
+    bot || permissions.intersects(SLOWMODE_EXEMPT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SendCapability, SendDenial, SendLimit};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{channel::ChannelType, guild::Permissions};
+
+    assert_fields!(SendDenial::ChannelType: kind);
+    assert_fields!(SendDenial::MissingPermissions: missing);
+    assert_impl_all!(SendCapability: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(SendDenial: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(SendLimit: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_check() {
+        let base = Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL;
+
+        assert_eq!(
+            SendCapability::No(SendDenial::MissingPermissions {
+                missing: Permissions::SEND_MESSAGES,
+            }),
+            super::check(Permissions::VIEW_CHANNEL, ChannelType::GuildNews, false),
+        );
+        assert_eq!(
+            SendCapability::No(SendDenial::ChannelType {
+                kind: ChannelType::GuildCategory,
+            }),
+            super::check(Permissions::all(), ChannelType::GuildCategory, false),
+        );
+        assert_eq!(
+            SendCapability::Limited(vec![
+                SendLimit::Attachments,
+                SendLimit::History,
+                SendLimit::Slowmode,
+            ]),
+            super::check(base, ChannelType::GuildText, true),
+        );
+        assert_eq!(
+            SendCapability::Limited(vec![SendLimit::Attachments, SendLimit::History]),
+            super::check(
+                base | Permissions::MANAGE_MESSAGES,
+                ChannelType::GuildText,
+                true
+            ),
+        );
+        assert_eq!(
+            SendCapability::Yes,
+            super::check(Permissions::all(), ChannelType::GuildNews, true),
+        );
+    }
+
+    #[test]
+    fn test_is_slowmode_exempt() {
+        assert!(super::is_slowmode_exempt(
+            Permissions::MANAGE_CHANNELS,
+            false
+        ));
+        assert!(super::is_slowmode_exempt(
+            Permissions::MANAGE_MESSAGES,
+            false
+        ));
+        assert!(super::is_slowmode_exempt(Permissions::empty(), true));
+        assert!(!super::is_slowmode_exempt(
+            Permissions::all() - Permissions::MANAGE_CHANNELS - Permissions::MANAGE_MESSAGES,
+            false
+        ));
+    }
+
+    #[test]
+    fn test_can_send() {
+        assert!(SendCapability::Yes.can_send());
+        assert!(SendCapability::Limited(vec![SendLimit::Slowmode]).can_send());
+        assert!(!SendCapability::No(SendDenial::MissingPermissions {
+            missing: Permissions::VIEW_CHANNEL,
+        })
+        .can_send());
+    }
+}
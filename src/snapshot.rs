@@ -0,0 +1,182 @@
+//! Immutable, shareable snapshots of calculated permissions.
+//!
+//! Web backends and bots calculate the permissions of a guild's members once
+//! and then read them from many tasks. A [`Snapshot`] owns the calculated
+//! permissions behind an [`Arc`], so cloning it is cheap and it can be moved
+//! into spawned tasks and request handlers, which require values that are
+//! `Send`, `Sync`, and `'static`.
+//!
+//! # Freshness
+//!
+//! Snapshots are never updated after they're created. Each one is stamped with
+//! the [`DataVersion`] of the data it was calculated from, and is [stale] once
+//! the current version has moved past it. The owner of the data is expected to
+//! increment the version whenever a role, channel, or member update is
+//! received, calculate a new snapshot, and replace the shared one; tasks that
+//! still hold the old snapshot keep reading consistent, if outdated,
+//! permissions until they drop it.
+//!
+//! [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
+//! [`DataVersion`]: ../version/struct.DataVersion.html
+//! [`Snapshot`]: struct.Snapshot.html
+//! [stale]: struct.Snapshot.html#method.is_stale
+
+use super::version::DataVersion;
+use std::{collections::HashMap, sync::Arc};
+use twilight_model::{
+    guild::Permissions,
+    id::{ChannelId, GuildId, UserId},
+};
+
+/// Calculated permissions shared by every clone of a snapshot.
+#[derive(Debug, Eq, PartialEq)]
+struct SnapshotData {
+    channels: HashMap<(ChannelId, UserId), Permissions>,
+    guild_id: GuildId,
+    roots: HashMap<UserId, Permissions>,
+    version: DataVersion,
+}
+
+/// Cheaply cloneable, immutable snapshot of the calculated permissions of a
+/// guild's members.
+///
+/// Refer to the [module-level documentation] for how snapshots are kept
+/// fresh.
+///
+/// # Examples
+///
+/// Share a snapshot with another thread:
+///
+/// ```rust
+/// use std::thread;
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, UserId},
+/// };
+/// use twilight_permission_calculator::{snapshot::Snapshot, version::DataVersion};
+///
+/// let version = DataVersion::default();
+/// let snapshot = Snapshot::new(
+///     GuildId(1),
+///     version,
+///     vec![(UserId(2), Permissions::VIEW_CHANNEL)],
+///     vec![(ChannelId(3), UserId(2), Permissions::VIEW_CHANNEL)],
+/// );
+///
+/// let shared = snapshot.clone();
+/// let handle = thread::spawn(move || shared.in_channel(ChannelId(3), UserId(2)));
+///
+/// assert_eq!(Some(Permissions::VIEW_CHANNEL), handle.join().unwrap());
+/// assert!(snapshot.is_stale(version.next()));
+/// ```
+///
+/// [module-level documentation]: index.html
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot(Arc<SnapshotData>);
+
+impl Snapshot {
+    /// Create a snapshot of permissions calculated from a version of a guild's
+    /// data.
+    ///
+    /// Guild-level permissions are given as tuples of the member's ID and
+    /// permissions, such as from [`Calculator::root`]. Channel permissions are
+    /// given as tuples of the channel's ID, member's ID, and permissions, such
+    /// as from [`Calculator::in_channel`]. Later entries for the same member or
+    /// pair of channel and member replace earlier ones.
+    ///
+    /// [`Calculator::in_channel`]: ../struct.Calculator.html#method.in_channel
+    /// [`Calculator::root`]: ../struct.Calculator.html#method.root
+    pub fn new<
+        R: IntoIterator<Item = (UserId, Permissions)>,
+        C: IntoIterator<Item = (ChannelId, UserId, Permissions)>,
+    >(
+        guild_id: GuildId,
+        version: DataVersion,
+        roots: R,
+        channels: C,
+    ) -> Self {
+        Self(Arc::new(SnapshotData {
+            channels: channels
+                .into_iter()
+                .map(|(channel_id, user_id, permissions)| ((channel_id, user_id), permissions))
+                .collect(),
+            guild_id,
+            roots: roots.into_iter().collect(),
+            version,
+        }))
+    }
+
+    /// ID of the guild the permissions were calculated in.
+    pub fn guild_id(&self) -> GuildId {
+        self.0.guild_id
+    }
+
+    /// Version of the data the permissions were calculated from.
+    pub fn version(&self) -> DataVersion {
+        self.0.version
+    }
+
+    /// Whether the data has been updated since the snapshot was created,
+    /// meaning that a new snapshot should be calculated.
+    pub fn is_stale(&self, current_version: DataVersion) -> bool {
+        self.0.version < current_version
+    }
+
+    /// Guild-level permissions of a member.
+    ///
+    /// Returns `None` if the member isn't in the snapshot.
+    pub fn root(&self, user_id: UserId) -> Option<Permissions> {
+        self.0.roots.get(&user_id).copied()
+    }
+
+    /// Permissions of a member in a channel.
+    ///
+    /// Returns `None` if the pair of channel and member isn't in the snapshot.
+    pub fn in_channel(&self, channel_id: ChannelId, user_id: UserId) -> Option<Permissions> {
+        self.0.channels.get(&(channel_id, user_id)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Snapshot;
+    use crate::version::DataVersion;
+    use static_assertions::assert_impl_all;
+    use std::{fmt::Debug, sync::Arc};
+    use twilight_model::{
+        guild::Permissions,
+        id::{ChannelId, GuildId, UserId},
+    };
+
+    assert_impl_all!(Snapshot: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    fn assert_static<T: 'static>(_: &T) {}
+
+    #[test]
+    fn test_snapshot() {
+        let snapshot = Snapshot::new(
+            GuildId(1),
+            DataVersion::new(2),
+            vec![
+                (UserId(3), Permissions::empty()),
+                (UserId(3), Permissions::SEND_MESSAGES),
+            ],
+            vec![(ChannelId(4), UserId(3), Permissions::SEND_MESSAGES)],
+        );
+        let clone = snapshot.clone();
+        assert_static(&clone);
+
+        assert!(Arc::ptr_eq(&snapshot.0, &clone.0));
+        assert_eq!(GuildId(1), clone.guild_id());
+        assert_eq!(DataVersion::new(2), clone.version());
+        assert!(!clone.is_stale(DataVersion::new(2)));
+        assert!(clone.is_stale(DataVersion::new(3)));
+        assert_eq!(Some(Permissions::SEND_MESSAGES), clone.root(UserId(3)));
+        assert!(clone.root(UserId(4)).is_none());
+        assert_eq!(
+            Some(Permissions::SEND_MESSAGES),
+            clone.in_channel(ChannelId(4), UserId(3))
+        );
+        assert!(clone.in_channel(ChannelId(3), UserId(4)).is_none());
+    }
+}
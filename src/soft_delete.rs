@@ -0,0 +1,238 @@
+//! Previewing how deleting a channel affects members.
+//!
+//! Deleting a channel can leave members without anywhere to do something,
+//! such as speak or send messages. [`SoftDelete`] removes a channel from
+//! consideration and finds the members for whom it was the only channel with
+//! a set of permissions, so that the deletion can be assessed before it's
+//! made.
+//!
+//! [`SoftDelete`]: struct.SoftDelete.html
+
+use super::{Calculator, CalculatorError};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Calculate which members lose their only channel with a set of permissions
+/// when a channel is deleted.
+///
+/// # Examples
+///
+/// Find the members that can only speak in a voice channel that's about to be
+/// deleted:
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::soft_delete::SoftDelete;
+///
+/// let everyone = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+/// let members = &[(UserId(2), &everyone[..]), (UserId(3), &everyone[..])];
+///
+/// let lounge = &[PermissionOverwrite {
+///     allow: Permissions::SPEAK,
+///     deny: Permissions::empty(),
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+/// let stage = &[PermissionOverwrite {
+///     allow: Permissions::SPEAK,
+///     deny: Permissions::empty(),
+///     kind: PermissionOverwriteType::Member(UserId(3)),
+/// }];
+/// let channels = vec![
+///     (ChannelId(4), ChannelType::GuildVoice, &lounge[..]),
+///     (ChannelId(5), ChannelType::GuildVoice, &stage[..]),
+/// ];
+///
+/// let preview = SoftDelete::new(GuildId(1), members).preview(
+///     ChannelId(4),
+///     channels,
+///     Permissions::SPEAK,
+/// )?;
+///
+/// assert_eq!(vec![UserId(2)], preview.stranded);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[must_use = "the soft delete isn't useful if you don't preview it"]
+pub struct SoftDelete<'a> {
+    guild_id: GuildId,
+    members: &'a [(UserId, &'a [(RoleId, Permissions)])],
+    owner_id: Option<UserId>,
+}
+
+impl<'a> SoftDelete<'a> {
+    /// Create a preview of deleting a channel of a guild for a set of members.
+    ///
+    /// Member roles are given like they are to [`Calculator::new`].
+    ///
+    /// [`Calculator::new`]: ../struct.Calculator.html#method.new
+    pub fn new(guild_id: GuildId, members: &'a [(UserId, &'a [(RoleId, Permissions)])]) -> Self {
+        Self {
+            guild_id,
+            members,
+            owner_id: None,
+        }
+    }
+
+    /// Configure the ID of the owner of the guild.
+    ///
+    /// Refer to the documentation for [`Calculator::owner_id`].
+    ///
+    /// [`Calculator::owner_id`]: ../struct.Calculator.html#method.owner_id
+    pub fn owner_id(mut self, owner_id: UserId) -> Self {
+        self.owner_id.replace(owner_id);
+
+        self
+    }
+
+    /// Find the members for whom the deleted channel is the only one of the
+    /// guild's channels in which they have all of the required permissions.
+    ///
+    /// Channels are given as tuples of the channel's ID, type, and permission
+    /// overwrites, and should include the deleted channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone` role
+    /// isn't in the roles of a member.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn preview<
+        'b,
+        U: IntoIterator<Item = (ChannelId, ChannelType, &'b [PermissionOverwrite])>,
+    >(
+        &self,
+        deleted_channel_id: ChannelId,
+        channels: U,
+        required: Permissions,
+    ) -> Result<SoftDeletePreview, CalculatorError> {
+        let channels = channels.into_iter().collect::<Vec<_>>();
+        let mut stranded = Vec::new();
+
+        for (user_id, member_roles) in self.members {
+            let mut calculator = Calculator::new(self.guild_id, *user_id, member_roles);
+
+            if let Some(owner_id) = self.owner_id {
+                calculator = calculator.owner_id(owner_id);
+            }
+
+            let root = calculator.root()?;
+            let mut in_deleted = false;
+            let mut elsewhere = false;
+
+            for (channel_id, channel_type, channel_overwrites) in &channels {
+                let permissions =
+                    calculator.in_channel_with_root(root, *channel_type, *channel_overwrites);
+
+                if !permissions.contains(required) {
+                    continue;
+                }
+
+                if *channel_id == deleted_channel_id {
+                    in_deleted = true;
+                } else {
+                    elsewhere = true;
+
+                    break;
+                }
+            }
+
+            if in_deleted && !elsewhere {
+                stranded.push(*user_id);
+            }
+        }
+
+        Ok(SoftDeletePreview { stranded })
+    }
+}
+
+/// Members affected by deleting a channel.
+///
+/// Created via [`SoftDelete::preview`].
+///
+/// [`SoftDelete::preview`]: struct.SoftDelete.html#method.preview
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SoftDeletePreview {
+    /// Members that would no longer have the required permissions in any
+    /// channel, in the order they were given.
+    pub stranded: Vec<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SoftDelete, SoftDeletePreview};
+    use crate::CalculatorError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(SoftDeletePreview: stranded);
+    assert_impl_all!(SoftDelete<'_>: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(SoftDeletePreview: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_preview() {
+        let everyone = &[(RoleId(1), Permissions::VIEW_CHANNEL)];
+        let members = &[
+            (UserId(2), &everyone[..]),
+            (UserId(3), &everyone[..]),
+            (UserId(4), &everyone[..]),
+        ];
+        let only_member = &[PermissionOverwrite {
+            allow: Permissions::SEND_MESSAGES,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(UserId(2)),
+        }];
+        let channels = vec![
+            (ChannelId(5), ChannelType::GuildText, &only_member[..]),
+            (ChannelId(6), ChannelType::GuildText, &[][..]),
+        ];
+
+        // The owner can send messages everywhere, so they're never stranded.
+        let preview = SoftDelete::new(GuildId(1), members)
+            .owner_id(UserId(3))
+            .preview(ChannelId(5), channels.clone(), Permissions::SEND_MESSAGES)
+            .unwrap();
+        assert_eq!(vec![UserId(2)], preview.stranded);
+
+        // Nobody can send messages in the other channel, so nobody loses it.
+        let preview = SoftDelete::new(GuildId(1), members)
+            .preview(ChannelId(6), channels, Permissions::SEND_MESSAGES)
+            .unwrap();
+        assert!(preview.stranded.is_empty());
+    }
+
+    #[test]
+    fn test_everyone_role_missing() {
+        let member_roles = &[(RoleId(2), Permissions::VIEW_CHANNEL)];
+        let members = &[(UserId(3), &member_roles[..])];
+
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing {
+                guild_id: GuildId(1)
+            }),
+            SoftDelete::new(GuildId(1), members).preview(
+                ChannelId(4),
+                Vec::new(),
+                Permissions::SEND_MESSAGES
+            ),
+        );
+    }
+}
@@ -0,0 +1,461 @@
+//! Calculating permissions against pluggable data backends.
+//!
+//! Bots keep guild data in different places: an in-memory cache, a database,
+//! or fixtures in tests. [`PermissionSource`] is implemented by a backend to
+//! supply the roles, owner, member roles, and channel overwrites that
+//! calculations need, and [`SourceCalculator`] fetches them from any source
+//! and calculates with them, so that tests and production share one
+//! calculation path.
+//!
+//! [`PermissionSource`]: trait.PermissionSource.html
+//! [`SourceCalculator`]: struct.SourceCalculator.html
+
+use super::{Calculator, CalculatorError};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{ChannelId, GuildId, RoleId, UserId},
+};
+
+/// Owner of a guild and the permissions of a member's roles, fetched from a
+/// source.
+pub(crate) type MemberData = (Option<UserId>, Vec<(RoleId, Permissions)>);
+
+/// Type and permission overwrites of a channel, supplied by a source.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct SourceChannel {
+    /// Type of the channel.
+    pub kind: ChannelType,
+    /// Permission overwrites of the channel.
+    pub overwrites: Vec<PermissionOverwrite>,
+}
+
+/// Backend supplying the data that permissions are calculated from.
+pub trait PermissionSource {
+    /// Error returned when the backend fails to supply data, such as when a
+    /// database query fails.
+    ///
+    /// Sources that can't fail use [`Infallible`].
+    ///
+    /// [`Infallible`]: https://doc.rust-lang.org/std/convert/enum.Infallible.html
+    type Error;
+
+    /// ID of the owner of a guild, if known.
+    fn owner_id(&self, guild_id: GuildId) -> Result<Option<UserId>, Self::Error>;
+
+    /// Permissions of roles of a guild.
+    ///
+    /// The `@everyone` role has the same ID as the guild and is always
+    /// requested. Roles that aren't known are left out.
+    fn role_permissions(
+        &self,
+        guild_id: GuildId,
+        role_ids: &[RoleId],
+    ) -> Result<Vec<(RoleId, Permissions)>, Self::Error>;
+
+    /// IDs of the roles of a member, not including the `@everyone` role.
+    ///
+    /// Returns `None` if the member isn't known.
+    fn member_role_ids(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Option<Vec<RoleId>>, Self::Error>;
+
+    /// Type and permission overwrites of a channel of a guild.
+    ///
+    /// Returns `None` if the channel isn't known.
+    fn channel(
+        &self,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<Option<SourceChannel>, Self::Error>;
+}
+
+/// Error returned when permissions couldn't be calculated from a source.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum SourceError<E> {
+    /// Permissions couldn't be calculated from the supplied data.
+    Calculating {
+        /// Reason the permissions couldn't be calculated.
+        source: CalculatorError,
+    },
+    /// Source doesn't know the channel.
+    ChannelMissing {
+        /// ID of the channel.
+        channel_id: ChannelId,
+    },
+    /// Source doesn't know the member.
+    MemberMissing {
+        /// ID of the guild.
+        guild_id: GuildId,
+        /// ID of the member.
+        user_id: UserId,
+    },
+    /// Source failed to supply data.
+    Source {
+        /// Error returned by the source.
+        source: E,
+    },
+}
+
+impl<E> Display for SourceError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Calculating { .. } => f.write_str("permissions couldn't be calculated"),
+            Self::ChannelMissing { channel_id } => f.write_fmt(format_args!(
+                "channel {} isn't known to the source",
+                channel_id
+            )),
+            Self::MemberMissing { guild_id, user_id } => f.write_fmt(format_args!(
+                "member {} of guild {} isn't known to the source",
+                user_id, guild_id
+            )),
+            Self::Source { .. } => f.write_str("source failed to supply data"),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for SourceError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Calculating { source } => Some(source),
+            Self::Source { source } => Some(source),
+            Self::ChannelMissing { .. } | Self::MemberMissing { .. } => None,
+        }
+    }
+}
+
+/// Calculate permissions with data fetched from a source.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::convert::Infallible;
+/// use twilight_model::{
+///     channel::ChannelType,
+///     guild::Permissions,
+///     id::{ChannelId, GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::source::{
+///     PermissionSource, SourceCalculator, SourceChannel,
+/// };
+///
+/// /// Guild with one channel in which everyone can view it.
+/// struct Fixture;
+///
+/// impl PermissionSource for Fixture {
+///     type Error = Infallible;
+///
+///     fn owner_id(&self, _: GuildId) -> Result<Option<UserId>, Infallible> {
+///         Ok(None)
+///     }
+///
+///     fn role_permissions(
+///         &self,
+///         _: GuildId,
+///         role_ids: &[RoleId],
+///     ) -> Result<Vec<(RoleId, Permissions)>, Infallible> {
+///         Ok(role_ids.iter().map(|id| (*id, Permissions::VIEW_CHANNEL)).collect())
+///     }
+///
+///     fn member_role_ids(&self, _: GuildId, _: UserId) -> Result<Option<Vec<RoleId>>, Infallible> {
+///         Ok(Some(Vec::new()))
+///     }
+///
+///     fn channel(&self, _: GuildId, _: ChannelId) -> Result<Option<SourceChannel>, Infallible> {
+///         Ok(Some(SourceChannel {
+///             kind: ChannelType::GuildText,
+///             overwrites: Vec::new(),
+///         }))
+///     }
+/// }
+///
+/// let calculator = SourceCalculator::new(&Fixture);
+///
+/// assert_eq!(
+///     Permissions::VIEW_CHANNEL,
+///     calculator.in_channel(GuildId(1), UserId(2), ChannelId(3))?,
+/// );
+/// # Ok(()) }
+/// ```
+#[derive(Debug)]
+pub struct SourceCalculator<'a, S: ?Sized> {
+    source: &'a S,
+}
+
+impl<'a, S: PermissionSource + ?Sized> SourceCalculator<'a, S> {
+    /// Create a calculator fetching data from a source.
+    pub fn new(source: &'a S) -> Self {
+        Self { source }
+    }
+
+    /// Calculate the guild-level permissions of a member.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SourceError::MemberMissing`] if the source doesn't know the
+    /// member.
+    ///
+    /// Returns [`SourceError::Calculating`] if the source doesn't know the
+    /// `@everyone` role.
+    ///
+    /// Returns [`SourceError::Source`] if the source failed to supply data.
+    ///
+    /// [`SourceError::Calculating`]: enum.SourceError.html#variant.Calculating
+    /// [`SourceError::MemberMissing`]: enum.SourceError.html#variant.MemberMissing
+    /// [`SourceError::Source`]: enum.SourceError.html#variant.Source
+    pub fn root(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<Permissions, SourceError<S::Error>> {
+        let (owner_id, member_roles) = self.member(guild_id, user_id)?;
+
+        calculate(guild_id, user_id, owner_id, &member_roles, None)
+    }
+
+    /// Calculate the permissions of a member in a channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SourceError::ChannelMissing`] if the source doesn't know the
+    /// channel.
+    ///
+    /// Otherwise returns the same errors as [`root`].
+    ///
+    /// [`SourceError::ChannelMissing`]: enum.SourceError.html#variant.ChannelMissing
+    /// [`root`]: #method.root
+    pub fn in_channel(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+        channel_id: ChannelId,
+    ) -> Result<Permissions, SourceError<S::Error>> {
+        let channel = self
+            .source
+            .channel(guild_id, channel_id)
+            .map_err(|source| SourceError::Source { source })?
+            .ok_or(SourceError::ChannelMissing { channel_id })?;
+        let (owner_id, member_roles) = self.member(guild_id, user_id)?;
+
+        calculate(guild_id, user_id, owner_id, &member_roles, Some(&channel))
+    }
+
+    /// Fetch the owner of the guild and the permissions of the member's roles.
+    fn member(
+        &self,
+        guild_id: GuildId,
+        user_id: UserId,
+    ) -> Result<MemberData, SourceError<S::Error>> {
+        let role_ids = self
+            .source
+            .member_role_ids(guild_id, user_id)
+            .map_err(|source| SourceError::Source { source })?
+            .ok_or(SourceError::MemberMissing { guild_id, user_id })?;
+        let member_roles = self
+            .source
+            .role_permissions(guild_id, &with_everyone(guild_id, role_ids))
+            .map_err(|source| SourceError::Source { source })?;
+        let owner_id = self
+            .source
+            .owner_id(guild_id)
+            .map_err(|source| SourceError::Source { source })?;
+
+        Ok((owner_id, member_roles))
+    }
+}
+
+impl<S: ?Sized> Clone for SourceCalculator<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: ?Sized> Copy for SourceCalculator<'_, S> {}
+
+/// Prepend the `@everyone` role to the IDs of a member's roles.
+pub(crate) fn with_everyone(guild_id: GuildId, role_ids: Vec<RoleId>) -> Vec<RoleId> {
+    let mut ids = Vec::with_capacity(role_ids.len() + 1);
+    ids.push(RoleId(guild_id.0));
+    ids.extend(role_ids.into_iter().filter(|id| id.0 != guild_id.0));
+
+    ids
+}
+
+/// Calculate the permissions of a member with data supplied by a source, in
+/// a channel if one is given.
+pub(crate) fn calculate<E>(
+    guild_id: GuildId,
+    user_id: UserId,
+    owner_id: Option<UserId>,
+    member_roles: &[(RoleId, Permissions)],
+    channel: Option<&SourceChannel>,
+) -> Result<Permissions, SourceError<E>> {
+    let mut calculator = Calculator::new(guild_id, user_id, member_roles);
+
+    if let Some(owner_id) = owner_id {
+        calculator = calculator.owner_id(owner_id);
+    }
+
+    let permissions = match channel {
+        Some(channel) => calculator.in_channel(channel.kind, &channel.overwrites),
+        None => calculator.root(),
+    };
+
+    permissions.map_err(|source| SourceError::Calculating { source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PermissionSource, SourceCalculator, SourceChannel, SourceError};
+    use crate::{Calculator, CalculatorError};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        collections::HashMap,
+        convert::Infallible,
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{ChannelId, GuildId, RoleId, UserId},
+    };
+
+    assert_fields!(SourceChannel: kind, overwrites);
+    assert_impl_all!(SourceCalculator<'static, Fixture>: Clone, Copy, Debug, Send, Sync);
+    assert_impl_all!(SourceChannel: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(
+        SourceError<CalculatorError>: Clone,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[derive(Debug, Default)]
+    struct Fixture {
+        channels: HashMap<ChannelId, SourceChannel>,
+        members: HashMap<UserId, Vec<RoleId>>,
+        owner_id: Option<UserId>,
+        roles: HashMap<RoleId, Permissions>,
+    }
+
+    impl PermissionSource for Fixture {
+        type Error = Infallible;
+
+        fn owner_id(&self, _: GuildId) -> Result<Option<UserId>, Infallible> {
+            Ok(self.owner_id)
+        }
+
+        fn role_permissions(
+            &self,
+            _: GuildId,
+            role_ids: &[RoleId],
+        ) -> Result<Vec<(RoleId, Permissions)>, Infallible> {
+            Ok(role_ids
+                .iter()
+                .filter_map(|id| self.roles.get(id).map(|permissions| (*id, *permissions)))
+                .collect())
+        }
+
+        fn member_role_ids(
+            &self,
+            _: GuildId,
+            user_id: UserId,
+        ) -> Result<Option<Vec<RoleId>>, Infallible> {
+            Ok(self.members.get(&user_id).cloned())
+        }
+
+        fn channel(
+            &self,
+            _: GuildId,
+            channel_id: ChannelId,
+        ) -> Result<Option<SourceChannel>, Infallible> {
+            Ok(self.channels.get(&channel_id).cloned())
+        }
+    }
+
+    #[test]
+    fn test_source_calculator() {
+        let overwrites = vec![PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::SEND_MESSAGES,
+            kind: PermissionOverwriteType::Role(RoleId(2)),
+        }];
+        let mut fixture = Fixture::default();
+        fixture.channels.insert(
+            ChannelId(3),
+            SourceChannel {
+                kind: ChannelType::GuildText,
+                overwrites: overwrites.clone(),
+            },
+        );
+        fixture
+            .members
+            .insert(UserId(4), vec![RoleId(2), RoleId(9)]);
+        fixture.members.insert(UserId(5), Vec::new());
+        fixture.owner_id = Some(UserId(5));
+        fixture.roles.insert(RoleId(1), Permissions::VIEW_CHANNEL);
+        fixture.roles.insert(RoleId(2), Permissions::SEND_MESSAGES);
+
+        let calculator = SourceCalculator::new(&fixture);
+        let member_roles = &[
+            (RoleId(1), Permissions::VIEW_CHANNEL),
+            (RoleId(2), Permissions::SEND_MESSAGES),
+        ];
+
+        assert_eq!(
+            Calculator::new(GuildId(1), UserId(4), member_roles)
+                .in_channel(ChannelType::GuildText, &overwrites)
+                .unwrap(),
+            calculator
+                .in_channel(GuildId(1), UserId(4), ChannelId(3))
+                .unwrap(),
+        );
+        assert_eq!(
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            calculator.root(GuildId(1), UserId(4)).unwrap(),
+        );
+        assert_eq!(
+            Permissions::all(),
+            calculator.root(GuildId(1), UserId(5)).unwrap(),
+        );
+        assert_eq!(
+            Err(SourceError::MemberMissing {
+                guild_id: GuildId(1),
+                user_id: UserId(6),
+            }),
+            calculator.root(GuildId(1), UserId(6)),
+        );
+        assert_eq!(
+            Err(SourceError::ChannelMissing {
+                channel_id: ChannelId(7),
+            }),
+            calculator.in_channel(GuildId(1), UserId(4), ChannelId(7)),
+        );
+
+        fixture.roles.remove(&RoleId(1));
+        assert!(matches!(
+            SourceCalculator::new(&fixture).root(GuildId(1), UserId(4)),
+            Err(SourceError::Calculating {
+                source: CalculatorError::EveryoneRoleMissing { .. }
+            })
+        ));
+    }
+}
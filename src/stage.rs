@@ -0,0 +1,102 @@
+//! Checking the roles of members in stage channels.
+//!
+//! Stage channels have an audience, which can only listen and request to
+//! speak, and moderators, who can invite members of the audience to speak,
+//! move them back to the audience, and manage the stage. Whether a member is
+//! a moderator only depends on their permissions in the stage channel.
+//!
+//! Inviting members to speak and moving them back to the audience only need
+//! the Mute Members permission, since they change whether the member is
+//! suppressed, while starting and ending the stage needs every moderator
+//! permission.
+
+use twilight_model::guild::Permissions;
+
+/// Permissions that make a member a moderator of a stage channel.
+const MODERATOR: Permissions = Permissions::from_bits_truncate(
+    Permissions::MANAGE_CHANNELS.bits()
+        | Permissions::MOVE_MEMBERS.bits()
+        | Permissions::MUTE_MEMBERS.bits(),
+);
+
+/// Whether a member with permissions in a stage channel is a moderator of it.
+///
+/// Stage moderators have the Manage Channels, Move Members, and Mute Members
+/// permissions.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::stage;
+///
+/// let audience = Permissions::CONNECT | Permissions::REQUEST_TO_SPEAK;
+/// let moderator = audience
+///     | Permissions::MANAGE_CHANNELS
+///     | Permissions::MOVE_MEMBERS
+///     | Permissions::MUTE_MEMBERS;
+///
+/// assert!(!stage::is_moderator(audience));
+/// assert!(stage::is_moderator(moderator));
+/// ```
+pub fn is_moderator(permissions: Permissions) -> bool {
+    permissions.contains(MODERATOR)
+}
+
+/// Whether a member with permissions in a stage channel can invite a member
+/// of the audience to speak.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::stage;
+///
+/// assert!(stage::can_invite_to_speak(Permissions::MUTE_MEMBERS));
+/// assert!(!stage::can_invite_to_speak(Permissions::REQUEST_TO_SPEAK));
+/// ```
+pub fn can_invite_to_speak(permissions: Permissions) -> bool {
+    permissions.contains(Permissions::MUTE_MEMBERS)
+}
+
+/// Whether a member with permissions in a stage channel can move a speaker
+/// back to the audience.
+pub fn can_move_to_audience(permissions: Permissions) -> bool {
+    permissions.contains(Permissions::MUTE_MEMBERS)
+}
+
+/// Whether a member with permissions in a stage channel can start, edit, and
+/// end the stage.
+///
+/// Only moderators can manage the stage; refer to [`is_moderator`].
+///
+/// [`is_moderator`]: fn.is_moderator.html
+pub fn can_manage_stage(permissions: Permissions) -> bool {
+    is_moderator(permissions)
+}
+
+#[cfg(test)]
+mod tests {
+    use twilight_model::guild::Permissions;
+
+    #[test]
+    fn test_is_moderator() {
+        assert!(super::is_moderator(Permissions::all()));
+        assert!(!super::is_moderator(
+            Permissions::MANAGE_CHANNELS | Permissions::MUTE_MEMBERS
+        ));
+    }
+
+    #[test]
+    fn test_speakers() {
+        let permissions = Permissions::MUTE_MEMBERS;
+
+        assert!(super::can_invite_to_speak(permissions));
+        assert!(super::can_move_to_audience(permissions));
+        assert!(!super::can_manage_stage(permissions));
+        assert!(!super::can_move_to_audience(
+            Permissions::MANAGE_CHANNELS | Permissions::MOVE_MEMBERS
+        ));
+        assert!(super::can_manage_stage(Permissions::all()));
+    }
+}
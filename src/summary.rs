@@ -0,0 +1,112 @@
+//! Summarizing in how many channels a member has each permission.
+//!
+//! Auditing a member across a guild usually boils down to statements such as
+//! "this member can delete messages in 37 of 40 channels". A
+//! [`PermissionSummary`] counts, for every permission, how many of the given
+//! channels the member has it in, so that callers don't have to aggregate the
+//! permissions of every channel themselves.
+//!
+//! [`PermissionSummary`]: struct.PermissionSummary.html
+
+use super::names;
+use twilight_model::guild::Permissions;
+
+/// Number of channels in which a member has each permission, created via
+/// [`Calculator::summarize`].
+///
+/// [`Calculator::summarize`]: ../struct.Calculator.html#method.summarize
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PermissionSummary {
+    /// Total number of channels that were summarized.
+    pub channels: usize,
+    /// Every known permission and the number of channels the member has it in,
+    /// in order of the permissions' bit positions.
+    ///
+    /// Guild-level permissions, such as Ban Members, are never present in a
+    /// channel and so are always counted as zero.
+    pub counts: Vec<(Permissions, usize)>,
+}
+
+impl PermissionSummary {
+    /// Summarize the permissions of a member in each channel.
+    pub(crate) fn new<I: IntoIterator<Item = Permissions>>(channels: I) -> Self {
+        let mut summary = Self {
+            channels: 0,
+            counts: names::iter(Permissions::all())
+                .map(|(permission, _)| (permission, 0))
+                .collect(),
+        };
+
+        for permissions in channels {
+            summary.channels += 1;
+
+            for (permission, count) in &mut summary.counts {
+                if permissions.contains(*permission) {
+                    *count += 1;
+                }
+            }
+        }
+
+        summary
+    }
+
+    /// Number of channels in which the member has a permission.
+    ///
+    /// Returns zero if the given value isn't exactly one known permission.
+    pub fn count(&self, permission: Permissions) -> usize {
+        self.counts
+            .iter()
+            .find(|(known, _)| *known == permission)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Permissions that the member has in every channel.
+    ///
+    /// Returns no permissions if no channels were summarized.
+    pub fn everywhere(&self) -> Permissions {
+        if self.channels == 0 {
+            return Permissions::empty();
+        }
+
+        self.counts
+            .iter()
+            .filter(|(_, count)| *count == self.channels)
+            .fold(Permissions::empty(), |all, (permission, _)| {
+                all | *permission
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PermissionSummary;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::Permissions;
+
+    assert_fields!(PermissionSummary: channels, counts);
+    assert_impl_all!(PermissionSummary: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_summary() {
+        let summary = PermissionSummary::new(vec![
+            Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL,
+            Permissions::VIEW_CHANNEL,
+            Permissions::empty(),
+        ]);
+
+        assert_eq!(3, summary.channels);
+        assert_eq!(1, summary.count(Permissions::MANAGE_MESSAGES));
+        assert_eq!(2, summary.count(Permissions::VIEW_CHANNEL));
+        assert_eq!(0, summary.count(Permissions::BAN_MEMBERS));
+        assert_eq!(
+            0,
+            summary.count(Permissions::MANAGE_MESSAGES | Permissions::VIEW_CHANNEL)
+        );
+        assert!(summary.everywhere().is_empty());
+
+        let summary = PermissionSummary::new(vec![Permissions::SPEAK, Permissions::SPEAK]);
+        assert_eq!(Permissions::SPEAK, summary.everywhere());
+        assert!(PermissionSummary::new(Vec::new()).everywhere().is_empty());
+    }
+}
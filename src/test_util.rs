@@ -0,0 +1,21 @@
+//! Fixtures shared by the tests of multiple modules.
+
+use twilight_model::{
+    guild::{Permissions, Role},
+    id::RoleId,
+};
+
+/// Create a role with an ID and permissions, and defaults for everything else.
+pub fn role(role_id: u64, permissions: Permissions) -> Role {
+    Role {
+        color: 0,
+        hoist: false,
+        id: RoleId(role_id),
+        managed: false,
+        mentionable: false,
+        name: "role".to_owned(),
+        permissions,
+        position: 1,
+        tags: None,
+    }
+}
@@ -0,0 +1,155 @@
+//! Tracing the steps taken to calculate a member's permissions in a channel.
+//!
+//! Complicated channel setups are hard to debug from the calculated
+//! permissions alone. [`Calculator::trace`] records every step of the
+//! calculation in the order it was taken, from the member's roles through the
+//! channel's overwrites to the permissions removed for the channel's type,
+//! along with the permissions before and after each step.
+//!
+//! [`Calculator::trace`]: ../struct.Calculator.html#method.trace
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use twilight_model::{channel::ChannelType, guild::Permissions};
+
+/// Step taken while calculating a member's permissions.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case", tag = "type"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum Step {
+    /// Member's roles grant the Administrator permission, so every permission
+    /// was granted.
+    Administrator,
+    /// Permissions that don't apply to the type of the channel were removed.
+    ChannelType {
+        /// Type of the channel.
+        kind: ChannelType,
+    },
+    /// Member or their roles are denied a permission in the channel, so the
+    /// permissions that [depend] on it were removed.
+    ///
+    /// [depend]: ../dependency/index.html
+    DependencyDenied {
+        /// Permission that was denied.
+        permission: Permissions,
+    },
+    /// Guild-level permissions of the `@everyone` role were granted.
+    Everyone,
+    /// Overwrite of the `@everyone` role in the channel was applied.
+    EveryoneOverwrite,
+    /// Guild-level permissions, which can't be used in a channel, were
+    /// removed.
+    GuildPermissions,
+    /// Overwrite of the member in the channel was applied.
+    MemberOverwrite,
+    /// Member is the owner of the guild, so every permission was granted.
+    Owner,
+    /// Overwrites of the member's roles in the channel were applied.
+    RoleOverwrites,
+    /// Guild-level permissions of the member's roles were granted.
+    Roles,
+    /// Member or their roles are denied Send Messages in the channel, so the
+    /// other permissions used when sending messages were removed.
+    SendMessagesDenied,
+    /// Member is timed out, so every permission other than Read Message
+    /// History and View Channel was removed.
+    TimedOut,
+    /// Member or their roles are denied View Channel in the channel, so every
+    /// permission was removed.
+    ViewChannelDenied,
+}
+
+/// Step of a calculation along with the permissions before and after it.
+#[cfg(feature = "explain")]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TraceStep {
+    /// Permissions after the step.
+    pub after: Permissions,
+    /// Permissions before the step.
+    pub before: Permissions,
+    /// Step that was taken.
+    pub step: Step,
+}
+
+#[cfg(feature = "explain")]
+impl TraceStep {
+    /// Permissions that the step granted.
+    pub fn granted(&self) -> Permissions {
+        self.after - self.before
+    }
+
+    /// Permissions that the step removed.
+    pub fn removed(&self) -> Permissions {
+        self.before - self.after
+    }
+}
+
+/// Steps taken to calculate a member's permissions in a channel, created via
+/// [`Calculator::trace`].
+///
+/// [`Calculator::trace`]: ../struct.Calculator.html#method.trace
+#[cfg(feature = "explain")]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Trace {
+    /// Calculated permissions, which are the permissions after the last step.
+    pub permissions: Permissions,
+    /// Steps in the order they were taken.
+    ///
+    /// Steps that only apply in some cases, such as [`Step::Owner`], are only
+    /// included when they were taken.
+    ///
+    /// [`Step::Owner`]: enum.Step.html#variant.Owner
+    pub steps: Vec<TraceStep>,
+}
+
+#[cfg(all(feature = "explain", test))]
+mod tests {
+    use super::{Step, Trace, TraceStep};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{fmt::Debug, hash::Hash};
+    use twilight_model::guild::Permissions;
+
+    assert_fields!(Step::ChannelType: kind);
+    assert_fields!(Step::DependencyDenied: permission);
+    assert_fields!(Trace: permissions, steps);
+    assert_fields!(TraceStep: after, before, step);
+    assert_impl_all!(Step: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(Trace: Clone, Debug, Eq, Hash, PartialEq, Send, Sync);
+    assert_impl_all!(TraceStep: Clone, Copy, Debug, Eq, Hash, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_granted_removed() {
+        let step = TraceStep {
+            after: Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL,
+            before: Permissions::ADD_REACTIONS | Permissions::VIEW_CHANNEL,
+            step: Step::RoleOverwrites,
+        };
+
+        assert_eq!(Permissions::SEND_MESSAGES, step.granted());
+        assert_eq!(Permissions::ADD_REACTIONS, step.removed());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use twilight_model::channel::ChannelType;
+
+        let trace = Trace {
+            permissions: Permissions::VIEW_CHANNEL,
+            steps: vec![TraceStep {
+                after: Permissions::VIEW_CHANNEL,
+                before: Permissions::CONNECT | Permissions::VIEW_CHANNEL,
+                step: Step::ChannelType {
+                    kind: ChannelType::GuildText,
+                },
+            }],
+        };
+
+        let value = serde_json::to_value(&trace).unwrap();
+        assert_eq!("channel_type", value["steps"][0]["step"]["type"]);
+        assert_eq!(trace, serde_json::from_value(value).unwrap());
+    }
+}
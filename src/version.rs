@@ -0,0 +1,160 @@
+//! Versioning calculated permissions for invalidating external caches.
+//!
+//! Permissions only change when roles, channels, or members are updated.
+//! Instead of expiring cached permissions after some amount of time, a
+//! [`DataVersion`] can be incremented whenever such an update is received and
+//! given to the [`Calculator`]. Results calculated with it are stamped with the
+//! version, and are [stale] once the current version has moved past it.
+//!
+//! [`Calculator`]: ../struct.Calculator.html
+//! [`DataVersion`]: struct.DataVersion.html
+//! [stale]: struct.Versioned.html#method.is_stale
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Version of the data that permissions are calculated from.
+///
+/// Versions are opaque counters that only move forward. The default version
+/// is the initial version.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     guild::Permissions,
+///     id::{GuildId, RoleId, UserId},
+/// };
+/// use twilight_permission_calculator::{version::DataVersion, Calculator};
+///
+/// let mut version = DataVersion::default();
+/// let member_roles = &[(RoleId(1), Permissions::SEND_MESSAGES)];
+///
+/// let cached = Calculator::new(GuildId(1), UserId(2), member_roles)
+///     .data_version(version)
+///     .root_versioned()?;
+/// assert!(!cached.is_stale(version));
+///
+/// // A role was updated, so anything calculated before is outdated.
+/// version.increment();
+/// assert!(cached.is_stale(version));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DataVersion(u64);
+
+impl DataVersion {
+    /// Create a version from a raw counter, such as one persisted alongside a
+    /// cache.
+    pub const fn new(version: u64) -> Self {
+        Self(version)
+    }
+
+    /// Raw counter of the version.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Move the version forward, such as when a role, channel, or member
+    /// update is received.
+    pub fn increment(&mut self) {
+        self.0 = self.0.saturating_add(1);
+    }
+
+    /// Version after this one.
+    pub fn next(self) -> Self {
+        Self(self.0.saturating_add(1))
+    }
+}
+
+impl Display for DataVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Calculated value stamped with the version of the data it was calculated
+/// from.
+///
+/// Created via methods such as [`Calculator::root_versioned`].
+///
+/// [`Calculator::root_versioned`]: ../struct.Calculator.html#method.root_versioned
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Versioned<T> {
+    /// Calculated value.
+    pub value: T,
+    /// Version of the data the value was calculated from.
+    pub version: DataVersion,
+}
+
+impl<T> Versioned<T> {
+    /// Stamp a value with a version.
+    pub const fn new(value: T, version: DataVersion) -> Self {
+        Self { value, version }
+    }
+
+    /// Whether the data has been updated since the value was calculated,
+    /// meaning that the value should be calculated again.
+    pub fn is_stale(&self, current_version: DataVersion) -> bool {
+        self.version < current_version
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataVersion, Versioned};
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::guild::Permissions;
+
+    assert_fields!(Versioned<Permissions>: value, version);
+    assert_impl_all!(
+        DataVersion: Clone,
+        Copy,
+        Debug,
+        Default,
+        Display,
+        Eq,
+        Hash,
+        Ord,
+        PartialEq,
+        PartialOrd,
+        Send,
+        Sync
+    );
+    assert_impl_all!(
+        Versioned<Permissions>: Clone,
+        Copy,
+        Debug,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_increment() {
+        let mut version = DataVersion::new(1);
+        version.increment();
+
+        assert_eq!(2, version.get());
+        assert_eq!(DataVersion::new(3), version.next());
+        assert_eq!(
+            DataVersion::new(u64::MAX),
+            DataVersion::new(u64::MAX).next()
+        );
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let versioned = Versioned::new(Permissions::SPEAK, DataVersion::new(2));
+
+        assert!(!versioned.is_stale(DataVersion::new(1)));
+        assert!(!versioned.is_stale(DataVersion::new(2)));
+        assert!(versioned.is_stale(DataVersion::new(3)));
+    }
+}
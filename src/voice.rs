@@ -0,0 +1,245 @@
+//! Checking whether a member can join and stream in a voice channel.
+//!
+//! Joining a voice channel requires the Connect and View Channel permissions,
+//! and a channel with a user limit can't be joined once it's full unless the
+//! member has the Move Members permission, which administrators always have.
+//! [`can_join_voice`] combines these so that bots can predict whether
+//! joining will fail.
+//!
+//! Streaming, also known as going live, additionally requires the Stream
+//! permission in voice channels. The Stream permission doesn't apply to stage
+//! channels, where only speakers can stream and members in the audience are
+//! suppressed; [`check_stream`] handles both.
+//!
+//! [`can_join_voice`]: fn.can_join_voice.html
+//! [`check_stream`]: fn.check_stream.html
+
+use super::CalculatorError;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+use twilight_model::{channel::ChannelType, guild::Permissions};
+
+/// Permissions needed to join a voice channel that isn't full.
+const JOIN: Permissions =
+    Permissions::from_bits_truncate(Permissions::CONNECT.bits() | Permissions::VIEW_CHANNEL.bits());
+
+/// Whether a member with permissions in a voice channel can join it.
+///
+/// `user_limit` is the channel's user limit, where `None` and `Some(0)` mean
+/// that the channel doesn't have one, and `current_members` is how many
+/// members are already connected to it.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::guild::Permissions;
+/// use twilight_permission_calculator::voice;
+///
+/// let permissions = Permissions::CONNECT | Permissions::VIEW_CHANNEL;
+///
+/// assert!(voice::can_join_voice(permissions, Some(5), 4));
+/// assert!(!voice::can_join_voice(permissions, Some(5), 5));
+/// assert!(voice::can_join_voice(permissions | Permissions::MOVE_MEMBERS, Some(5), 5));
+/// ```
+pub fn can_join_voice(
+    permissions: Permissions,
+    user_limit: Option<u64>,
+    current_members: u64,
+) -> bool {
+    if !permissions.contains(JOIN) {
+        return false;
+    }
+
+    match user_limit {
+        Some(limit) if limit > 0 && current_members >= limit => {
+            permissions.contains(Permissions::MOVE_MEMBERS)
+        }
+        _ => true,
+    }
+}
+
+/// Reason that a member can't stream in a channel.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum StreamError {
+    /// Permissions couldn't be calculated.
+    Calculating {
+        /// Reason the permissions couldn't be calculated.
+        source: CalculatorError,
+    },
+    /// Streams can't be started in channels of the type.
+    ChannelType {
+        /// Type of the channel.
+        kind: ChannelType,
+    },
+    /// Member is missing permissions required to stream.
+    MissingPermissions {
+        /// Missing permissions, out of Connect, Stream, and View Channel.
+        missing: Permissions,
+    },
+    /// Member is in the audience of a stage channel rather than a speaker.
+    Suppressed,
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Calculating { .. } => f.write_str("permissions couldn't be calculated"),
+            Self::ChannelType { kind } => f.write_fmt(format_args!(
+                "streams can't be started in channels of type {:?}",
+                kind
+            )),
+            Self::MissingPermissions { .. } => {
+                f.write_str("member is missing permissions required to stream")
+            }
+            Self::Suppressed => f.write_str("member is in the audience of the stage"),
+        }
+    }
+}
+
+impl Error for StreamError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Calculating { source } => Some(source),
+            Self::ChannelType { .. } | Self::MissingPermissions { .. } | Self::Suppressed => None,
+        }
+    }
+}
+
+/// Check whether a member with permissions in a voice or stage channel can
+/// stream in it.
+///
+/// `suppressed` is whether the member is in the audience of a stage channel,
+/// and is ignored for voice channels.
+///
+/// # Examples
+///
+/// ```rust
+/// use twilight_model::{channel::ChannelType, guild::Permissions};
+/// use twilight_permission_calculator::voice::{self, StreamError};
+///
+/// let permissions = Permissions::CONNECT | Permissions::VIEW_CHANNEL;
+///
+/// assert_eq!(
+///     Err(StreamError::MissingPermissions {
+///         missing: Permissions::STREAM,
+///     }),
+///     voice::check_stream(permissions, ChannelType::GuildVoice, false),
+/// );
+/// assert!(voice::check_stream(permissions, ChannelType::GuildStageVoice, false).is_ok());
+/// assert_eq!(
+///     Err(StreamError::Suppressed),
+///     voice::check_stream(permissions, ChannelType::GuildStageVoice, true),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [`StreamError::ChannelType`] if the channel isn't a voice or stage
+/// channel.
+///
+/// Returns [`StreamError::MissingPermissions`] with every missing permission
+/// if the member is missing any that are required.
+///
+/// Returns [`StreamError::Suppressed`] if the member is in the audience of a
+/// stage channel.
+///
+/// [`StreamError::ChannelType`]: enum.StreamError.html#variant.ChannelType
+/// [`StreamError::MissingPermissions`]: enum.StreamError.html#variant.MissingPermissions
+/// [`StreamError::Suppressed`]: enum.StreamError.html#variant.Suppressed
+pub fn check_stream(
+    permissions: Permissions,
+    channel_type: ChannelType,
+    suppressed: bool,
+) -> Result<(), StreamError> {
+    let (required, suppressed) = match channel_type {
+        ChannelType::GuildStageVoice => (JOIN, suppressed),
+        ChannelType::GuildVoice => (JOIN | Permissions::STREAM, false),
+        kind => return Err(StreamError::ChannelType { kind }),
+    };
+    let missing = required - permissions;
+
+    if !missing.is_empty() {
+        Err(StreamError::MissingPermissions { missing })
+    } else if suppressed {
+        Err(StreamError::Suppressed)
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StreamError;
+    use static_assertions::{assert_fields, assert_impl_all};
+    use std::{
+        error::Error,
+        fmt::{Debug, Display},
+        hash::Hash,
+    };
+    use twilight_model::{channel::ChannelType, guild::Permissions};
+
+    assert_fields!(StreamError::Calculating: source);
+    assert_fields!(StreamError::ChannelType: kind);
+    assert_fields!(StreamError::MissingPermissions: missing);
+    assert_impl_all!(
+        StreamError: Clone,
+        Debug,
+        Display,
+        Error,
+        Eq,
+        Hash,
+        PartialEq,
+        Send,
+        Sync
+    );
+
+    #[test]
+    fn test_can_join_voice() {
+        let join = Permissions::CONNECT | Permissions::VIEW_CHANNEL;
+
+        assert!(super::can_join_voice(join, None, 99));
+        assert!(super::can_join_voice(join, Some(0), 99));
+        assert!(super::can_join_voice(join, Some(2), 1));
+        assert!(!super::can_join_voice(join, Some(2), 2));
+        assert!(super::can_join_voice(Permissions::all(), Some(2), 3));
+        assert!(!super::can_join_voice(
+            Permissions::CONNECT | Permissions::MOVE_MEMBERS,
+            None,
+            0
+        ));
+    }
+
+    #[test]
+    fn test_check_stream() {
+        let join = Permissions::CONNECT | Permissions::VIEW_CHANNEL;
+
+        assert!(
+            super::check_stream(join | Permissions::STREAM, ChannelType::GuildVoice, true).is_ok()
+        );
+        assert_eq!(
+            Err(StreamError::MissingPermissions {
+                missing: Permissions::CONNECT | Permissions::STREAM,
+            }),
+            super::check_stream(Permissions::VIEW_CHANNEL, ChannelType::GuildVoice, false),
+        );
+        assert_eq!(
+            Err(StreamError::MissingPermissions {
+                missing: Permissions::CONNECT,
+            }),
+            super::check_stream(
+                Permissions::VIEW_CHANNEL,
+                ChannelType::GuildStageVoice,
+                true
+            ),
+        );
+        assert_eq!(
+            Err(StreamError::ChannelType {
+                kind: ChannelType::GuildText,
+            }),
+            super::check_stream(Permissions::all(), ChannelType::GuildText, false),
+        );
+    }
+}
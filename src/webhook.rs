@@ -0,0 +1,194 @@
+//! Calculating what webhooks can do in channels.
+//!
+//! Webhooks aren't members, so they don't have roles and member overwrites
+//! don't apply to them. Instead, whether a webhook's message can mention
+//! everyone or use external emojis is decided by the permissions of the
+//! `@everyone` role in the webhook's channel. Bots proxying messages through
+//! webhooks need to check those rather than their own permissions.
+//!
+//! Messages sent by webhooks can always have embeds and attachments, so only
+//! the permissions that affect webhooks are calculated.
+
+use super::{role, CalculatorError};
+use twilight_model::{
+    channel::{permission_overwrite::PermissionOverwrite, ChannelType},
+    guild::Permissions,
+    id::{GuildId, RoleId},
+};
+
+/// Permissions affecting the messages of webhooks.
+pub const PERMISSIONS_WEBHOOK: Permissions = Permissions::from_bits_truncate(
+    Permissions::MENTION_EVERYONE.bits() | Permissions::USE_EXTERNAL_EMOJIS.bits(),
+);
+
+/// Calculate the permissions of webhooks in the channels of a guild.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use twilight_model::{
+///     channel::{
+///         permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+///         ChannelType,
+///     },
+///     guild::Permissions,
+///     id::{GuildId, RoleId},
+/// };
+/// use twilight_permission_calculator::webhook::WebhookCalculator;
+///
+/// let guild_roles = &[
+///     (RoleId(1), Permissions::MENTION_EVERYONE | Permissions::VIEW_CHANNEL),
+///     (RoleId(2), Permissions::USE_EXTERNAL_EMOJIS),
+/// ];
+/// let channel_overwrites = &[PermissionOverwrite {
+///     allow: Permissions::empty(),
+///     deny: Permissions::MENTION_EVERYONE,
+///     kind: PermissionOverwriteType::Role(RoleId(1)),
+/// }];
+///
+/// let calculator = WebhookCalculator::new(GuildId(1), guild_roles)?;
+///
+/// // Roles other than `@everyone` don't apply to webhooks.
+/// assert!(calculator
+///     .in_channel(ChannelType::GuildText, channel_overwrites)
+///     .is_empty());
+/// assert!(calculator.can_mention_everyone(ChannelType::GuildText, &[]));
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[must_use = "calculators aren't useful if you don't calculate permissions"]
+pub struct WebhookCalculator {
+    everyone: Permissions,
+    guild_id: GuildId,
+}
+
+impl WebhookCalculator {
+    /// Create a calculator from the roles of a guild.
+    ///
+    /// Guild roles are given as tuples of the role's ID and permissions, and
+    /// must include the `@everyone` role, which has the same ID as the guild.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalculatorError::EveryoneRoleMissing`] if the `@everyone`
+    /// role isn't in the guild's roles.
+    ///
+    /// [`CalculatorError::EveryoneRoleMissing`]: ../enum.CalculatorError.html#variant.EveryoneRoleMissing
+    pub fn new(
+        guild_id: GuildId,
+        guild_roles: &[(RoleId, Permissions)],
+    ) -> Result<Self, CalculatorError> {
+        guild_roles
+            .iter()
+            .find(|(id, _)| id.0 == guild_id.0)
+            .map(|(_, everyone)| Self::from_everyone(guild_id, *everyone))
+            .ok_or(CalculatorError::EveryoneRoleMissing { guild_id })
+    }
+
+    /// Create a calculator from the permissions of the `@everyone` role.
+    pub fn from_everyone(guild_id: GuildId, everyone: Permissions) -> Self {
+        Self { everyone, guild_id }
+    }
+
+    /// Calculate the permissions of webhooks in a channel.
+    ///
+    /// Only the overwrite of the `@everyone` role is applied, and only
+    /// [`PERMISSIONS_WEBHOOK`] are returned.
+    ///
+    /// [`PERMISSIONS_WEBHOOK`]: constant.PERMISSIONS_WEBHOOK.html
+    pub fn in_channel<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> Permissions {
+        role::everyone_in_channel(
+            self.guild_id,
+            self.everyone,
+            channel_type,
+            channel_overwrites,
+        ) & PERMISSIONS_WEBHOOK
+    }
+
+    /// Whether the messages of webhooks in a channel can mention everyone,
+    /// here, and every role.
+    pub fn can_mention_everyone<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> bool {
+        self.in_channel(channel_type, channel_overwrites)
+            .contains(Permissions::MENTION_EVERYONE)
+    }
+
+    /// Whether the messages of webhooks in a channel can use emojis from
+    /// other guilds.
+    pub fn can_use_external_emojis<'b, U: IntoIterator<Item = &'b PermissionOverwrite>>(
+        &self,
+        channel_type: ChannelType,
+        channel_overwrites: U,
+    ) -> bool {
+        self.in_channel(channel_type, channel_overwrites)
+            .contains(Permissions::USE_EXTERNAL_EMOJIS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WebhookCalculator, PERMISSIONS_WEBHOOK};
+    use crate::CalculatorError;
+    use static_assertions::assert_impl_all;
+    use std::fmt::Debug;
+    use twilight_model::{
+        channel::{
+            permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+            ChannelType,
+        },
+        guild::Permissions,
+        id::{GuildId, RoleId, UserId},
+    };
+
+    assert_impl_all!(WebhookCalculator: Clone, Debug, Eq, PartialEq, Send, Sync);
+
+    #[test]
+    fn test_in_channel() {
+        let guild_id = GuildId(1);
+        let calculator = WebhookCalculator::from_everyone(
+            guild_id,
+            Permissions::SEND_MESSAGES | Permissions::VIEW_CHANNEL | PERMISSIONS_WEBHOOK,
+        );
+
+        assert_eq!(
+            PERMISSIONS_WEBHOOK,
+            calculator.in_channel(ChannelType::GuildText, &[]),
+        );
+
+        // Member overwrites and overwrites of other roles don't apply.
+        let others = &[
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: PERMISSIONS_WEBHOOK,
+                kind: PermissionOverwriteType::Member(UserId(2)),
+            },
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: PERMISSIONS_WEBHOOK,
+                kind: PermissionOverwriteType::Role(RoleId(3)),
+            },
+        ];
+        assert!(calculator.can_mention_everyone(ChannelType::GuildText, others));
+
+        let everyone = &[PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::USE_EXTERNAL_EMOJIS,
+            kind: PermissionOverwriteType::Role(RoleId(1)),
+        }];
+        assert!(calculator.can_mention_everyone(ChannelType::GuildText, everyone));
+        assert!(!calculator.can_use_external_emojis(ChannelType::GuildText, everyone));
+
+        assert_eq!(
+            Err(CalculatorError::EveryoneRoleMissing { guild_id }),
+            WebhookCalculator::new(guild_id, &[(RoleId(3), Permissions::all())]),
+        );
+    }
+}